@@ -0,0 +1,45 @@
+//! Suggested answers to the yes/no Tokyo decisions, each with the reason behind it, so a
+//! frontend can offer a default that fits the situation instead of a fixed one.
+
+use crate::{Decision, Game};
+
+/// A suggested answer and why it was suggested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advice {
+    pub answer: bool,
+    pub reason: String, // A short clause, e.g. "2 HP against 3 attackers is too risky"
+}
+
+impl Advice {
+    fn new(answer: bool, reason: String) -> Self {
+        Advice { answer, reason }
+    }
+}
+
+/// Suggests an answer to `decision` if it is a yes/no question about Tokyo: conceding,
+/// yielding or entering. The advice weighs the player's HP against how many monsters
+/// outside Tokyo could attack them there, and whether holding on would win the game.
+pub fn advise(game: &Game, decision: &Decision) -> Option<Advice> {
+    let (player_id, leaving) = match *decision {
+        Decision::ConcedeTokyo { player_id, .. } | Decision::YieldTokyo { player_id, .. } => (player_id, true),
+        Decision::EnterTokyo { player_id, .. } => (player_id, false),
+        _ => return None,
+    };
+    let player = game.get_player(player_id)?;
+    let attackers = game.living_players()
+        .filter(|other| other.id != player_id && !game.tokyo.contains(other.id))
+        .count() as u8;
+    let hold_vp = game.tokyo_hold_vp();
+    let in_danger = player.hp <= attackers.saturating_add(1);
+
+    let advice = if player.victory_points.saturating_add(hold_vp) >= game.max_vp && !in_danger {
+        Advice::new(!leaving, format!("holding Tokyo for one upkeep (+{} VP) would win the game", hold_vp))
+    } else if in_danger {
+        Advice::new(leaving, format!("{} HP against {} attacker{} is too risky", player.hp, attackers, if attackers == 1 { "" } else { "s" }))
+    } else if leaving {
+        Advice::new(false, format!("{} HP is enough to hold on for +{} VP next upkeep", player.hp, hold_vp))
+    } else {
+        Advice::new(true, format!("an empty Tokyo is worth +{} VP now and +{} each upkeep", game.rules.tokyo_entry_vp, hold_vp))
+    };
+    Some(advice)
+}
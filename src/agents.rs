@@ -0,0 +1,155 @@
+//! Built-in computer opponents.
+
+use crate::{Agent, CardEffect, DieResult, Game, PseudoCard, ShopAction, TokyoZone};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The built-in bots, for picking one by name at setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AgentKind {
+    Random,    // Coin flips for every decision
+    Heuristic, // Simple rules of thumb about HP, VP and Tokyo
+}
+
+impl AgentKind {
+    pub const ALL: [AgentKind; 2] = [AgentKind::Random, AgentKind::Heuristic];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgentKind::Random => "random bot",
+            AgentKind::Heuristic => "heuristic bot",
+        }
+    }
+
+    /// A fresh bot of this kind. `seed` drives any randomness it uses.
+    pub fn build(&self, seed: u64) -> Box<dyn Agent> {
+        match self {
+            AgentKind::Random => Box::new(RandomAgent::new(seed)),
+            AgentKind::Heuristic => Box::new(HeuristicAgent),
+        }
+    }
+}
+
+/// Makes every decision at random (among the legal ones). A baseline to beat.
+#[derive(Debug)]
+pub struct RandomAgent {
+    rng: StdRng,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        RandomAgent { rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn choose_dice_to_keep(&mut self, _game: &Game, _player_id: u32, _dice: &[DieResult; 6], _reroll: u8) -> [bool; 6] {
+        let mut keep = [false; 6];
+        for kept in keep.iter_mut() {
+            *kept = self.rng.gen_bool(0.5);
+        }
+        keep
+    }
+
+    fn decide_concede_tokyo(&mut self, _game: &Game, _player_id: u32, _zone: TokyoZone) -> bool {
+        self.rng.gen_bool(0.5)
+    }
+
+    fn decide_yield_tokyo(&mut self, _game: &Game, _player_id: u32, _zone: TokyoZone, _attacker_id: u32) -> bool {
+        self.rng.gen_bool(0.5)
+    }
+
+    fn decide_enter_tokyo(&mut self, _game: &Game, _player_id: u32, _zone: TokyoZone) -> bool {
+        self.rng.gen_bool(0.5)
+    }
+
+    fn choose_shop_action(&mut self, game: &Game, player_id: u32) -> ShopAction {
+        let energy = game.get_player(player_id).map_or(0, |p| p.energy);
+        let mut options = vec![ShopAction::Done];
+        for (slot, card) in game.shop.face_up().iter().enumerate() {
+            if game.card_cost(player_id, card).affordable(energy) {
+                options.push(ShopAction::Buy(slot));
+            }
+        }
+        if energy >= game.sweep_cost() {
+            options.push(ShopAction::Sweep);
+        }
+        let hand = game.get_player(player_id).map_or(0, |p| p.evolutions.len());
+        options.extend((0..hand).map(ShopAction::Evolve));
+        options[self.rng.gen_range(0..options.len())]
+    }
+
+    fn choose_purchases(&mut self, _game: &Game, _player_id: u32, _card: &PseudoCard, affordable: u8) -> u8 {
+        self.rng.gen_range(0..=affordable)
+    }
+}
+
+/// Plays like a cautious beginner: goes for triples and Energy, fights for Tokyo while
+/// healthy, leaves it when hurt, and spends Energy on the priciest card it can afford.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicAgent;
+
+impl HeuristicAgent {
+    /// At or below this HP the bot stops fighting over Tokyo.
+    const RETREAT_HP: u8 = 5;
+}
+
+impl Agent for HeuristicAgent {
+    fn choose_dice_to_keep(&mut self, game: &Game, player_id: u32, dice: &[DieResult; 6], _reroll: u8) -> [bool; 6] {
+        let player = game.get_player(player_id).expect("Player must exist");
+        let in_tokyo = game.tokyo.contains(player_id);
+        let count = |face: DieResult| dice.iter().filter(|&&die| die == face).count();
+        let wants_claws = in_tokyo || game.tokyo.first_open_zone().is_some() || player.hp > HeuristicAgent::RETREAT_HP;
+        let can_heal = !game.overtime && (!in_tokyo || game.rules.heal_in_tokyo);
+        let wants_hearts = can_heal && player.hp <= player.max_hp / 2;
+
+        let mut keep = [false; 6];
+        for (kept, &die) in keep.iter_mut().zip(dice) {
+            *kept = match die {
+                DieResult::One | DieResult::Two => count(die) >= 3,
+                DieResult::Three => count(die) >= 2,
+                DieResult::Energy => game.rules.energy,
+                DieResult::Claw => wants_claws,
+                DieResult::Heart => wants_hearts,
+            };
+        }
+        keep
+    }
+
+    fn decide_concede_tokyo(&mut self, game: &Game, player_id: u32, _zone: TokyoZone) -> bool {
+        game.get_player(player_id).is_some_and(|p| p.hp < HeuristicAgent::RETREAT_HP)
+    }
+
+    fn decide_yield_tokyo(&mut self, game: &Game, player_id: u32, _zone: TokyoZone, _attacker_id: u32) -> bool {
+        game.get_player(player_id).is_some_and(|p| p.hp <= HeuristicAgent::RETREAT_HP)
+    }
+
+    fn decide_enter_tokyo(&mut self, game: &Game, player_id: u32, _zone: TokyoZone) -> bool {
+        game.get_player(player_id).is_some_and(|p| p.hp > HeuristicAgent::RETREAT_HP)
+    }
+
+    fn choose_shop_action(&mut self, game: &Game, player_id: u32) -> ShopAction {
+        let player = game.get_player(player_id).expect("Player must exist");
+        if !player.evolutions.is_empty() {
+            return ShopAction::Evolve(0); // Evolutions are free, so play them as soon as possible
+        }
+        let energy = player.energy;
+        game.shop.face_up().iter()
+            .enumerate()
+            .map(|(slot, card)| (slot, game.card_cost(player_id, card)))
+            .filter(|(_, price)| price.affordable(energy))
+            .max_by_key(|(_, price)| price.total)
+            .map_or(ShopAction::Done, |(slot, _)| ShopAction::Buy(slot))
+    }
+
+    fn choose_purchases(&mut self, game: &Game, player_id: u32, card: &PseudoCard, affordable: u8) -> u8 {
+        match (card.effect, game.get_player(player_id)) {
+            // Only heal as far as it helps
+            (CardEffect::Heal(amount), Some(player)) if amount > 0 => {
+                affordable.min(player.max_hp.saturating_sub(player.hp).div_ceil(amount))
+            }
+            _ => affordable,
+        }
+    }
+}
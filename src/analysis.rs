@@ -0,0 +1,237 @@
+//! Bulk replay analysis: statistics over a whole directory of recorded games, such as a
+//! self-play dataset of thousands of replays. The files are shared out between worker
+//! threads. Each file is parsed straight from disk as it is read, played through the
+//! engine, counted and dropped, so memory use stays at about one game per thread however
+//! large the directory is.
+
+use crate::{CardStat, Game, GameEvent, Replay, Victory};
+use serde::Serialize;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Most skipped files an analysis names; any more are only counted.
+pub const MAX_LISTED_FAILURES: usize = 20;
+
+/// How one seat of the table did, over every game that had it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeatStat {
+    pub seat: usize, // From 1, in turn order
+    pub games: usize,
+    pub wins: usize,
+}
+
+/// How one monster did, over every game it was played in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MonsterStat {
+    pub monster: String,
+    pub games: usize,
+    pub wins: usize,
+}
+
+/// What a directory of replays adds up to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReplayAnalysis {
+    pub files: usize,          // Files looked at
+    pub games: usize,          // Replays read and played through
+    pub points_wins: usize,    // Games won on VP
+    pub last_standing: usize,  // Games won by the last monster standing
+    pub no_survivors: usize,
+    pub unfinished: usize,     // Recordings that stop before the game ends
+    pub total_turns: u64,
+    pub longest: u32,          // Turns in the longest game
+    pub seats: Vec<SeatStat>,       // First seat first
+    pub monsters: Vec<MonsterStat>, // Most played first
+    pub cards: Vec<CardStat>,       // Most bought first
+    pub skipped: usize,             // Files that weren't replays or broke the rules
+    pub failures: Vec<(String, String)>, // (file, reason) for the first `MAX_LISTED_FAILURES` skipped, by path
+}
+
+impl ReplayAnalysis {
+    pub fn average_turns(&self) -> f64 {
+        self.total_turns as f64 / self.games.max(1) as f64
+    }
+
+    /// Reads, checks and counts the replay in `path`, or notes why it was skipped.
+    fn add_file(&mut self, path: &Path) {
+        self.files += 1;
+        let replay = File::open(path).map_err(|error| error.to_string())
+            .and_then(|file| Replay::from_reader(BufReader::new(file)).map_err(|error| format!("not a replay: {}", error)));
+        match replay.and_then(|replay| replay.verify().map_err(|violation| format!("breaks the rules at {}", violation))) {
+            Ok(game) => self.add_game(&game),
+            Err(reason) => {
+                self.skipped += 1;
+                self.failures.push((path.display().to_string(), reason));
+                self.failures.sort();
+                self.failures.truncate(MAX_LISTED_FAILURES);
+            }
+        }
+    }
+
+    fn add_game(&mut self, game: &Game) {
+        self.games += 1;
+        self.total_turns += game.turn as u64;
+        self.longest = self.longest.max(game.turn);
+        let victory = game.victory();
+        match victory {
+            Some(Victory::Points { .. }) => self.points_wins += 1,
+            Some(Victory::LastStanding { .. }) => self.last_standing += 1,
+            Some(Victory::NoSurvivors) => self.no_survivors += 1,
+            None => self.unfinished += 1,
+        }
+        // Winners are whoever placed first in a finished game, as in tournament reports
+        let winners: Vec<u32> = match victory {
+            Some(_) => game.final_placements().into_iter().filter(|&(_, placement)| placement == 1).map(|(id, _)| id).collect(),
+            None => Vec::new(),
+        };
+
+        for (seat, player) in game.players.iter().enumerate() {
+            let won = winners.contains(&player.id) as usize;
+            if self.seats.len() <= seat {
+                self.seats.push(SeatStat { seat: seat + 1, games: 0, wins: 0 });
+            }
+            self.seats[seat].games += 1;
+            self.seats[seat].wins += won;
+            if let Some(monster) = player.monster {
+                let index = match self.monsters.iter().position(|stat| stat.monster == monster.name()) {
+                    Some(index) => index,
+                    None => {
+                        self.monsters.push(MonsterStat { monster: monster.name().to_string(), games: 0, wins: 0 });
+                        self.monsters.len() - 1
+                    }
+                };
+                self.monsters[index].games += 1;
+                self.monsters[index].wins += won;
+            }
+        }
+        for timed in &game.history {
+            if let GameEvent::CardPurchased { player_id, ref card, .. } = timed.event {
+                let index = match self.cards.iter().position(|stat| stat.card == *card) {
+                    Some(index) => index,
+                    None => {
+                        self.cards.push(CardStat { card: card.clone(), bought: 0, bought_by_winners: 0 });
+                        self.cards.len() - 1
+                    }
+                };
+                self.cards[index].bought += 1;
+                self.cards[index].bought_by_winners += winners.contains(&player_id) as usize;
+            }
+        }
+    }
+
+    /// Adds what another worker counted.
+    fn merge(&mut self, other: ReplayAnalysis) {
+        self.files += other.files;
+        self.games += other.games;
+        self.points_wins += other.points_wins;
+        self.last_standing += other.last_standing;
+        self.no_survivors += other.no_survivors;
+        self.unfinished += other.unfinished;
+        self.total_turns += other.total_turns;
+        self.longest = self.longest.max(other.longest);
+        for stat in other.seats {
+            match self.seats.iter_mut().find(|known| known.seat == stat.seat) {
+                Some(known) => {
+                    known.games += stat.games;
+                    known.wins += stat.wins;
+                }
+                None => self.seats.push(stat),
+            }
+        }
+        for stat in other.monsters {
+            match self.monsters.iter_mut().find(|known| known.monster == stat.monster) {
+                Some(known) => {
+                    known.games += stat.games;
+                    known.wins += stat.wins;
+                }
+                None => self.monsters.push(stat),
+            }
+        }
+        for stat in other.cards {
+            match self.cards.iter_mut().find(|known| known.card == stat.card) {
+                Some(known) => {
+                    known.bought += stat.bought;
+                    known.bought_by_winners += stat.bought_by_winners;
+                }
+                None => self.cards.push(stat),
+            }
+        }
+        self.skipped += other.skipped;
+        self.failures.extend(other.failures);
+        self.failures.sort();
+        self.failures.truncate(MAX_LISTED_FAILURES);
+    }
+}
+
+/// Analyses every file in `dir` (not its subdirectories) as a replay, sharing the files
+/// out between `threads` worker threads (at least one). Files that aren't replays, or
+/// whose actions break the rules, are skipped and listed. The result doesn't depend on
+/// the number of threads.
+pub fn analyze_replays(dir: impl AsRef<Path>, threads: usize) -> io::Result<ReplayAnalysis> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let next = AtomicUsize::new(0);
+    let parts: Vec<ReplayAnalysis> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| scope.spawn(|| {
+                let mut part = ReplayAnalysis::default();
+                while let Some(path) = paths.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    part.add_file(path);
+                }
+                part
+            }))
+            .collect();
+        workers.into_iter().map(|worker| worker.join().expect("Analysis workers don't panic")).collect()
+    });
+
+    let mut analysis = ReplayAnalysis::default();
+    for part in parts {
+        analysis.merge(part);
+    }
+    analysis.seats.sort_by_key(|stat| stat.seat);
+    analysis.monsters.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.monster.cmp(&b.monster)));
+    analysis.cards.sort_by(|a, b| b.bought.cmp(&a.bought).then_with(|| a.card.cmp(&b.card)));
+    Ok(analysis)
+}
+
+impl fmt::Display for ReplayAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = |part: usize, whole: usize| part as f64 * 100.0 / whole.max(1) as f64;
+        writeln!(f, "{} replays analysed out of {} files ({} skipped), {:.1} turns on average, longest {} turns",
+                 self.games, self.files, self.skipped, self.average_turns(), self.longest)?;
+        writeln!(f, "  Endings: {} on VP, {} last monster standing, {} with no survivors, {} unfinished",
+                 self.points_wins, self.last_standing, self.no_survivors, self.unfinished)?;
+        for stat in &self.seats {
+            writeln!(f, "  Seat {}: {:>5.1}% wins over {} games", stat.seat, percent(stat.wins, stat.games), stat.games)?;
+        }
+        for stat in &self.monsters {
+            writeln!(f, "  {:<12} {:>5.1}% wins over {} games", stat.monster, percent(stat.wins, stat.games), stat.games)?;
+        }
+        if !self.cards.is_empty() {
+            writeln!(f, "  Most bought cards:")?;
+            for stat in self.cards.iter().take(10) {
+                writeln!(f, "    {:<20} {:>6} bought, {:>5.1}% by the winner", stat.card, stat.bought, percent(stat.bought_by_winners, stat.bought))?;
+            }
+        }
+        if !self.failures.is_empty() {
+            writeln!(f, "  Skipped:")?;
+            for (path, reason) in &self.failures {
+                writeln!(f, "    {}: {}", path, reason)?;
+            }
+            if self.skipped > self.failures.len() {
+                writeln!(f, "    ...and {} more", self.skipped - self.failures.len())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2621 @@
+//! Interactive hot-seat King of Tokyo on the terminal, built on the `kingoftokyo` engine.
+
+use kingoftokyo::{
+    advise, analyze_replays, Locale, parse_player_count, ApiServer, validate_player_names, Agent, AgentKind, BotIdentity, BotPack, BotRegistration, ClientMessage, compare, ConfigError, Connection, DamageSource, Decision, DieResult, Engine, FileStorage, VpSource, Game, GameConfig, GameEvent, HealthRegistry, HostMessage, Leaderboard,
+    narrate_in, Arg, Message, MessageKey, simulate, simulate_from, Monster, PlayerController, PredictionLog, Profile, Progress, PseudoCard, DataDir, RatedBot, Replay, RuleId, Series, SeriesFormat, ShopAction, Storage, Theme, TieBreak, TokyoZone, TournamentReport, TurnPhase, Victory, PREDICTION_GAMES, PRESETS, PROTOCOL_VERSION,
+};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Write};
+use std::fs::{File, OpenOptions};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, UNIX_EPOCH};
+
+// --- 1. Reading Input ---
+
+/// Set once stdin reaches end-of-file (Ctrl-D, a closed pipe). The main loop checks it
+/// after every turn and stops with the final tally instead of auto-answering every prompt.
+static INPUT_CLOSED: AtomicBool = AtomicBool::new(false);
+
+/// Set by Ctrl-C (or SIGTERM) once `catch_interrupts` has run, and cleared by whoever deals
+/// with it: a prompt during play offers to save, write a bug report or stop, and a server
+/// checkpoints its tables and exits.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Lines typed at the terminal, read on a thread of their own so a prompt can notice Ctrl-C
+/// while it waits. Disconnected once stdin is closed.
+static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+/// How fast events play out on screen, set by `--pace`, `--pause` and `--step`. Applies to
+/// everything the console shows, so bot-only exhibition games and replays scroll the same way.
+static PACE: OnceLock<Pace> = OnceLock::new();
+
+/// Where `--narrate` sends one plain sentence per event, for screen readers and TTS tools.
+static NARRATION: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// The language picked with `--lang`, overriding the profile's.
+static LANGUAGE: OnceLock<Locale> = OnceLock::new();
+
+/// Rolls read from a `--practice` scenario file, one line of faces per roll, used up in order.
+static SCENARIO: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Where saves, profiles, bot packs and standings are kept; see `--data-dir`.
+static DATA_DIR: OnceLock<DataDir> = OnceLock::new();
+
+/// The preferences loaded with `--profile`: keys, theme, hints and advisor.
+static PROFILE: OnceLock<Profile> = OnceLock::new();
+
+/// Identities the bot seats took on at setup, by player name, for emotes.
+static BOT_IDENTITIES: Mutex<Vec<(String, BotIdentity)>> = Mutex::new(Vec::new());
+
+/// The optional subsystems (narration, profile, bot pack, standings) and whether they still work.
+static HEALTH: Mutex<HealthRegistry> = Mutex::new(HealthRegistry::new());
+
+fn profile() -> &'static Profile {
+    PROFILE.get_or_init(Profile::default)
+}
+
+fn data_dir() -> &'static DataDir {
+    DATA_DIR.get_or_init(|| DataDir::locate(None))
+}
+
+/// The file a document called `name` in `collection` of the data directory is kept in, for
+/// telling the player where to look.
+fn data_file(collection: &str, name: &str) -> String {
+    data_dir().root().join(collection).join(format!("{}.json", name)).display().to_string()
+}
+
+/// The language events are shown and narrated in, prompts and reports are worded in, and
+/// numbers and durations are written in: `--lang`'s, else the loaded profile's when it's one that ships, else the
+/// environment's (LC_ALL, LC_NUMERIC or LANG, e.g. "es_ES.UTF-8") when it ships, else English.
+fn locale() -> Locale {
+    let env_tag = || ["LC_ALL", "LC_NUMERIC", "LANG"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    LANGUAGE.get().copied()
+        .or_else(|| PROFILE.get().filter(|profile| !profile.name.is_empty()).and_then(|profile| Locale::from_tag(&profile.language)))
+        .or_else(|| env_tag().and_then(|tag| Locale::from_tag(tag.get(..2)?)))
+        .unwrap_or(Locale::English)
+}
+
+/// The catalog's `key` in the console's language, with `args` filled in.
+fn text(key: MessageKey, args: &[(&'static str, Arg)]) -> String {
+    locale().render(&Message { key, args: args.to_vec() })
+}
+
+/// A name, or text already put into words, for a placeholder of `text`.
+fn named(value: impl ToString) -> Arg {
+    Arg::Name(value.to_string())
+}
+
+/// Drops emoji (and the space after them) when the profile asks for the ASCII theme.
+fn themed(text: &str) -> String {
+    if profile().theme == Theme::Emoji {
+        return text.to_string();
+    }
+    let is_icon = |c: char| matches!(c as u32, 0x2300..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF | 0xFE0F | 0x200D);
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_icon(c) {
+            while chars.peek().is_some_and(|&next| is_icon(next)) {
+                chars.next();
+            }
+            while chars.peek() == Some(&' ') {
+                chars.next();
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `println!` through the profile's theme.
+macro_rules! say {
+    ($($arg:tt)*) => { println!("{}", themed(&format!($($arg)*))) };
+}
+
+/// Switches off the optional subsystem `name` after it failed with `error`, warning once
+/// on stderr, which keeps stdout clean for `--json`.
+fn degrade(name: &str, error: impl std::fmt::Display) {
+    let warning = HEALTH.lock().expect("Health lock is never poisoned").disable(name, &error.to_string());
+    if let Some(warning) = warning {
+        eprintln!("{}", themed(&format!("⚠️  Warning: {}; the game carries on without it.", warning)));
+    }
+}
+
+fn is_enabled(name: &str) -> bool {
+    HEALTH.lock().expect("Health lock is never poisoned").is_enabled(name)
+}
+
+/// Makes Ctrl-C and SIGTERM set `INTERRUPTED` instead of killing the process. Only called
+/// where something deals with it (interactive play and the servers), so batch runs like
+/// `--simulate` still stop at once. Elsewhere than Unix, Ctrl-C stops the process as before.
+#[cfg(unix)]
+fn catch_interrupts() {
+    extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+    }
+    extern "C" fn interrupt(_: i32) {
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+    const SIGINT: i32 = 2;
+    const SIGTERM: i32 = 15;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+    unsafe {
+        signal(SIGINT, interrupt);
+        signal(SIGTERM, interrupt);
+    }
+}
+
+#[cfg(not(unix))]
+fn catch_interrupts() {}
+
+fn read_line_input(prompt: &str) -> String {
+    read_line_interruptible(prompt).unwrap_or_else(|| {
+        // Nothing is in play to save, or Ctrl-C came again while the interrupt menu was up
+        say!("\n    {}", text(MessageKey::Interrupted, &[]));
+        std::process::exit(130)
+    })
+}
+
+/// Like `read_line_input`, but None if Ctrl-C is pressed while waiting, for prompts that
+/// have a game to offer to save.
+fn read_line_interruptible(prompt: &str) -> Option<String> {
+    print!("{}", themed(prompt));
+    // Flush the output buffer to ensure the prompt is displayed before input
+    io::stdout().flush().expect("Failed to flush stdout");
+    let lines = LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let mut line = String::new();
+            // 0 bytes read means stdin is closed; dropping the sender tells the prompt
+            match io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if sender.send(line).is_err() => break,
+                Ok(_) => {}
+            }
+        });
+        Mutex::new(receiver)
+    });
+    let lines = lines.lock().unwrap_or_else(PoisonError::into_inner);
+    loop {
+        if INTERRUPTED.swap(false, Ordering::SeqCst) {
+            println!();
+            return None;
+        }
+        match lines.recv_timeout(Duration::from_millis(100)) {
+            Ok(line) => return Some(line.trim().to_string()),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                if !INPUT_CLOSED.swap(true, Ordering::SeqCst) {
+                    say!("\n    ⚠️  {}", text(MessageKey::InputClosed, &[]));
+                }
+                return Some(String::new());
+            }
+        }
+    }
+}
+
+/// Writes a bug report to `<data dir>/bug-reports/`: the build, the command line, the
+/// optional subsystems' health, the game as a save (which replays it from the seed) and as
+/// it stood when this turn began. Prints where it went.
+fn write_bug_report(game: &Game, turn_start: &str) {
+    let name = format!("report-{}", std::time::SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()));
+    let capabilities = Engine::capabilities();
+    let health: Vec<String> = HEALTH.lock().expect("Health lock is never poisoned").subsystems().iter().map(|subsystem| subsystem.to_string()).collect();
+    let report = serde_json::json!({
+        "engine_version": capabilities.engine_version,
+        "features": capabilities.features,
+        "args": std::env::args().skip(1).collect::<Vec<_>>(),
+        "subsystems": health,
+        "game": game.to_json(),
+        "turn_start": turn_start,
+    });
+    let json = serde_json::to_string_pretty(&report).expect("Bug reports are always serializable");
+    let path = named(data_file(BUG_REPORTS, &name));
+    match data_dir().storage().put(BUG_REPORTS, &name, &json) {
+        Ok(()) => say!("    🐞 {}", text(MessageKey::BugReportWritten, &[("path", path)])),
+        Err(error) => println!("    {}", text(MessageKey::BugReportFailed, &[("path", path), ("error", named(error))])),
+    }
+}
+
+/// Collection of the data directory bug reports go in.
+const BUG_REPORTS: &str = "bug-reports";
+
+/// Writes `contents` to `path`, creating the folders it goes in first.
+fn write_creating_dirs(path: &str, contents: &str) -> io::Result<()> {
+    if let Some(parent) = Path::new(path).parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+/// Reads an option value from the command line as JSON (15, true, null), or as a string
+/// if it isn't JSON.
+fn parse_option_value(value: &str) -> serde_json::Value {
+    serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
+/// Formats dice with their 1-based positions, e.g. "[1] One  [2] Claw ...", marking kept dice.
+fn format_dice(dice: &[DieResult; 6], keep: &[bool; 6]) -> String {
+    dice.iter()
+        .zip(keep)
+        .enumerate()
+        .map(|(i, (die, &kept))| format!("[{}] {:?}{}", i + 1, die, if kept { "*" } else { "" }))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Parses die faces like "1 1 c h" or "one,claw,heart": 1, 2, 3, e(nergy), c(law) or h(eart).
+/// Returns None if any part isn't a face.
+fn parse_faces(input: &str) -> Option<Vec<DieResult>> {
+    input.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.to_ascii_lowercase().as_str() {
+            "1" | "one" => Some(DieResult::One),
+            "2" | "two" => Some(DieResult::Two),
+            "3" | "three" => Some(DieResult::Three),
+            "e" | "energy" => Some(DieResult::Energy),
+            "c" | "claw" => Some(DieResult::Claw),
+            "h" | "heart" => Some(DieResult::Heart),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses a keep answer like "1 3 5" or "1,3,5" into keep flags. Returns None if any
+/// part isn't a die position from 1 to 6.
+fn parse_keep(input: &str) -> Option<[bool; 6]> {
+    let mut keep = [false; 6];
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty()) {
+        match part.parse::<usize>() {
+            Ok(n) if (1..=6).contains(&n) => keep[n - 1] = true,
+            _ => return None,
+        }
+    }
+    Some(keep)
+}
+
+// --- 2. Console Play ---
+
+/// Playback pacing for events shown on the console.
+#[derive(Debug, Clone, Copy, Default)]
+struct Pace {
+    delay: Duration,       // After every event
+    major_pause: Duration, // Extra wait after a major event (see `GameEvent::is_major`)
+    step: bool,            // Wait for Enter before each new turn
+}
+
+impl Pace {
+    /// Reads `--pace <ms>`, `--pause <ms>` and `--step`; None if none of them were given.
+    fn from_args(args: &[String]) -> Option<Self> {
+        let millis = |flag: &str| {
+            let i = args.iter().position(|arg| arg == flag)?;
+            args.get(i + 1).and_then(|ms| ms.parse().ok()).map(Duration::from_millis)
+        };
+        let pace = Pace {
+            delay: millis("--pace").unwrap_or_default(),
+            major_pause: millis("--pause").unwrap_or_default(),
+            step: args.iter().any(|arg| arg == "--step"),
+        };
+        (!pace.delay.is_zero() || !pace.major_pause.is_zero() || pace.step).then_some(pace)
+    }
+
+    fn before(&self, event: &GameEvent) {
+        if self.step && matches!(event, GameEvent::TurnStarted { .. }) && !INPUT_CLOSED.load(Ordering::SeqCst) {
+            read_line_input(&format!("\n{}", text(MessageKey::PromptNextTurn, &[])));
+        }
+    }
+
+    fn after(&self, event: &GameEvent) {
+        io::stdout().flush().expect("Failed to flush stdout");
+        let wait = if event.is_major() { self.delay + self.major_pause } else { self.delay };
+        std::thread::sleep(wait);
+    }
+}
+
+/// Where `save` and `load` go when no file is named: a file in the data directory's saves.
+fn default_save_file() -> String {
+    data_dir().save_file("kingoftokyo-save.json").display().to_string()
+}
+
+/// Plays every seat from one terminal: prints what happened since the last prompt, then
+/// asks whoever the engine is waiting on.
+struct Console {
+    shown: usize,  // Events of the current game already printed
+    rounds: u32,   // Rounds started so far, so the spectator board skips the first
+    claws: u8,     // Claws in the dice being resolved, for the enter-Tokyo prompt
+    checkpoint: String,   // The game as it stood when the current turn began, for `save`
+    loaded: Option<Game>, // A game picked with `load`, to switch to once this turn is over
+    local: bool,          // Whether `save`, `load`, `undo` and `redo` are offered (not in network games)
+    undo_limit: u8,       // The game's `rules.undo_limit`; `undo` and `redo` are refused at 0
+    rewind: Option<Rewind>, // `undo` or `redo` typed while answering the decision in progress
+    quit: bool,             // `quit` was typed; the game stops once this turn is over
+    seats: usize,           // Players answering at this console; the keyboard is handed over when there are several
+    decider: Option<(u32, u32)>, // Turn and player of the last question asked here
+}
+
+/// Something typed at a prompt that isn't an answer to the question.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Save(String), // File to save to
+    Load(String), // File to load from
+    Undo,
+    Redo,
+    Status, // Everyone's HP, VP and Energy
+    Board,  // Who is in Tokyo
+    Review, // This turn's events again, slowly, with the rules behind them
+    Rule(String), // One entry of the rules table by id, or the rules behind this turn if blank
+    Help,
+    Quit,   // Stop the game and show the final tally
+}
+
+impl Command {
+    /// Reads a command from a line of input, or None if the line is an answer instead.
+    fn parse(input: &str) -> Option<Command> {
+        let (word, rest) = input.split_once(' ').unwrap_or((input, ""));
+        // Commands may be typed with a leading colon, e.g. ":review"
+        let word = word.strip_prefix(':').unwrap_or(word);
+        let path = || if rest.trim().is_empty() { default_save_file() } else { rest.trim().to_string() };
+        let command = match word.to_lowercase().as_str() {
+            "save" => Command::Save(path()),
+            "load" => Command::Load(path()),
+            "rule" => Command::Rule(rest.trim().to_string()),
+            _ if !rest.trim().is_empty() => return None,
+            "undo" => Command::Undo,
+            "redo" => Command::Redo,
+            "status" => Command::Status,
+            "board" => Command::Board,
+            "review" => Command::Review,
+            "help" => Command::Help,
+            "quit" => Command::Quit,
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    /// Whether the command only makes sense when the game runs on this machine, so not in
+    /// network games.
+    fn is_local(&self) -> bool {
+        matches!(self, Command::Save(_) | Command::Load(_) | Command::Undo | Command::Redo | Command::Quit)
+    }
+}
+
+/// Taking a decision back, or putting it back again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rewind {
+    Undo,
+    Redo,
+}
+
+impl Console {
+    fn new() -> Self {
+        Console {
+            shown: 0, rounds: 0, claws: 0, checkpoint: String::new(), loaded: None, local: true, undo_limit: 0, rewind: None, quit: false,
+            seats: 1, decider: None,
+        }
+    }
+
+    /// Says whose decision comes next when someone else answered the last one this turn,
+    /// e.g. a Tokyo occupant asked to yield during the attacker's turn, so players sharing
+    /// the keyboard know to pass it. A new turn's heading already says whose it is.
+    fn hand_over(&mut self, game: &Game, player_id: u32) {
+        let handed = self.decider.is_some_and(|(turn, decider)| turn == game.turn && decider != player_id);
+        if self.seats > 1 && handed && !self.quit && self.loaded.is_none() {
+            let name = game.get_player(player_id).map_or("?", |p| p.name.as_str());
+            say!("\n    🎮 {}", text(MessageKey::HandOver, &[("player", named(name))]));
+        }
+        self.decider = Some((game.turn, player_id));
+    }
+
+    /// Asks a question about `game`, handling any `Command` typed instead of an answer.
+    /// Once a game has been loaded or quit the rest of the current turn is thrown away, and
+    /// after `undo` or `redo` the decision in progress is, so every remaining question gets
+    /// the default answer without being asked.
+    fn ask(&mut self, game: &Game, prompt: &str) -> String {
+        loop {
+            if self.loaded.is_some() || self.rewind.is_some() || self.quit {
+                return String::new();
+            }
+            let Some(input) = read_line_interruptible(prompt) else {
+                self.interrupted(game);
+                continue;
+            };
+            match Command::parse(&input) {
+                Some(command) if !command.is_local() || self.local => self.run(game, command),
+                _ => return input,
+            }
+        }
+    }
+
+    /// Carries out a command typed at a prompt.
+    fn run(&mut self, game: &Game, command: Command) {
+        match command {
+            Command::Save(path) => match write_creating_dirs(&path, &self.checkpoint) {
+                Ok(()) => say!("    💾 {}", text(MessageKey::Saved, &[("path", named(path))])),
+                Err(error) => println!("    {}", text(MessageKey::SaveFailed, &[("path", named(path)), ("error", named(error))])),
+            },
+            Command::Load(path) => match Game::load_from_file(&path) {
+                Ok(game) => {
+                    say!("    📂 {}", text(MessageKey::Loaded, &[("path", named(&path)), ("number", Arg::Number(game.turn + 1))]));
+                    self.loaded = Some(game);
+                }
+                Err(error) => println!("    {}", text(MessageKey::LoadFailed, &[("path", named(path)), ("error", named(error))])),
+            },
+            Command::Undo | Command::Redo if self.undo_limit == 0 => println!("    {}", text(MessageKey::UndoOff, &[])),
+            Command::Undo => self.rewind = Some(Rewind::Undo),
+            Command::Redo => self.rewind = Some(Rewind::Redo),
+            Command::Status => print_status(game),
+            Command::Board => print_board(game),
+            Command::Review => review_turn(game),
+            Command::Rule(id) => print_rule(game, &id),
+            Command::Help => self.print_help(),
+            Command::Quit => {
+                say!("    🚪 {}", text(MessageKey::Stopping, &[]));
+                self.quit = true;
+            }
+        }
+    }
+
+    /// Offers a way out after Ctrl-C: save the game and stop, write a bug report, stop, or
+    /// carry on. The save is the game as it stood at the start of this turn, as for `save`.
+    /// Network games have nothing to save here, so stopping leaves the game. Ctrl-C again
+    /// stops at once.
+    fn interrupted(&mut self, game: &Game) {
+        let prompt = if self.local { MessageKey::PromptInterrupted } else { MessageKey::PromptInterruptedOnline };
+        loop {
+            let input = read_line_input(&format!("    ⏸️  {}", text(prompt, &[])));
+            match input.to_lowercase().as_str() {
+                "s" if self.local => match write_creating_dirs(&default_save_file(), &self.checkpoint) {
+                    Ok(()) => {
+                        say!("    💾 {}", text(MessageKey::SavedToResume, &[("path", named(default_save_file()))]));
+                        self.run(game, Command::Quit);
+                        return;
+                    }
+                    Err(error) => println!("    {}", text(MessageKey::SaveFailed, &[("path", named(default_save_file())), ("error", named(error))])),
+                },
+                "b" => write_bug_report(game, &self.checkpoint),
+                "q" if self.local => {
+                    self.run(game, Command::Quit);
+                    return;
+                }
+                "q" => {
+                    say!("    🚪 {}", text(MessageKey::Leaving, &[]));
+                    self.quit = true;
+                    return;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    /// Lists the commands that work at this console's prompts.
+    fn print_help(&self) {
+        // The commands themselves are typed the same in every language
+        let command = |command: &str, key: MessageKey| println!("      {:<13} {}", command, text(key, &[]));
+        println!("    {}", text(MessageKey::HelpIntro, &[]));
+        command("status", MessageKey::HelpStatus);
+        command("board", MessageKey::HelpBoard);
+        command("review", MessageKey::HelpReview);
+        command("rule [id]", MessageKey::HelpRule);
+        if self.local {
+            command("save [file]", MessageKey::HelpSave);
+            command("load [file]", MessageKey::HelpLoad);
+            if self.undo_limit > 0 {
+                command("undo / redo", MessageKey::HelpUndo);
+            }
+            command("quit", MessageKey::HelpQuit);
+        }
+        command("help", MessageKey::HelpHelp);
+    }
+
+    /// Asks a yes/no question with the profile's keys, the default shown in capitals.
+    fn confirm(&mut self, game: &Game, question: &str, default: bool) -> bool {
+        let keys = &profile().keys;
+        let choices = if default {
+            format!("{}/{}", keys.yes.to_uppercase(), keys.no)
+        } else {
+            format!("{}/{}", keys.yes, keys.no.to_uppercase())
+        };
+        let input = self.ask(game, &format!("{} ({}): ", question, choices));
+        if default {
+            !input.eq_ignore_ascii_case(&keys.no)
+        } else {
+            input.eq_ignore_ascii_case(&keys.yes)
+        }
+    }
+
+    /// Asks a yes/no question about Tokyo. With the profile's advisor on, the default is the
+    /// one `advise` suggests instead of `default`, and with hints on the prompt says why.
+    fn confirm_tokyo(&mut self, game: &Game, decision: Decision, question: &str, default: bool) -> bool {
+        let advice = if profile().advisor { advise(game, &decision) } else { None };
+        match advice {
+            Some(advice) if profile().hints => {
+                let answer = text(if advice.answer { MessageKey::AnswerYes } else { MessageKey::AnswerNo }, &[]);
+                let hint = text(MessageKey::PromptAdvice, &[("answer", named(answer)), ("reason", named(advice.reason))]);
+                self.confirm(game, &format!("{} (💡 {})", question, hint), advice.answer)
+            }
+            Some(advice) => self.confirm(game, question, advice.answer),
+            None => self.confirm(game, question, default),
+        }
+    }
+
+    /// Prints every event logged since the last call, and narrates it if `--narrate` is on.
+    fn show_events(&mut self, game: &Game) {
+        if self.loaded.is_some() || self.quit {
+            return;
+        }
+        while let Some(timed) = game.history.get(self.shown) {
+            self.shown += 1;
+            let pace = PACE.get();
+            if let Some(pace) = pace {
+                pace.before(&timed.event);
+            }
+            self.show_event(game, &timed.event);
+            emote(game, &timed.event);
+            if let Some(pace) = pace {
+                pace.after(&timed.event);
+            }
+            if let (Some(narration), Some(line)) = (NARRATION.get(), narrate_in(locale(), game, &timed.event)) {
+                if is_enabled("narration") {
+                    let mut file = narration.lock().expect("Narration file lock is never poisoned");
+                    // A TTS reader going away shouldn't stop the game, only the narration
+                    if let Err(error) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+                        degrade("narration", error);
+                    }
+                }
+            }
+        }
+    }
+
+    fn show_event(&mut self, game: &Game, event: &GameEvent) {
+        let name = |id: u32| game.get_player(id).map_or("?", |p| p.name.as_str());
+        if let GameEvent::DiceResolved { dice, .. } = *event {
+            self.claws = dice.iter().filter(|&&die| die == DieResult::Claw).count() as u8;
+        }
+        // The decorated lines below are English; other languages get the catalog's sentence
+        // for every event it has one for
+        let locale = locale();
+        if locale != Locale::English {
+            if let Some(line) = narrate_in(locale, game, event) {
+                println!("    {}", line);
+                return;
+            }
+        }
+        match *event {
+            GameEvent::TurnStarted { turn, player_id, hp, victory_points } => {
+                println!("\n---------------------------------------------------------");
+                let monster = game.get_player(player_id).and_then(|p| p.monster).map_or(String::new(), |m| format!(" as {}", m.name()));
+                println!("--- Turn {} - {}'s Turn{} (HP: {}, VP: {}) ---", turn, name(player_id), monster, hp, victory_points);
+                println!("---------------------------------------------------------");
+            }
+            GameEvent::RoundStarted => {
+                self.rounds += 1;
+                if self.rounds > 1 && !game.spectators.is_empty() {
+                    print_spectator_board(game);
+                }
+            }
+            GameEvent::DiceRolled { roll, rolls, dice, kept, .. } => {
+                say!("    🎲 Roll {}/{}: {}", roll, rolls, format_dice(&dice, &kept));
+            }
+            GameEvent::DiceChanged { player_id, by, ref effect, die, face, rerolled } => {
+                let change = if rerolled { "rerolls" } else { "sets" };
+                say!("    🎲 {}'s {} {} {}'s die {} to {:?}.", name(by), effect, change, name(player_id), die + 1, face);
+            }
+            GameEvent::DiceResolved { dice, .. } => println!("    Roll Results: {:?}", dice),
+            GameEvent::TriplesScored { vp, total, .. } => {
+                say!("    ⭐ Matched numbers gain **{}** VP. (Total VP: {})", vp, total);
+            }
+            GameEvent::EnergyGained { amount, total, .. } => {
+                say!("    ⚡ Gains +{} Energy. (Total Energy: {})", amount, total);
+            }
+            GameEvent::Healed { amount, hp, .. } => say!("    ❤️ Gains +{} HP. (Total HP: {})", amount, hp),
+            GameEvent::HeartsIgnored { overtime: true, .. } => say!("    ❤️ Heart roll ignored: no healing in overtime."),
+            GameEvent::HeartsIgnored { overtime: false, .. } => say!("    ❤️ Heart roll ignored: Player is in Tokyo."),
+            GameEvent::AttackLaunched { player_id, damage, from_tokyo, .. } => {
+                let direction = if from_tokyo { "from" } else { "to" };
+                say!("    💥 **ATTACK!** {} deals {} damage {} Tokyo.", name(player_id), damage, direction);
+            }
+            GameEvent::DamageDealt { target_id, damage, hp, .. } => {
+                println!("        -> {} takes {} damage! (HP: {})", name(target_id), damage, hp);
+            }
+            GameEvent::TokyoHeld { player_id, zone, vp, total } => {
+                say!("    ⭐ **{}** MAINTAINS {} control and gains +{} VP! (VP: {})", name(player_id), zone.label(), vp, total);
+            }
+            GameEvent::TokyoEntered { player_id, zone, vp, total } => {
+                say!("    🚪 **{}** ENTERS {} and gains +{} VP! (Total VP: {})", name(player_id), zone.label(), vp, total);
+            }
+            GameEvent::TokyoEntryDeclined { player_id } => say!("    🚫 {} declines to enter Tokyo.", name(player_id)),
+            GameEvent::TokyoConceded { player_id, .. } => say!("    📢 {} CONCEDES Tokyo!", name(player_id)),
+            GameEvent::TokyoBayClosed { player_id } => {
+                say!("    🌊 Fewer than 5 monsters remain: Tokyo Bay is out of play.");
+                if let Some(player_id) = player_id {
+                    say!("    📢 {} leaves Tokyo Bay.", name(player_id));
+                }
+            }
+            GameEvent::TokyoVacated { player_id, zone } => say!("    🏚️  {} is empty: {} can't hold it any more.", zone.label(), name(player_id)),
+            GameEvent::TokyoYielded { player_id, zone, .. } => say!("    📢 {} YIELDS {}!", name(player_id), zone.label()),
+            GameEvent::TokyoDefended { player_id, zone, attacker_id } => {
+                say!("    🛡️ {} holds {} against {}'s attack.", name(player_id), zone.label(), name(attacker_id));
+            }
+            GameEvent::CardRevealed { ref card } => say!("    🃏 {} is revealed in the shop.", card),
+            GameEvent::EvolutionDrawn { player_id, .. } => say!("    🧬 Three Hearts! {} draws an evolution card.", name(player_id)),
+            GameEvent::EvolutionPlayed { player_id, ref evolution, permanent } => {
+                let stays = if permanent { " It stays in play." } else { "" };
+                say!("    🧬 {} EVOLVES: {}!{}", name(player_id), evolution, stays);
+            }
+            GameEvent::CardPurchased { player_id, ref card, cost, energy, ref price } => {
+                let breakdown = if price.steps.is_empty() { String::new() } else { format!(" [{}]", price) };
+                say!("    🛒 {} buys {} for {} Energy{}. (Energy left: {})", name(player_id), card, cost, breakdown, energy);
+            }
+            GameEvent::ShopSwept { player_id, energy } => {
+                say!("    🧹 {} sweeps the shop for {} Energy. (Energy left: {})", name(player_id), game.sweep_cost(), energy);
+            }
+            GameEvent::VpGained { amount, total, .. } => say!("    ⭐ Gains +{} VP. (Total VP: {})", amount, total),
+            GameEvent::DamageBlocked { player_id, ref blocked_by, .. } => println!("        -> {}'s {} blocks the hit!", name(player_id), blocked_by),
+            GameEvent::PseudoCardBought { player_id, ref card, times, spent, energy } => {
+                say!("    🛒 {} uses {} x{} for {} Energy. (Energy left: {})", name(player_id), card, times, spent, energy);
+            }
+            GameEvent::OvertimeStarted { turn, hold_vp } => {
+                say!("\n### ⏰ SUDDEN-DEATH OVERTIME after {} turns! ###", turn - 1);
+                println!("    No more healing, and holding Tokyo is now worth +{} VP.", hold_vp);
+            }
+            GameEvent::TurnSkipped { player_id } => say!("\n    ⏭️  {} skips this turn.", name(player_id)),
+            GameEvent::SkipScheduled { player_id } => say!("    ⏸️  {} will skip their next turn.", name(player_id)),
+            GameEvent::ExtraTurnGranted { player_id } => say!("    🔁 {} will take an extra turn!", name(player_id)),
+            GameEvent::PlayerEliminated { player_id, placement, .. } => {
+                say!("\n    💀 **{}** has been ELIMINATED! (Finishes {})", name(player_id), locale.ordinal(placement));
+            }
+            GameEvent::MaxHpChanged { player_id, max_hp, hp } => {
+                say!("    💪 {}'s max HP is now {}. (HP: {})", name(player_id), max_hp, hp);
+            }
+            GameEvent::MaxVpChanged { player_id, max_vp } => say!("    🏁 {} now needs {} VP to win.", name(player_id), max_vp),
+            GameEvent::GameOver { victory } => {
+                say!("\n### 🎉 GAME OVER! ###");
+                match victory {
+                    Victory::Points { player_id, max_vp } => println!("{} reached {} Victory Points!", name(player_id), max_vp),
+                    Victory::LastStanding { player_id } => println!("{} is the Last Kaiju Standing!", name(player_id)),
+                    Victory::NoSurvivors => println!("All Kaiju were eliminated simultaneously!"),
+                }
+            }
+            GameEvent::PracticeStarted => say!("🎯 Practice game: dice can be set by hand, and the game is unranked."),
+            GameEvent::MonsterChosen { player_id, monster } => {
+                println!("{} plays as {} ({}).", name(player_id), monster.name(), monster.ability());
+            }
+            GameEvent::GameStarted { .. } | GameEvent::PhaseEnded { .. } => {}
+            // Events from a newer engine than this frontend knows about aren't shown
+            _ => {}
+        }
+    }
+}
+
+/// Lets a bot with an identity react to its big moments: entering Tokyo, knocking a
+/// monster out and winning. Emotes are only shown, so they don't touch the game's dice.
+fn emote(game: &Game, event: &GameEvent) {
+    let player_id = match *event {
+        GameEvent::TokyoEntered { player_id, .. } => player_id,
+        GameEvent::PlayerEliminated { player_id, .. } => {
+            match game.damage_log.iter().rev().find(|record| record.knockout && record.target_id == player_id) {
+                Some(record) => record.attacker_id,
+                None => return,
+            }
+        }
+        GameEvent::GameOver { victory: Victory::Points { player_id, .. } | Victory::LastStanding { player_id } } => player_id,
+        _ => return,
+    };
+    let Some(player) = game.get_player(player_id) else {
+        return;
+    };
+    let identities = BOT_IDENTITIES.lock().expect("Bot identity lock is never poisoned");
+    if let Some((_, identity)) = identities.iter().find(|(name, _)| *name == player.name) {
+        if let Some(line) = identity.catchphrase(rand::thread_rng().gen()) {
+            say!("    💬 {} ({}): \"{}\"", identity.name, player.name, line);
+        }
+    }
+}
+
+impl Agent for Console {
+    fn choose_forced_dice(&mut self, game: &Game, player_id: u32, _dice: &[DieResult; 6], keep: &[bool; 6], _roll: u8) -> Option<[DieResult; 6]> {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let open = keep.iter().filter(|&&kept| !kept).count();
+        loop {
+            let scripted = SCENARIO.lock().expect("Scenario lock is never poisoned").pop_front();
+            let input = match scripted {
+                Some(line) => {
+                    say!("    📜 Scenario roll: {}", line);
+                    line
+                }
+                None => self.ask(game, &format!("    🎯 {}", text(MessageKey::PromptSetDice, &[("count", Arg::Number(open as u32))]))),
+            };
+            if input.is_empty() {
+                return None;
+            }
+            match parse_faces(&input) {
+                Some(faces) if faces.len() == open => {
+                    let mut forced = [DieResult::One; 6];
+                    let mut faces = faces.into_iter();
+                    for (die, &kept) in forced.iter_mut().zip(keep) {
+                        if !kept {
+                            *die = faces.next().expect("One face per unkept die");
+                        }
+                    }
+                    return Some(forced);
+                }
+                _ => println!("    {}", text(MessageKey::RetryFaces, &[("count", Arg::Number(open as u32))])),
+            }
+        }
+    }
+
+    fn choose_dice_to_keep(&mut self, game: &Game, player_id: u32, _dice: &[DieResult; 6], _reroll: u8) -> [bool; 6] {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let keep_all = &profile().keys.keep_all;
+        let key = if profile().hints { MessageKey::PromptKeepDiceHinted } else { MessageKey::PromptKeepDice };
+        let prompt = format!("    ❓ {}", text(key, &[("key", named(keep_all))]));
+        loop {
+            let input = self.ask(game, &prompt);
+            if input.eq_ignore_ascii_case(keep_all) {
+                return [true; 6];
+            }
+            match parse_keep(&input) {
+                Some(keep) => return keep,
+                None => println!("    {}", text(MessageKey::RetryKeep, &[])),
+            }
+        }
+    }
+
+    fn decide_concede_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone) -> bool {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let name = &game.get_player(player_id).expect("Player must exist").name;
+        let question = format!("\n    ❓ {}", text(MessageKey::PromptConcedeTokyo, &[("player", named(name))]));
+        self.confirm_tokyo(game, Decision::ConcedeTokyo { player_id, zone }, &question, false)
+    }
+
+    fn decide_yield_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone, attacker_id: u32) -> bool {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let name = &game.get_player(player_id).expect("Occupant must exist").name;
+        let attacker = &game.get_player(attacker_id).expect("Attacker must exist").name;
+        let question = format!("\n    ⚔️  {}", text(MessageKey::PromptYieldTokyo, &[("player", named(name)), ("by", named(attacker)), ("zone", Arg::Zone(zone))]));
+        self.confirm_tokyo(game, Decision::YieldTokyo { player_id, zone, attacker_id }, &question, false)
+    }
+
+    fn decide_enter_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone) -> bool {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let name = &game.get_player(player_id).expect("Player must exist").name;
+        let question = text(MessageKey::PromptEnterTokyo, &[("zone", Arg::Zone(zone)), ("player", named(name)), ("count", Arg::Number(self.claws as u32))]);
+        self.confirm_tokyo(game, Decision::EnterTokyo { player_id, zone }, &format!("    ❓ {}", question), true)
+    }
+
+    fn choose_shop_action(&mut self, game: &Game, player_id: u32) -> ShopAction {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let player = game.get_player(player_id).expect("Player must exist");
+        let shop = game.shop.face_up();
+        say!("\n    🏪 {}", text(MessageKey::ShopHeading, &[("count", Arg::Number(game.shop.deck_len() as u32))]));
+        for (slot, card) in shop.iter().enumerate() {
+            println!("       [{}] {} ({} Energy, {:?}): {}", slot + 1, card.name, game.card_cost(player_id, card), card.kind, card.description);
+        }
+        let evolve = &profile().keys.evolve;
+        if !player.evolutions.is_empty() {
+            say!("    🧬 {}", text(MessageKey::EvolutionsHeading, &[]));
+            for (index, evolution) in player.evolutions.iter().enumerate() {
+                println!("       [{}{}] {} ({:?}): {}", evolve, index + 1, evolution.name, evolution.kind, evolution.description);
+            }
+        }
+        loop {
+            let sweep = &profile().keys.sweep;
+            let evolving = if player.evolutions.is_empty() { String::new() } else { text(MessageKey::PromptShopEvolve, &[("key", named(evolve))]) };
+            let prompt = text(MessageKey::PromptShop, &[
+                ("player", named(&player.name)), ("energy", Arg::Number(player.energy as u32)), ("count", Arg::Number(shop.len() as u32)),
+                ("key", named(sweep)), ("cost", Arg::Number(game.sweep_cost() as u32)), ("evolve", named(evolving)),
+            ]);
+            let input = self.ask(game, &format!("    ❓ {}", prompt));
+            if input.is_empty() {
+                return ShopAction::Done;
+            }
+            let lowered = input.to_lowercase();
+            if let Some(number) = lowered.strip_prefix(&evolve.to_lowercase()).filter(|_| !player.evolutions.is_empty()) {
+                match number.trim().parse::<usize>().ok().and_then(|n| n.checked_sub(1)).filter(|&index| index < player.evolutions.len()) {
+                    Some(index) => return ShopAction::Evolve(index),
+                    None => println!("    {}", text(MessageKey::RetryEvolution, &[("key", named(evolve)), ("max", Arg::Number(player.evolutions.len() as u32))])),
+                }
+                continue;
+            }
+            if input.eq_ignore_ascii_case(sweep) {
+                if player.energy >= game.sweep_cost() {
+                    return ShopAction::Sweep;
+                }
+                println!("    {}", text(MessageKey::RetrySweep, &[]));
+                continue;
+            }
+            match input.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|slot| shop.get(slot).map(|card| (slot, card))) {
+                Some((slot, card)) if game.card_cost(player_id, card).affordable(player.energy) => return ShopAction::Buy(slot),
+                Some((_, card)) => println!("    {}", text(MessageKey::RetryCost, &[("card", named(card.name)), ("cost", named(game.card_cost(player_id, card)))])),
+                None => println!("    {}", text(MessageKey::RetryCard, &[("max", Arg::Number(shop.len() as u32))])),
+            }
+        }
+    }
+
+    fn choose_purchases(&mut self, game: &Game, player_id: u32, card: &PseudoCard, affordable: u8) -> u8 {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let player = game.get_player(player_id).expect("Player must exist");
+        loop {
+            let prompt = text(MessageKey::PromptPurchases, &[
+                ("player", named(&player.name)), ("energy", Arg::Number(player.energy as u32)), ("card", named(card.name)),
+                ("cost", Arg::Number(card.cost as u32)), ("effect", named(card.effect.describe())), ("max", Arg::Number(affordable as u32)),
+            ]);
+            let input = self.ask(game, &format!("\n    🛒 {}", prompt));
+            if input.is_empty() {
+                return 0;
+            }
+            match input.parse::<u8>() {
+                Ok(times) if times <= affordable => return times,
+                _ => println!("    {}", text(MessageKey::RetryTimes, &[("max", Arg::Number(affordable as u32))])),
+            }
+        }
+    }
+
+    fn decide_spectate(&mut self, game: &Game, player_id: u32) -> bool {
+        self.show_events(game);
+        self.hand_over(game, player_id);
+        let name = &game.get_player(player_id).expect("Player must exist").name;
+        self.confirm(game, &format!("    👀 {}", text(MessageKey::PromptSpectate, &[("player", named(name))])), true)
+    }
+}
+
+/// The table for one game: bots play their own seats, and the console asks about the rest.
+struct Seats {
+    console: Console,
+    bots: HashMap<u32, Box<dyn Agent>>, // Player ID -> the bot playing that seat
+}
+
+impl PlayerController for Seats {
+    fn agent_for(&mut self, player_id: u32) -> &mut dyn Agent {
+        match self.bots.get_mut(&player_id) {
+            Some(bot) => bot.as_mut(),
+            None => &mut self.console,
+        }
+    }
+}
+
+// --- 3. Report Formatting ---
+
+/// Prints the total and average time spent in each turn phase.
+fn print_phase_timings(game: &Game, locale: Locale) {
+    println!("\n--- {} ---", text(MessageKey::TimingsHeading, &[]));
+    for phase in TurnPhase::ALL {
+        let durations: Vec<Duration> = game.history.iter()
+            .filter_map(|e| match &e.event {
+                GameEvent::PhaseEnded { phase: p, duration, .. } if *p == phase => Some(*duration),
+                _ => None,
+            })
+            .collect();
+        if durations.is_empty() {
+            continue;
+        }
+        let total: Duration = durations.iter().sum();
+        println!("- {}", text(MessageKey::TimingLine, &[
+            ("phase", named(format!("{:?}", phase))), ("duration", named(locale.duration(total))),
+            ("average", named(locale.duration(total / durations.len() as u32))), ("count", Arg::Number(durations.len() as u32)),
+        ]));
+    }
+}
+
+/// The between-rounds summary shown to spectators.
+fn print_spectator_board(game: &Game) {
+    let names: Vec<&str> = game.spectators.iter()
+        .filter_map(|&id| game.get_player(id))
+        .map(|p| p.name.as_str())
+        .collect();
+    say!("\n    👀 {}", text(MessageKey::SpectatorBoard, &[("list", named(names.join(", ")))]));
+    for player in game.living_players() {
+        let mut args = vec![
+            ("player", named(&player.name)), ("hp", Arg::Number(player.hp as u32)), ("score", Arg::Number(player.victory_points as u32)),
+            ("energy", Arg::Number(player.energy as u32)),
+        ];
+        let key = match game.tokyo.zone_of(player.id) {
+            Some(zone) => {
+                args.push(("zone", Arg::Zone(zone)));
+                MessageKey::SpectatorInTokyo
+            }
+            None => MessageKey::SpectatorOutside,
+        };
+        println!("       {}", text(key, &args));
+    }
+}
+
+/// Prints every monster's HP, VP and Energy, for `status`.
+fn print_status(game: &Game) {
+    say!("    📋 {}", text(MessageKey::StatusHeading, &[("number", Arg::Number(game.turn))]));
+    for player in &game.players {
+        let monster = player.monster.map_or(String::new(), |m| format!(" ({})", m.name()));
+        let who = named(format!("{}{}", player.name, monster));
+        if player.hp == 0 {
+            println!("       {}", text(MessageKey::StatusEliminated, &[("player", who), ("score", Arg::Number(player.victory_points as u32))]));
+        } else {
+            println!("       {}", text(MessageKey::StatusLine, &[
+                ("player", who), ("hp", Arg::Number(player.hp as u32)), ("max_hp", Arg::Number(player.max_hp as u32)),
+                ("score", Arg::Number(player.victory_points as u32)), ("goal", Arg::Number(game.max_vp as u32)),
+                ("energy", Arg::Number(player.energy as u32)),
+            ]));
+        }
+    }
+    let health = HEALTH.lock().expect("Health lock is never poisoned");
+    for subsystem in health.disabled().chain(game.health().disabled()) {
+        println!("       ⚠️  {}", subsystem);
+    }
+}
+
+/// Plays the turn so far back slowly, each event followed by the rule that produced it
+/// under this game's settings, for `review`. Uses the `--pace` set, or a short pause.
+fn review_turn(game: &Game) {
+    say!("    📖 {}", text(MessageKey::ReviewHeading, &[("number", Arg::Number(game.turn))]));
+    let mut replay = Console { local: false, ..Console::new() };
+    for (timed, rule) in kingoftokyo::review_turn(game) {
+        match PACE.get() {
+            Some(pace) => pace.before(&timed.event),
+            None => std::thread::sleep(Duration::from_millis(400)),
+        }
+        replay.show_event(game, &timed.event);
+        if let Some(rule) = rule {
+            say!("           📜 {}", rule);
+        }
+    }
+    say!("    📖 {}", text(MessageKey::ReviewEnd, &[]));
+}
+
+/// Prints the rules table entry `id`, or with no id the rules behind this turn's events,
+/// for `rule`.
+fn print_rule(game: &Game, id: &str) {
+    let ids: Vec<&str> = RuleId::ALL.iter().map(|rule| rule.id()).collect();
+    if id.is_empty() {
+        let mut rules: Vec<RuleId> = Vec::new();
+        for rule in kingoftokyo::turn_events(game).iter().filter_map(|timed| timed.event.rule()) {
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+        say!("    📜 {}", text(MessageKey::RulesThisTurn, &[]));
+        for rule in rules {
+            println!("       {:<15} {}", rule.id(), rule.title());
+        }
+        println!("    {}", text(MessageKey::RuleIds, &[("list", named(ids.join(", ")))]));
+        return;
+    }
+    match RuleId::from_id(id) {
+        Some(rule) => {
+            say!("    📜 {} ({})", rule.title(), rule.reference());
+            println!("       {}", rule.summary(game));
+        }
+        None => println!("    {}", text(MessageKey::NoSuchRule, &[("key", named(id)), ("list", named(ids.join(", ")))])),
+    }
+}
+
+/// Prints who holds each Tokyo spot and who waits outside, for `board`.
+fn print_board(game: &Game) {
+    let name = |id: u32| game.get_player(id).map_or("?", |p| p.name.as_str());
+    say!("    🏙️  {}", text(MessageKey::BoardHeading, &[]));
+    let occupants = game.tokyo.occupants();
+    if occupants.is_empty() {
+        println!("       {}", text(MessageKey::TokyoEmpty, &[]));
+    }
+    for (zone, player_id) in occupants {
+        println!("       {}", text(MessageKey::ZoneHeld, &[("zone", Arg::Zone(zone)), ("player", named(name(player_id)))]));
+    }
+    let outside: Vec<&str> = game.living_players()
+        .filter(|player| !game.tokyo.contains(player.id))
+        .map(|player| player.name.as_str())
+        .collect();
+    if !outside.is_empty() {
+        println!("       {}", text(MessageKey::OutsideTokyo, &[("list", named(outside.join(", ")))]));
+    }
+}
+
+/// Prints each player's totals for the game as a table.
+fn print_statistics(game: &Game, locale: Locale) {
+    println!("\n--- {} ---", text(MessageKey::StatisticsHeading, &[]));
+    // The column headings come as one comma-separated line, so each language names all nine
+    let columns = text(MessageKey::StatisticsColumns, &[]);
+    let heading: Vec<&str> = columns.split(", ").chain(std::iter::repeat("")).take(9).collect();
+    println!("{:<14} {:>6} {:>6} {:>4} {:>7} {:>6} {:>6} {:>6}   {} {}",
+             heading[0], heading[1], heading[2], heading[3], heading[4], heading[5], heading[6], heading[7], heading[8],
+             VpSource::ALL.iter().map(|source| source.label()).collect::<Vec<_>>().join(" / "));
+    for stats in game.player_stats() {
+        let name = game.get_player(stats.player_id).map_or("?", |p| p.name.as_str());
+        let vp: Vec<String> = stats.vp_by_source.iter().map(|&(_, vp)| vp.to_string()).collect();
+        println!("{:<14} {:>6} {:>6} {:>4} {:>7} {:>6} {:>6} {:>6}   {}",
+                 name, locale.number(stats.damage_dealt as u64), locale.number(stats.damage_taken as u64), stats.knockouts,
+                 locale.number(stats.energy_earned as u64), locale.number(stats.energy_spent as u64),
+                 stats.turns, stats.turns_in_tokyo, vp.join(" / "));
+    }
+}
+
+/// Prints how each player's rerolls went against the odds, luckiest first.
+fn print_dice_luck(game: &Game, locale: Locale) {
+    if game.practice {
+        return;
+    }
+    println!("\n--- {} ---", text(MessageKey::DiceLuckHeading, &[]));
+    println!("{}", text(MessageKey::DiceLuckNote, &[]));
+    let mut stats = game.player_stats();
+    stats.sort_by(|a, b| b.dice_luck.total_cmp(&a.dice_luck));
+    for stats in stats {
+        let name = game.get_player(stats.player_id).map_or("?", |p| p.name.as_str());
+        let verdict = match stats.dice_luck {
+            luck if luck >= 1.0 => MessageKey::Lucky,
+            luck if luck <= -1.0 => MessageKey::Robbed,
+            _ => MessageKey::AsExpected,
+        };
+        let sign = if stats.dice_luck >= 0.0 { "+" } else { "" };
+        let luck = format!("{}{}", sign, locale.decimal(stats.dice_luck, 1));
+        println!("- {}", text(MessageKey::DiceLuckLine, &[("player", named(name)), ("luck", named(luck)), ("verdict", named(text(verdict, &[])))]));
+    }
+}
+
+/// Prints the damage ledger: totals dealt/taken per player, who landed each knockout,
+/// and a per-attacker breakdown of damage by target and source.
+fn print_damage_report(game: &Game) {
+    println!("\n--- {} ---", text(MessageKey::DamageHeading, &[]));
+    if game.damage_log.is_empty() {
+        println!("{}", text(MessageKey::NoDamage, &[]));
+        return;
+    }
+
+    for player in &game.players {
+        let dealt: u32 = game.damage_log.iter()
+            .filter(|r| r.attacker_id == player.id)
+            .map(|r| r.amount as u32)
+            .sum();
+        let taken: u32 = game.damage_log.iter()
+            .filter(|r| r.target_id == player.id)
+            .map(|r| r.amount as u32)
+            .sum();
+        println!("- {}", text(MessageKey::DamageTotals, &[("player", named(&player.name)), ("dealt", Arg::Number(dealt)), ("taken", Arg::Number(taken))]));
+
+        for target in game.players.iter().filter(|t| t.id != player.id) {
+            let mut by_source: Vec<(DamageSource, u32)> = Vec::new();
+            for record in game.damage_log.iter().filter(|r| r.attacker_id == player.id && r.target_id == target.id) {
+                match by_source.iter_mut().find(|(source, _)| *source == record.source) {
+                    Some((_, total)) => *total += record.amount as u32,
+                    None => by_source.push((record.source, record.amount as u32)),
+                }
+            }
+            for (source, total) in by_source.iter().filter(|(_, total)| *total > 0) {
+                println!("    -> {}", text(MessageKey::DamageTo, &[("damage", Arg::Number(*total)), ("target", named(&target.name)), ("source", named(source.label()))]));
+            }
+        }
+    }
+
+    for record in game.damage_log.iter().filter(|r| r.knockout) {
+        let attacker = game.get_player(record.attacker_id).map_or("?", |p| p.name.as_str());
+        let target = game.get_player(record.target_id).map_or("?", |p| p.name.as_str());
+        let assists: Vec<&str> = game.players.iter()
+            .filter(|p| p.id != record.attacker_id && p.id != record.target_id)
+            .filter(|p| game.damage_log.iter().any(|r| r.attacker_id == p.id && r.target_id == record.target_id && r.amount > 0))
+            .map(|p| p.name.as_str())
+            .collect();
+        let mut args = vec![("by", named(attacker)), ("target", named(target))];
+        let key = if assists.is_empty() {
+            MessageKey::KnockedOut
+        } else {
+            args.push(("list", named(assists.join(", "))));
+            MessageKey::KnockedOutAssisted
+        };
+        say!("💀 {}", text(key, &args));
+    }
+}
+
+// --- 4. Series Play ---
+
+/// One finished game of a series: each player's name, seat (1 = went first) and placement.
+struct SeriesGame {
+    results: Vec<(String, usize, u8)>,
+}
+
+impl SeriesGame {
+    fn record(game: &Game) -> Self {
+        let results = game.final_placements().into_iter()
+            .map(|(player_id, placement)| {
+                let seat = game.players.iter().position(|p| p.id == player_id).expect("Placed player must exist") + 1;
+                (game.players[seat - 1].name.clone(), seat, placement)
+            })
+            .collect();
+        SeriesGame { results }
+    }
+}
+
+/// Wins and average placement per seat and per player across the series, so first-player
+/// advantage (or the lack of it) shows up in the numbers.
+fn print_series_summary(series: &[SeriesGame], num_players: usize, locale: Locale) {
+    println!("\n=== {} ===", text(MessageKey::SeriesHeading, &[("count", Arg::Number(series.len() as u32))]));
+
+    println!("\n{}", text(MessageKey::BySeat, &[]));
+    for seat in 1..=num_players {
+        let placements: Vec<u8> = series.iter()
+            .flat_map(|g| g.results.iter().filter(|r| r.1 == seat).map(|r| r.2))
+            .collect();
+        let wins = placements.iter().filter(|&&p| p == 1).count();
+        let average = placements.iter().map(|&p| p as f64).sum::<f64>() / placements.len().max(1) as f64;
+        println!("- {}", text(MessageKey::SeatLine, &[
+            ("number", Arg::Number(seat as u32)), ("count", Arg::Number(wins as u32)),
+            ("share", named(locale.percent(wins as f64 / series.len() as f64))), ("average", named(locale.decimal(average, 2))),
+        ]));
+    }
+
+    println!("\n{}", text(MessageKey::ByPlayer, &[]));
+    let mut first_game: Vec<&(String, usize, u8)> = series[0].results.iter().collect();
+    first_game.sort_by_key(|r| r.1);
+    for (name, _, _) in first_game {
+        let results: Vec<&(String, usize, u8)> = series.iter()
+            .flat_map(|g| g.results.iter().filter(|r| &r.0 == name))
+            .collect();
+        let wins = results.iter().filter(|r| r.2 == 1).count();
+        let seats: Vec<String> = results.iter().map(|r| r.1.to_string()).collect();
+        println!("- {}", text(MessageKey::PlayerSeriesLine, &[("player", named(name)), ("count", Arg::Number(wins as u32)), ("list", named(seats.join(", ")))]));
+    }
+}
+
+// --- 5. Main Game Loop Implementation (Full Interactive Flow) ---
+
+/// Prints the presets accepted by `--preset`.
+fn print_presets() {
+    println!("Available presets:");
+    for preset in &PRESETS {
+        println!("  {:<13} {}", preset.name, preset.description);
+    }
+}
+
+fn main() {
+    // `--capabilities` describes this build; `--preset <name>` skips the preset question at setup;
+    // `--load <file>` picks a saved game back up (`--legacy-save` lets it take a trusted save from
+    // before saves were checksummed); `--timeline <file> [csv|json]` prints the
+    // per-turn standings of a saved game for charting; `--seed <number>` makes the games reproducible;
+    // `--simulate <games> [seats]` plays bot-only games (seats like "bbr": b = heuristic, r = random)
+    // and prints statistics; `--narrate <file>` appends a plain-sentence narration of play to a file;
+    // `--pace <ms>`, `--pause <ms>` and `--step` slow play down for watching; `--record <file>`
+    // writes a replay of each game played; `--replay <file>` plays a recorded game back turn by turn;
+    // `--host <port> [players]` hosts a network game that others join with `--join <host:port>`;
+    // `--profile <name>` applies the key bindings, theme, hints and advisor saved in profiles/<name>.json;
+    // `--data-dir <dir>` keeps saves, profiles, bot packs and standings in <dir> instead of the
+    // platform's data directory (also settable with KINGOFTOKYO_DATA_DIR);
+    // `--json [players]` plays one game over stdin/stdout in the network protocol, for frontends;
+    // `--config <file>` and `--set <option>=<value>` (repeatable) change rules on top of the preset;
+    // `--compare <option>=<a>,<b>,...` makes `--simulate` run a batch per value and show the shifts;
+    // `--from <save>` makes `--simulate` play on from a saved game instead of starting fresh ones;
+    // `--bot-pack <name>` picks the names and catchphrases bots take on (`none` turns them off);
+    // `--series <format> [name]` plays a best-of-N or round-robin series with saved standings;
+    // `--report <replay file> [name]` writes <name>.md and <name>.html about a recorded series;
+    // `--fuzz [games]` plays random games checking the engine's invariants (exits with 1 on a failure)
+    // `--serve <port>` runs the HTTP/JSON API for creating, joining and playing games remotely;
+    // `--lang <tag>` shows game events (and writes `--narrate` lines) in that language, e.g. `es`;
+    // `--register-bot <name> <command...>` enters a program speaking the network protocol on
+    // stdin/stdout in leaderboard games; `--leaderboard <port> [name]` plays rated games between
+    // every registered bot until stopped, serving the ratings with the HTTP API;
+    // `--analyze <dir> [json]` checks every replay in a directory and prints statistics over them all;
+    // `--predict [games]` shows each seat's predicted chances before every game and records how it went
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "--capabilities") {
+        print!("{}", Engine::capabilities());
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--data-dir") {
+        let Some(dir) = args.get(i + 1) else {
+            println!("--data-dir needs a directory, e.g. --data-dir . to keep files where you are.");
+            return;
+        };
+        let _ = DATA_DIR.set(DataDir::locate(Some(Path::new(dir))));
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--register-bot") {
+        let Some(name) = args.get(i + 1) else {
+            println!("--register-bot needs a name and the command that starts the bot, e.g. --register-bot Clawbot python3 clawbot.py.");
+            return;
+        };
+        // Everything after the name is the bot's command, flags included
+        match BotRegistration::new(name, &args[i + 2..]) {
+            Ok(registration) => match registration.save_to(&mut data_dir().storage()) {
+                Ok(()) => println!("Registered {} ({}); it plays in leaderboard games from now on.", registration.name, registration.command.join(" ")),
+                Err(error) => println!("Couldn't save the registration to {}: {}", data_file(BotRegistration::BOTS, &registration.name), error),
+            },
+            Err(error) => print!("{}", error),
+        }
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--analyze") {
+        let Some(dir) = args.get(i + 1) else {
+            println!("--analyze needs a directory of replays, e.g. --analyze selfplay/.");
+            return;
+        };
+        let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+        match analyze_replays(dir, threads) {
+            Ok(analysis) if args.get(i + 2).is_some_and(|format| format == "json") => {
+                println!("{}", serde_json::to_string_pretty(&analysis).expect("Analyses are always serializable"));
+            }
+            Ok(analysis) => print!("{}", analysis),
+            Err(error) => println!("Couldn't read {}: {}", dir, error),
+        }
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--timeline") {
+        let path = &args.get(i + 1).cloned().unwrap_or_else(default_save_file);
+        match Game::load_from_file(path) {
+            Ok(game) if args.get(i + 2).is_some_and(|format| format == "json") => println!("{}", game.timeline_json()),
+            Ok(game) => print!("{}", game.timeline_csv()),
+            Err(error) => println!("Couldn't load {}: {}", path, error),
+        }
+        return;
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == "--report") {
+        let Some(path) = args.get(i + 1) else {
+            println!("--report needs the first replay of a series, as written by --record.");
+            return;
+        };
+        write_tournament_report(path, args.get(i + 2).map_or("report", |name| name.as_str()));
+        return;
+    }
+
+    if let Some(i) = args.iter().position(|arg| arg == "--profile") {
+        let Some(name) = args.get(i + 1) else {
+            println!("--profile needs a profile name, e.g. --profile alex.");
+            return;
+        };
+        if let Some(loaded) = load_profile(name) {
+            let _ = PROFILE.set(loaded);
+        }
+    }
+
+    // `--bot-pack <name>` picks the identities bots take on (`none` for plain bots)
+    let bot_pack = match args.iter().position(|arg| arg == "--bot-pack") {
+        Some(i) => match args.get(i + 1).map(|name| name.as_str()) {
+            Some("none") => None,
+            Some(name) => match BotPack::load_from(&data_dir().storage(), name) {
+                Ok(Some(pack)) => Some(pack),
+                Ok(None) => {
+                    println!("There is no bot pack {}: use {} or a file like {}.", name, BotPack::BUILTIN.join(", "), data_file(BotPack::PACKS, name));
+                    return;
+                }
+                Err(error) => {
+                    degrade("bot pack", format!("{}: {}", name, error));
+                    None
+                }
+            },
+            None => {
+                println!("--bot-pack needs a pack name, e.g. --bot-pack classic (or none).");
+                return;
+            }
+        },
+        None => Some(BotPack::for_language(&profile().language)),
+    };
+    let bot_pack = match bot_pack.as_ref().map(BotPack::validate) {
+        Some(Err(error)) => {
+            degrade("bot pack", error.to_string().trim());
+            None
+        }
+        _ => bot_pack,
+    };
+
+    // In --json mode stdout carries nothing but protocol messages
+    let json_mode = args.iter().any(|arg| arg == "--json");
+    if !json_mode {
+        say!("# 🦖 KING OF TOKYO (Simplified) 🏙️ #");
+    }
+    if let Some(pace) = Pace::from_args(&args) {
+        let _ = PACE.set(pace);
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--lang") {
+        let tags: Vec<&str> = Locale::ALL.iter().map(|locale| locale.tag()).collect();
+        match args.get(i + 1).and_then(|tag| Locale::from_tag(tag)) {
+            Some(locale) => {
+                let _ = LANGUAGE.set(locale);
+            }
+            None => {
+                println!("--lang needs one of these languages: {}.", tags.join(", "));
+                return;
+            }
+        }
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--narrate") {
+        let Some(path) = args.get(i + 1) else {
+            println!("--narrate needs a file to write the narration to.");
+            return;
+        };
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => {
+                let _ = NARRATION.set(Mutex::new(file));
+            }
+            Err(error) => degrade("narration", format!("{}: {}", path, error)),
+        }
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--replay") {
+        let Some(path) = args.get(i + 1) else {
+            println!("--replay needs a replay file, as written by --record.");
+            return;
+        };
+        match Replay::load_from_file(path) {
+            Ok(replay) => play_replay(&replay, path),
+            Err(error) => println!("Couldn't load {}: {}", path, error),
+        }
+        return;
+    }
+    let record_arg = match args.iter().position(|arg| arg == "--record") {
+        Some(i) => match args.get(i + 1) {
+            Some(path) => Some(path.as_str()),
+            None => {
+                println!("--record needs a file to write the replay to.");
+                return;
+            }
+        },
+        None => None,
+    };
+    if let Some(i) = args.iter().position(|arg| arg == "--join") {
+        let Some(address) = args.get(i + 1) else {
+            println!("--join needs the host's address, e.g. --join 192.168.1.20:7878.");
+            return;
+        };
+        join_game(address);
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--load") {
+        let path = &args.get(i + 1).filter(|path| !path.starts_with("--")).cloned().unwrap_or_else(default_save_file);
+        let legacy = args.iter().any(|arg| arg == "--legacy-save");
+        let loaded = std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|json| {
+            if legacy { Game::from_legacy_json(&json) } else { Game::from_json(&json) }.map_err(|error| error.to_string())
+        });
+        let mut game = match loaded {
+            Ok(game) => game,
+            Err(error) => {
+                println!("Couldn't load {}: {}", path, error);
+                if error.contains("as a legacy save") {
+                    println!("Add --legacy-save to load it anyway.");
+                }
+                return;
+            }
+        };
+        let names: Vec<&str> = game.players.iter().map(|p| p.name.as_str()).collect();
+        println!("\n--- {} ---", text(MessageKey::ResumeHeading, &[
+            ("path", named(path)), ("number", Arg::Number(game.turn + 1)), ("seed", named(game.seed)), ("preset", named(&game.preset)),
+        ]));
+        say!("{}", text(MessageKey::Seating, &[("list", named(names.join(" → ")))]));
+        play_game(&mut game, &HashMap::new());
+        print_game_report(&game, locale());
+        if let Some(path) = record_arg {
+            record_replay(&game, path);
+        }
+        return;
+    }
+    // `--practice [scenario]` lets players set their dice; the scenario file scripts the first rolls
+    let practice_arg = match args.iter().position(|arg| arg == "--practice") {
+        Some(i) => match args.get(i + 1).filter(|path| !path.starts_with("--")) {
+            Some(path) => match std::fs::read_to_string(path) {
+                Ok(text) => Some(text.lines().map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect()),
+                Err(error) => {
+                    println!("Couldn't read the scenario {}: {}", path, error);
+                    return;
+                }
+            },
+            None => Some(VecDeque::new()),
+        },
+        None => None,
+    };
+    // `--series <format> [name]` plays a best-of-N or round-robin series, keeping the
+    // standings in <data dir>/standings/<name>.json
+    let series_arg = match args.iter().position(|arg| arg == "--series") {
+        Some(i) => match args.get(i + 1).and_then(|format| SeriesFormat::parse(format)) {
+            Some(format) => {
+                let name = args.get(i + 2).filter(|name| !name.starts_with("--")).map_or("series", |name| name.as_str());
+                Some((format, name))
+            }
+            None => {
+                println!("--series needs a format: best-of-<games> (e.g. best-of-5) or round-robin.");
+                return;
+            }
+        },
+        None => None,
+    };
+    // `--predict [games]` shows each seat's chances before every game, from that many
+    // simulated games and the results kept in <data dir>/predictions/log.json
+    let predict_arg = match args.iter().position(|arg| arg == "--predict") {
+        Some(i) => match args.get(i + 1).filter(|games| !games.starts_with("--")).map_or(Some(PREDICTION_GAMES), |games| games.parse::<usize>().ok()) {
+            Some(games) => Some(games),
+            None => {
+                println!("--predict needs a number of games to simulate, e.g. --predict 500.");
+                return;
+            }
+        },
+        None => None,
+    };
+    if predict_arg.is_some() && practice_arg.is_some() {
+        println!("Practice games are unranked, so there is nothing to predict.");
+        return;
+    }
+    if series_arg.is_some() && practice_arg.is_some() {
+        println!("Practice games are unranked, so they can't be played as a series.");
+        return;
+    }
+    if let Some(rolls) = &practice_arg {
+        *SCENARIO.lock().expect("Scenario lock is never poisoned") = rolls.clone();
+    }
+    let preset_arg = match args.iter().position(|arg| arg == "--preset") {
+        Some(i) => match args.get(i + 1).and_then(|name| GameConfig::from_preset(name)) {
+            Some(config) => Some(config),
+            None => {
+                println!("--preset needs one of the preset names below.");
+                print_presets();
+                return;
+            }
+        },
+        None => None,
+    };
+    // `--config <file>` and `--set <option>=<value>` adjust the rules on top of the preset
+    let preset_arg = match args.iter().position(|arg| arg == "--config") {
+        Some(i) => {
+            let Some(path) = args.get(i + 1) else {
+                println!("--config needs a JSON file of options, e.g. --config house.json.");
+                return;
+            };
+            let json = match std::fs::read_to_string(path) {
+                Ok(json) => json,
+                Err(error) => {
+                    println!("Couldn't read {}: {}", path, error);
+                    return;
+                }
+            };
+            // A file that doesn't name its own preset builds on `--preset`
+            let loaded = match (preset_arg, serde_json::from_str::<serde_json::Value>(&json)) {
+                (Some(base), Ok(overrides)) if overrides.is_object() && overrides.get("preset").is_none() => base.with_overrides(&overrides),
+                _ => GameConfig::from_json(&json),
+            };
+            match loaded.and_then(|config| config.validate().map(|()| config)) {
+                Ok(config) => Some(config),
+                Err(error) => {
+                    print!("{}", ConfigError { source: format!("config file {}", path), ..error.locate(&json) });
+                    return;
+                }
+            }
+        }
+        None => preset_arg,
+    };
+    let mut preset_arg = preset_arg;
+    for (i, _) in args.iter().enumerate().filter(|(_, arg)| *arg == "--set") {
+        let Some((option, value)) = args.get(i + 1).and_then(|setting| setting.split_once('=')) else {
+            println!("--set needs an option and a value, e.g. --set max_vp=15 or --set rules.rerolls=3.");
+            return;
+        };
+        match preset_arg.unwrap_or_else(GameConfig::official).with_option(option, parse_option_value(value)) {
+            Ok(config) => preset_arg = Some(config),
+            Err(error) => {
+                print!("{}", error);
+                return;
+            }
+        }
+    }
+    let seed_arg = match args.iter().position(|arg| arg == "--seed") {
+        Some(i) => match args.get(i + 1).and_then(|seed| seed.parse::<u64>().ok()) {
+            Some(seed) => Some(seed),
+            None => {
+                println!("--seed needs a whole number, e.g. --seed 42.");
+                return;
+            }
+        },
+        None => None,
+    };
+    if let Some(i) = args.iter().position(|arg| arg == "--fuzz") {
+        let Some(games) = args.get(i + 1).filter(|games| !games.starts_with("--")).map_or(Some(1000), |games| games.parse::<usize>().ok()) else {
+            println!("--fuzz needs a number of games, e.g. --fuzz 5000.");
+            return;
+        };
+        let seed = seed_arg.unwrap_or_else(|| rand::thread_rng().gen());
+        println!("Fuzzing with seed {}...", seed);
+        match kingoftokyo::fuzz(games, seed) {
+            Ok(report) => print!("{}", report),
+            Err(failure) => {
+                print!("{}", failure);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--simulate") {
+        let games = match args.get(i + 1).filter(|games| !games.starts_with("--")) {
+            None => 100,
+            Some(games) => match games.parse::<usize>() {
+                Ok(games) => games,
+                Err(_) => {
+                    println!("--simulate needs a number of games, e.g. --simulate 1000.");
+                    return;
+                }
+            },
+        };
+        // `--from <save>` plays on from a saved position instead of fresh games
+        let start = match args.iter().position(|arg| arg == "--from") {
+            Some(j) => {
+                let path = args.get(j + 1).cloned().unwrap_or_else(default_save_file);
+                match Game::load_from_file(&path) {
+                    Ok(game) => Some((path, game)),
+                    Err(error) => {
+                        println!("Couldn't load {}: {}", path, error);
+                        return;
+                    }
+                }
+            }
+            None => None,
+        };
+        let default_seats = vec![AgentKind::Heuristic; start.as_ref().map_or(2, |(_, game)| game.players.len())];
+        let seats: Option<Vec<AgentKind>> = args.get(i + 2).filter(|seats| !seats.starts_with("--")).map_or(Some(default_seats), |seats| {
+            seats.chars()
+                .map(|seat| match seat.to_ascii_lowercase() {
+                    'b' => Some(AgentKind::Heuristic),
+                    'r' => Some(AgentKind::Random),
+                    _ => None,
+                })
+                .collect()
+        });
+        let Some(seats) = seats else {
+            println!("--simulate seats are one letter per seat: b = heuristic bot, r = random bot (e.g. bbr).");
+            return;
+        };
+        let seed = seed_arg.unwrap_or_else(|| rand::thread_rng().gen());
+        if let Some((path, game)) = start {
+            match simulate_from(games, &seats, &game, seed) {
+                Ok(report) => print!("Simulated on from turn {} of {} with seed {}: {}", game.turn + 1, path, seed, report),
+                Err(error) => print!("{}", error),
+            }
+            return;
+        }
+        let config = preset_arg.unwrap_or_else(GameConfig::official);
+        if let Some(i) = args.iter().position(|arg| arg == "--compare") {
+            let Some((option, values)) = args.get(i + 1).and_then(|setting| setting.split_once('=')) else {
+                println!("--compare needs an option and the values to try, e.g. --compare rules.economy.sweep_cost=1,2,3.");
+                return;
+            };
+            let values: Vec<serde_json::Value> = values.split(',').map(parse_option_value).collect();
+            match compare(games, &seats, &config, option, &values, seed) {
+                Ok(comparison) => print!("Simulated with seed {} ({} rules): {}", seed, config.preset, comparison),
+                Err(error) => print!("{}", error),
+            }
+            return;
+        }
+        match simulate(games, &seats, &config, seed) {
+            Ok(report) => print!("Simulated with seed {} ({} rules): {}", seed, config.preset, report),
+            Err(error) => print!("{}", error),
+        }
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--json") {
+        let players = match args.get(i + 1).filter(|players| !players.starts_with("--")).map(|players| parse_player_count(players)) {
+            None => 2,
+            Some(Ok(players)) => players,
+            Some(Err(error)) => {
+                eprintln!("--json: {}.", error);
+                return;
+            }
+        };
+        let config = preset_arg.unwrap_or_else(GameConfig::official);
+        let seed = seed_arg.unwrap_or_else(|| rand::thread_rng().gen());
+        json_game(players, config, seed);
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--host") {
+        let Some(port) = args.get(i + 1).and_then(|port| port.parse::<u16>().ok()) else {
+            println!("--host needs a port number to listen on, e.g. --host 7878.");
+            return;
+        };
+        let players = match args.get(i + 2).filter(|players| !players.starts_with("--")).map(|players| parse_player_count(players)) {
+            None => 2,
+            Some(Ok(players)) => players,
+            Some(Err(error)) => {
+                println!("--host: {}.", error);
+                return;
+            }
+        };
+        let config = preset_arg.unwrap_or_else(GameConfig::official);
+        let seed = seed_arg.unwrap_or_else(|| rand::thread_rng().gen());
+        host_game(port, players, config, seed);
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--serve") {
+        let Some(port) = args.get(i + 1).and_then(|port| port.parse::<u16>().ok()) else {
+            println!("--serve needs a port number to listen on, e.g. --serve 8080.");
+            return;
+        };
+        serve_api(port, seed_arg.unwrap_or_else(|| rand::thread_rng().gen()), "api", None);
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--leaderboard") {
+        let Some(port) = args.get(i + 1).and_then(|port| port.parse::<u16>().ok()) else {
+            println!("--leaderboard needs a port number to serve the ratings on, e.g. --leaderboard 8080.");
+            return;
+        };
+        let name = args.get(i + 2).filter(|name| !name.starts_with("--")).map_or("bots", |name| name.as_str());
+        let config = preset_arg.unwrap_or_else(GameConfig::official);
+        run_leaderboard(port, name, config, seed_arg.unwrap_or_else(|| rand::thread_rng().gen()));
+        return;
+    }
+    
+    // -----------------------------------------------------
+    // Game Setup
+    // -----------------------------------------------------
+    catch_interrupts();
+    let capabilities = Engine::capabilities();
+    let num_players = loop {
+        let input = read_line_input(&text(MessageKey::PromptPlayerCount, &[
+            ("min", Arg::Number(capabilities.min_players as u32)), ("max", Arg::Number(capabilities.max_players as u32)),
+        ]));
+        if input.is_empty() {
+            break 2;
+        }
+        match parse_player_count(&input) {
+            Ok(count) => break count,
+            Err(_) if INPUT_CLOSED.load(Ordering::SeqCst) => return,
+            Err(error) => println!("Sorry, {}.", error),
+        }
+    };
+    
+    let mut player_names: Vec<String> = Vec::new();
+    while player_names.len() < num_players {
+        let name = read_line_input(&text(MessageKey::PromptPlayerName, &[("number", Arg::Number(player_names.len() as u32 + 1))]));
+        let mut candidate: Vec<&str> = player_names.iter().map(|s| s.as_str()).collect();
+        candidate.push(&name);
+        match validate_player_names(&candidate) {
+            Ok(()) => player_names.push(name),
+            Err(error) if INPUT_CLOSED.load(Ordering::SeqCst) => {
+                print!("{}", error);
+                return;
+            }
+            Err(error) => print!("{}", error),
+        }
+    }
+    
+    // Any seat can be played by a bot; it keeps its bot when seats are rotated or shuffled
+    let mut bots: HashMap<String, AgentKind> = HashMap::new();
+    for name in &player_names {
+        let choice = read_line_input(&text(MessageKey::PromptSeat, &[("player", named(name))]));
+        let bot = match choice.to_ascii_lowercase().as_str() {
+            "r" => Some((AgentKind::Random, MessageKey::SeatRandomBot)),
+            "b" => Some((AgentKind::Heuristic, MessageKey::SeatHeuristicBot)),
+            _ => None,
+        };
+        if let Some((kind, seat)) = bot {
+            println!("{}", text(seat, &[("player", named(name))]));
+            bots.insert(name.clone(), kind);
+        }
+    }
+
+    // Bots take on identities from the bot pack, which they keep for the whole series
+    if let Some(pack) = &bot_pack {
+        let bot_seats: Vec<&String> = player_names.iter().filter(|name| bots.contains_key(*name)).collect();
+        let taken: Vec<&str> = player_names.iter().map(|name| name.as_str()).collect();
+        let identities = pack.assign(bot_seats.len(), &taken, seed_arg.unwrap_or_else(|| rand::thread_rng().gen()));
+        let mut assigned = BOT_IDENTITIES.lock().expect("Bot identity lock is never poisoned");
+        for (name, identity) in bot_seats.into_iter().zip(identities) {
+            println!("{}", text(MessageKey::BotIdentity, &[("player", named(name)), ("by", named(&identity.name))]));
+            assigned.push((name.clone(), identity));
+        }
+    }
+
+    // Monsters stay with their player too; each can only be picked once
+    println!("{}", text(MessageKey::MonstersHeading, &[]));
+    for (i, monster) in Monster::ALL.iter().enumerate() {
+        println!("  {} = {:<12} {}", i + 1, monster.name(), monster.ability());
+    }
+    let mut monsters: HashMap<String, Monster> = HashMap::new();
+    for name in &player_names {
+        loop {
+            let choice = read_line_input(&text(MessageKey::PromptMonster, &[("player", named(name)), ("max", Arg::Number(Monster::ALL.len() as u32))]));
+            if choice.is_empty() {
+                break;
+            }
+            match choice.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|i| Monster::ALL.get(i)) {
+                Some(monster) if monsters.values().any(|m| m == monster) => println!("{}", text(MessageKey::MonsterTaken, &[("monster", named(monster.name()))])),
+                Some(&monster) => {
+                    monsters.insert(name.clone(), monster);
+                    break;
+                }
+                None => println!("{}", text(MessageKey::RetryMonster, &[("max", Arg::Number(Monster::ALL.len() as u32))])),
+            }
+        }
+    }
+
+    let mut config = match preset_arg {
+        Some(config) => config,
+        None => loop {
+            let names: Vec<&str> = PRESETS.iter().map(|preset| preset.name).collect();
+            let name = read_line_input(&text(MessageKey::PromptPreset, &[("list", named(names.join("/")))]));
+            if name.is_empty() {
+                break GameConfig::official();
+            }
+            match GameConfig::from_preset(&name) {
+                Some(config) => break config,
+                None => println!("{}", text(MessageKey::UnknownPreset, &[("preset", named(name))])),
+            }
+        },
+    };
+    let keys = &profile().keys;
+    let choices = || named(format!("{}/{}", keys.yes, keys.no.to_uppercase()));
+    if config.rules.energy && config.rules.energy_per_vp.is_none() {
+        let exchange = read_line_input(&text(MessageKey::PromptEnergyPerVp, &[("energy", Arg::Number(5)), ("choices", choices())]));
+        if exchange.eq_ignore_ascii_case(&keys.yes) {
+            config.rules.energy_per_vp = Some(5);
+        }
+    }
+    if config.rules.energy && config.rules.energy_per_heal.is_none() {
+        let heal = read_line_input(&text(MessageKey::PromptEnergyPerHeal, &[("energy", Arg::Number(2)), ("choices", choices())]));
+        if heal.eq_ignore_ascii_case(&keys.yes) {
+            config.rules.energy_per_heal = Some(2);
+        }
+    }
+    if !config.rules.optional_entry {
+        let optional = read_line_input(&text(MessageKey::PromptOptionalEntry, &[("choices", choices())]));
+        if optional.eq_ignore_ascii_case(&keys.yes) {
+            config.rules.optional_entry = true;
+        }
+    }
+    
+    let locale = locale();
+    let mut storage = data_dir().storage();
+    let mut standings = match series_arg {
+        Some((format, name)) => match start_series(&storage, format, name, &player_names) {
+            Some(standings) => Some((name, standings)),
+            None => return,
+        },
+        None => None,
+    };
+    let mut predictions = match predict_arg {
+        Some(_) => match PredictionLog::load_from(&storage) {
+            Ok(log) => Some(log),
+            Err(error) => {
+                degrade("predictions", format!("{}: {}", data_file(PredictionLog::PREDICTIONS, PredictionLog::LOG), error));
+                None
+            }
+        },
+        None => None,
+    };
+    let mut seating = standings.as_ref().map_or_else(|| player_names.clone(), |(_, standings)| standings.seating());
+    let mut series: Vec<SeriesGame> = Vec::new();
+
+    loop {
+        let player_refs: Vec<&str> = seating.iter().map(|s| s.as_str()).collect();
+        // With --seed, game N of a series uses seed + N - 1, so every game can be replayed alone
+        let number = standings.as_ref().map_or(series.len(), |(_, standings)| standings.games as usize);
+        let seed = seed_arg.map_or_else(|| rand::thread_rng().gen(), |seed| seed.wrapping_add(number as u64));
+        let mut game = match Game::new(&player_refs, seed, config.clone()) {
+            Ok(game) => game,
+            Err(error) => {
+                print!("{}", error);
+                return;
+            }
+        };
+
+        if practice_arg.is_some() {
+            game.enable_practice();
+        }
+        let picks: Vec<(u32, Monster)> = game.players.iter()
+            .filter_map(|p| monsters.get(&p.name).map(|&monster| (p.id, monster)))
+            .collect();
+        for (player_id, monster) in picks {
+            game.choose_monster(player_id, monster);
+        }
+
+        println!("\n--- {} ---", text(MessageKey::GameHeading, &[
+            ("number", Arg::Number(series.len() as u32 + 1)), ("count", Arg::Number(num_players as u32)),
+            ("seed", named(game.seed)), ("preset", named(&game.preset)),
+        ]));
+        say!("{}", text(MessageKey::Seating, &[("list", named(seating.join(" → ")))]));
+        if profile().hints {
+            println!("{}", text(MessageKey::CommandsHint, &[]));
+            if game.rules.undo_limit > 0 {
+                println!("{}", text(MessageKey::UndoHint, &[("count", Arg::Number(game.rules.undo_limit as u32))]));
+            }
+        }
+
+        // Humans are stood in for by the heuristic bot in the simulations
+        let seats: Vec<AgentKind> = game.players.iter().map(|p| bots.get(&p.name).copied().unwrap_or(AgentKind::Heuristic)).collect();
+        let prediction = match (&predictions, predict_arg) {
+            (Some(log), Some(games)) => match log.predict(&game, &seats, games) {
+                Ok(prediction) => {
+                    print!("{}", prediction);
+                    Some(prediction)
+                }
+                Err(error) => {
+                    print!("{}", error);
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        play_game(&mut game, &bots);
+        print_game_report(&game, locale);
+        if let (Some(log), Some(prediction)) = (&mut predictions, &prediction) {
+            if log.record(prediction, &game) && is_enabled("predictions") {
+                print!("{}", log.calibration());
+                if let Err(error) = log.save_to(&mut storage) {
+                    degrade("predictions", format!("{}: {}", data_file(PredictionLog::PREDICTIONS, PredictionLog::LOG), error));
+                }
+            }
+        }
+        if let Some(path) = record_arg {
+            // Later games of a series get numbered files, e.g. game.json.2
+            let numbered = if series.is_empty() { path.to_string() } else { format!("{}.{}", path, series.len() + 1) };
+            record_replay(&game, &numbered);
+        }
+        series.push(SeriesGame::record(&game));
+
+        if let Some((name, standings)) = &mut standings {
+            let counted = standings.record(&game);
+            if is_enabled("standings") {
+                if let Err(error) = standings.save_to(&mut storage, name) {
+                    degrade("standings", format!("{}: {}", data_file(Series::STANDINGS, name), error));
+                }
+            }
+            println!("\n{}", standings);
+            if !counted {
+                println!("This game didn't finish, so it doesn't count. Run the same command again to carry on the series.");
+                break;
+            }
+            if standings.is_decided() {
+                println!("The series is over: {} wins it.", standings.standings()[0].name);
+                break;
+            }
+            if INPUT_CLOSED.load(Ordering::SeqCst) {
+                break;
+            }
+            seating = standings.seating();
+            continue;
+        }
+        if INPUT_CLOSED.load(Ordering::SeqCst) {
+            break;
+        }
+        let again = read_line_input(&format!("\n{}", text(MessageKey::PromptPlayAgain, &[])));
+        if again.eq_ignore_ascii_case("r") {
+            seating.rotate_left(1);
+        } else if again.eq_ignore_ascii_case("s") {
+            seating.shuffle(&mut rand::thread_rng());
+        } else {
+            break;
+        }
+    }
+
+    if series.len() > 1 && practice_arg.is_some() {
+        println!("\nPractice games are unranked, so there is no series summary.");
+    } else if series.len() > 1 {
+        print_series_summary(&series, num_players, locale);
+    }
+}
+
+/// Picks up the series saved as `<data dir>/standings/<name>.json`, or starts it if there is none.
+/// Prints why and returns None if the saved series is between other players, is already
+/// decided or can't be read.
+fn start_series(storage: &FileStorage, format: SeriesFormat, name: &str, players: &[String]) -> Option<Series> {
+    let names: Vec<&str> = players.iter().map(|name| name.as_str()).collect();
+    match Series::load_from(storage, name) {
+        Ok(Some(saved)) => {
+            let mut saved_names: Vec<&str> = saved.players.iter().map(|record| record.name.as_str()).collect();
+            let mut wanted = names.clone();
+            saved_names.sort_unstable();
+            wanted.sort_unstable();
+            if saved.format != format || saved_names != wanted {
+                println!("{} holds a {} series between {}. Pick another name to start a new one.",
+                         data_file(Series::STANDINGS, name), saved.format.label(), saved_names.join(", "));
+                None
+            } else if saved.is_decided() {
+                print!("{}", saved);
+                println!("That series is already over. Pick another name to start a new one.");
+                None
+            } else {
+                println!("Carrying on the series in {} after {} game(s).", data_file(Series::STANDINGS, name), saved.games);
+                Some(saved)
+            }
+        }
+        Ok(None) => match Series::new(format, &names) {
+            Ok(series) => {
+                println!("Starting a {} series; the standings are kept in {}.", format.label(), data_file(Series::STANDINGS, name));
+                Some(series)
+            }
+            Err(error) => {
+                print!("{}", error);
+                None
+            }
+        },
+        Err(error) => {
+            println!("Couldn't read {}: {}", data_file(Series::STANDINGS, name), error);
+            None
+        }
+    }
+}
+
+/// Loads `<data dir>/profiles/<name>.json`, creating it with default preferences the first time.
+/// Warns and returns None if it can't be used, so the game goes on with the defaults.
+fn load_profile(name: &str) -> Option<Profile> {
+    let mut storage = data_dir().storage();
+    let loaded = match Profile::load_from(&storage, name) {
+        Ok(Some(loaded)) => loaded,
+        Ok(None) => {
+            let created = Profile::new(name);
+            match created.save_to(&mut storage) {
+                Ok(()) => println!("Created profile {} with default preferences in {}.", name, data_file(Profile::PROFILES, name)),
+                Err(error) => degrade("profile", format!("{} can't be created: {}", name, error)),
+            }
+            created
+        }
+        Err(error) => {
+            degrade("profile", format!("{}: {}", name, error));
+            return None;
+        }
+    };
+    if let Err(error) = loaded.validate() {
+        degrade("profile", error.to_string().trim());
+        return None;
+    }
+    if !Profile::LANGUAGES.contains(&loaded.language.as_str()) {
+        println!("Language \"{}\" isn't available yet, so text is shown in English.", loaded.language);
+    }
+    Some(loaded)
+}
+
+/// Runs turns until someone wins or input runs out. A game loaded mid-turn with `load`
+/// takes over from the next turn.
+fn play_game(game: &mut Game, bots: &HashMap<String, AgentKind>) {
+    let mut seats = seat_players(game, bots);
+    loop {
+        seats.console.checkpoint = game.to_json();
+        let victory = play_turn(game, &mut seats);
+        if let Some(loaded) = seats.console.loaded.take() {
+            *game = loaded;
+            seats = seat_players(game, bots);
+            continue;
+        }
+        if seats.console.quit {
+            // The turn in progress was finished with default answers; set it aside
+            *game = Game::from_json(&seats.console.checkpoint).expect("A game's own save always loads");
+            println!("\nGame stopped early: quit.");
+            game.abandon();
+            break;
+        }
+        seats.console.show_events(game);
+        if victory.is_some() {
+            break;
+        }
+        // Ctrl-C while only bots were playing is dealt with between turns
+        if INTERRUPTED.swap(false, Ordering::SeqCst) {
+            seats.console.checkpoint = game.to_json();
+            seats.console.interrupted(game);
+            if seats.console.quit {
+                println!("\nGame stopped early: quit.");
+                game.abandon();
+                break;
+            }
+        }
+
+        if INPUT_CLOSED.load(Ordering::SeqCst) {
+            println!("\nGame stopped early: no more input.");
+            game.abandon();
+            break;
+        }
+    }
+}
+
+/// Plays one turn like `Game::play_turn`, but takes decisions back and puts them back when
+/// `undo` or `redo` is typed at a prompt.
+fn play_turn(game: &mut Game, seats: &mut Seats) -> Option<Victory> {
+    loop {
+        let action = game.pending().map(|decision| decision.ask(seats.agent_for(decision.player_id()), game));
+        let progress = match seats.console.rewind.take() {
+            Some(rewind) => match rewind_to_human(game, seats, rewind) {
+                Some(progress) => progress,
+                None => continue,
+            },
+            None => game.advance(action).expect("The pending decision was just answered"),
+        };
+        match progress {
+            Progress::GameOver(victory) => return Some(victory),
+            Progress::TurnEnded => return None,
+            _ => continue,
+        }
+    }
+}
+
+/// Undoes or redoes decisions until one for a player at the console is pending, so bots'
+/// answers in between are taken back or put back with it. Returns where a redo ended up
+/// if it finished the turn.
+fn rewind_to_human(game: &mut Game, seats: &mut Seats, rewind: Rewind) -> Option<Progress> {
+    let human = |decision: Decision| !seats.bots.contains_key(&decision.player_id());
+    let mut steps = 0;
+    let outcome = loop {
+        let result = match rewind {
+            Rewind::Undo => game.undo().map(|decision| (human(decision), None)),
+            Rewind::Redo => game.redo().map(|progress| match progress {
+                Progress::Decide(decision) => (human(decision), None),
+                done => (true, Some(done)),
+            }),
+        };
+        match result {
+            Ok((false, _)) => steps += 1,
+            Ok((true, done)) => {
+                steps += 1;
+                break done;
+            }
+            Err(error) => {
+                println!("    Can't {}: {}.", if rewind == Rewind::Undo { "undo" } else { "redo" }, error);
+                break None;
+            }
+        }
+    };
+    seats.console.shown = seats.console.shown.min(game.history.len());
+    if steps > 0 {
+        let (icon, verb) = if rewind == Rewind::Undo { ("↩️", "Took back") } else { ("↪️", "Put back") };
+        say!("    {} {} {} decision{}.", icon, verb, steps, if steps == 1 { "" } else { "s" });
+    }
+    outcome
+}
+
+/// Writes the replay of `game` for `--record`.
+fn record_replay(game: &Game, path: &str) {
+    let Some(replay) = Replay::from_game(game) else {
+        println!("Can't record a replay of this game: it was resumed from a save made before replays existed.");
+        return;
+    };
+    match replay.save_to_file(path) {
+        Ok(()) => println!("Replay written to {}.", path),
+        Err(error) => println!("Couldn't write the replay to {}: {}", path, error),
+    }
+}
+
+/// Writes `<name>.md` and `<name>.html` summing up the series recorded with `--record path`:
+/// `path` itself, then `path.2`, `path.3` and so on until one is missing. Every replay is
+/// checked against the rules first, and the report is only written if they all pass.
+fn write_tournament_report(path: &str, name: &str) {
+    let mut games = Vec::new();
+    loop {
+        let file = if games.is_empty() { path.to_string() } else { format!("{}.{}", path, games.len() + 1) };
+        if !games.is_empty() && !std::path::Path::new(&file).exists() {
+            break;
+        }
+        let replay = match Replay::load_from_file(&file) {
+            Ok(replay) => replay,
+            Err(error) => {
+                println!("Couldn't load {}: {}", file, error);
+                return;
+            }
+        };
+        match replay.verify() {
+            Ok(game) => games.push(game),
+            Err(violation) => {
+                println!("{} breaks the rules, so it can't go in a report: {}", file, violation);
+                return;
+            }
+        }
+    }
+
+    let report = TournamentReport::from_games(&format!("Tournament report: {}", path), &games);
+    for (extension, text) in [("md", report.to_markdown()), ("html", report.to_html())] {
+        let file = format!("{}.{}", name, extension);
+        match std::fs::write(&file, text) {
+            Ok(()) => println!("Report written to {}.", file),
+            Err(error) => println!("Couldn't write {}: {}", file, error),
+        }
+    }
+}
+
+/// Plays a recorded game back on the console, one turn at a time, at the `--pace` set.
+fn play_replay(replay: &Replay, path: &str) {
+    let mut playback = match replay.playback() {
+        Ok(playback) => playback,
+        Err(error) => {
+            print!("{}", error);
+            return;
+        }
+    };
+    println!("\n--- {} ---", text(MessageKey::ReplayHeading, &[
+        ("path", named(path)), ("count", Arg::Number(replay.players.len() as u32)), ("seed", named(replay.seed)),
+        ("preset", named(&replay.config.preset)),
+    ]));
+    say!("{}", text(MessageKey::Seating, &[("list", named(replay.players.join(" → ")))]));
+    let mut console = Console::new();
+    loop {
+        let progress = playback.next_turn();
+        console.show_events(playback.game());
+        match progress {
+            Ok(Progress::TurnEnded) if !playback.finished() => continue,
+            Ok(Progress::GameOver(_)) => break,
+            Ok(_) => {
+                println!("\nThe recording ends here: the game was stopped before it finished.");
+                break;
+            }
+            Err(error) => {
+                println!("\nThe replay doesn't match this version of the rules: {}", error);
+                return;
+            }
+        }
+    }
+    print_game_report(playback.game(), locale());
+}
+
+// --- 6. Network Play ---
+
+/// Hosts a game on `port`. This terminal plays the first seat; the other players join
+/// from their own machines with `--join`, in seat order.
+fn host_game(port: u16, players: usize, config: GameConfig, seed: u64) {
+    let mut names: Vec<String> = Vec::new();
+    while names.is_empty() {
+        let name = read_line_input(&text(MessageKey::PromptYourName, &[]));
+        match validate_player_names(&[&name]) {
+            Ok(()) => names.push(name),
+            Err(error) if INPUT_CLOSED.load(Ordering::SeqCst) => {
+                print!("{}", error);
+                return;
+            }
+            Err(error) => print!("{}", error),
+        }
+    }
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            println!("Couldn't listen on port {}: {}", port, error);
+            return;
+        }
+    };
+    println!("Waiting for {} more player(s) to join on port {}...", players - names.len(), port);
+    let mut clients: Vec<Connection> = Vec::new();
+    while names.len() < players {
+        let Ok(mut client) = listener.accept().and_then(|(stream, _)| Connection::new(stream)) else {
+            continue;
+        };
+        let name = match client.receive() {
+            Ok(ClientMessage::Join { name }) => name,
+            _ => {
+                let _ = client.send(&HostMessage::Rejected { reason: "expected a join message".to_string() });
+                continue;
+            }
+        };
+        let mut candidate: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        candidate.push(&name);
+        match validate_player_names(&candidate) {
+            Ok(()) => {
+                println!("{} joined from {}.", name, client.peer().map_or("?".to_string(), |peer| peer.to_string()));
+                names.push(name);
+                clients.push(client);
+            }
+            Err(error) => {
+                let _ = client.send(&HostMessage::Rejected { reason: error.to_string().trim().to_string() });
+            }
+        }
+    }
+
+    let player_refs: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    let mut game = match Game::new(&player_refs, seed, config) {
+        Ok(game) => game,
+        Err(error) => {
+            print!("{}", error);
+            return;
+        }
+    };
+    // Seats follow join order: the host is player 1, the first to join player 2, and so on
+    let mut remote: HashMap<u32, Connection> = (2..).zip(clients).collect();
+    for (&player_id, client) in remote.iter_mut() {
+        let _ = client.send(&HostMessage::Welcome { player_id, protocol_version: PROTOCOL_VERSION });
+    }
+    println!("\n--- {} ---", text(MessageKey::NetworkGameHeading, &[
+        ("count", Arg::Number(names.len() as u32)), ("seed", named(game.seed)), ("preset", named(&game.preset)),
+    ]));
+    say!("{}", text(MessageKey::Seating, &[("list", named(names.join(" → ")))]));
+
+    // Clients only hear about the game through `State`, sent before each of their decisions
+    // and at the end of every turn, each with their own view. A write to a client that has
+    // gone is noticed when they're next asked something.
+    let broadcast = |remote: &mut HashMap<u32, Connection>, game: &Game| {
+        for (&player_id, client) in remote.iter_mut() {
+            let _ = client.send(&HostMessage::State { game: game.view_json(Some(player_id)) });
+        }
+    };
+    // Ctrl-C is dealt with at the host's next prompt or the end of the turn, whichever
+    // comes first; a client's decision in progress is waited for
+    catch_interrupts();
+    let mut console = Console { local: false, ..Console::new() };
+    let victory = loop {
+        console.show_events(&game);
+        let decision = game.pending();
+        let action = match decision {
+            Some(decision) => match remote.get_mut(&decision.player_id()) {
+                Some(client) => {
+                    let _ = client.send(&HostMessage::State { game: game.view_json(Some(decision.player_id())) });
+                    let answer = client.send(&HostMessage::Decide { player_id: decision.player_id(), decision }).and_then(|_| client.receive());
+                    match answer {
+                        Ok(ClientMessage::Act { action }) => Some(action),
+                        Ok(ClientMessage::Join { .. }) => {
+                            let _ = client.send(&HostMessage::Rejected { reason: "already joined".to_string() });
+                            continue;
+                        }
+                        Err(error) => {
+                            let name = &game.get_player(decision.player_id()).expect("Deciding player must exist").name;
+                            println!("\n{} lost connection ({}). Ending the game.", name, error);
+                            game.abandon();
+                            break None;
+                        }
+                    }
+                }
+                None => Some(decision.ask(&mut console, &game)),
+            },
+            None => None,
+        };
+        match game.advance(action) {
+            Ok(Progress::GameOver(victory)) => break Some(victory),
+            Ok(Progress::TurnEnded) if INPUT_CLOSED.load(Ordering::SeqCst) => {
+                println!("\nGame stopped early: no more input.");
+                game.abandon();
+                break None;
+            }
+            Ok(Progress::TurnEnded) => {
+                if INTERRUPTED.swap(false, Ordering::SeqCst) {
+                    console.interrupted(&game);
+                }
+                if console.quit {
+                    println!("\nGame stopped early: the host left.");
+                    game.abandon();
+                    break None;
+                }
+                broadcast(&mut remote, &game);
+            }
+            Ok(_) => {}
+            Err(error) => {
+                // Only a client can send an action that doesn't fit; ask them again
+                if let Some(client) = decision.and_then(|decision| remote.get_mut(&decision.player_id())) {
+                    let _ = client.send(&HostMessage::Rejected { reason: error.to_string() });
+                }
+            }
+        }
+    };
+    console.show_events(&game);
+    broadcast(&mut remote, &game);
+    for client in remote.values_mut() {
+        let _ = client.send(&HostMessage::GameOver { victory });
+    }
+    print_game_report(&game, locale());
+}
+
+/// Answers HTTP API requests on `port` one at a time until the process is stopped, logging
+/// each. Games are created and played entirely by the clients. With a `leaderboard`, each
+/// request sees it as it stands when the request comes in. Ctrl-C (or SIGTERM) lets the
+/// request in progress finish, then checkpoints every table under `checkpoint` in the data
+/// directory and returns; the tables are picked back up from there on the next start.
+fn serve_api(port: u16, seed: u64, checkpoint: &str, leaderboard: Option<Arc<Mutex<Leaderboard>>>) {
+    let mut storage = data_dir().storage();
+    let (mut server, picked_up) = match ApiServer::load_from(&storage, checkpoint) {
+        Ok(Some(server)) => {
+            println!("Picked up {} table(s) from {}.", server.table_count(), data_file(ApiServer::CHECKPOINTS, checkpoint));
+            (server, true)
+        }
+        Ok(None) => (ApiServer::new(seed), false),
+        Err(error) => {
+            println!("Couldn't read the checkpoint in {}: {}", data_file(ApiServer::CHECKPOINTS, checkpoint), error);
+            return;
+        }
+    };
+    let listener = match TcpListener::bind(("0.0.0.0", port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            println!("Couldn't listen on port {}: {}", port, error);
+            return;
+        }
+    };
+    // A picked-up server carries on its own seed stream
+    let seeded = if picked_up { String::new() } else { format!(" (seed {})", seed) };
+    println!("Serving the game API on port {}{}. Open a game with POST /games; stop with Ctrl-C, which checkpoints the tables.", port, seeded);
+    // The listener is polled, so Ctrl-C is noticed between requests rather than only when
+    // the next one comes in
+    if let Err(error) = listener.set_nonblocking(true) {
+        println!("Couldn't listen on port {}: {}", port, error);
+        return;
+    }
+    catch_interrupts();
+    loop {
+        if INTERRUPTED.load(Ordering::SeqCst) {
+            match server.save_to(&mut storage, checkpoint) {
+                Ok(()) => println!("Stopped. {} table(s) checkpointed to {}.", server.table_count(), data_file(ApiServer::CHECKPOINTS, checkpoint)),
+                Err(error) => println!("Stopped, but couldn't checkpoint the tables to {}: {}", data_file(ApiServer::CHECKPOINTS, checkpoint), error),
+            }
+            return;
+        }
+        let stream = match listener.accept() {
+            Ok((stream, _)) if stream.set_nonblocking(false).is_ok() => stream,
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+            _ => continue,
+        };
+        let peer = stream.peer_addr().map_or("?".to_string(), |peer| peer.to_string());
+        if let Some(leaderboard) = &leaderboard {
+            server.set_leaderboard(leaderboard.lock().expect("The leaderboard lock is never poisoned").clone());
+        }
+        match server.serve(stream) {
+            Ok((request, status)) => println!("{} {} → {}", peer, request, status),
+            Err(error) => println!("{}: request dropped ({})", peer, error),
+        }
+    }
+}
+
+/// Plays rated games between every registered bot under `config` until the process is
+/// stopped, saving the leaderboard called `name` after each game, while the HTTP API on
+/// `port` serves it at `/leaderboard`. A board saved before is carried on. Ctrl-C
+/// checkpoints the API's tables under `name`; a rated game in progress is dropped unrated.
+fn run_leaderboard(port: u16, name: &str, config: GameConfig, seed: u64) {
+    let mut storage = data_dir().storage();
+    let bots = match RatedBot::registered(&storage) {
+        Ok(bots) => bots,
+        Err(error) => {
+            println!("Couldn't read the bots registered in {}: {}", data_dir().root().join(BotRegistration::BOTS).display(), error);
+            return;
+        }
+    };
+    let leaderboard = match Leaderboard::load_from(&storage, name) {
+        Ok(leaderboard) => leaderboard.unwrap_or_default(),
+        Err(error) => {
+            println!("Couldn't read the leaderboard in {}: {}", data_file(Leaderboard::LEADERBOARDS, name), error);
+            return;
+        }
+    };
+    let names: Vec<&str> = bots.iter().map(|bot| bot.name()).collect();
+    println!("Rating {} bots ({}) on the \"{}\" leaderboard, {} games in so far.", bots.len(), names.join(", "), name, leaderboard.games);
+    let shared = Arc::new(Mutex::new(leaderboard));
+    let board = Arc::clone(&shared);
+    let board_name = name.to_string();
+    std::thread::spawn(move || loop {
+        // Games are played on a copy, so requests aren't held up while one is in progress
+        let mut leaderboard = board.lock().expect("The leaderboard lock is never poisoned").clone();
+        // Game N is seeded with seed + N, so a restarted board doesn't replay the games it has had
+        let game = match leaderboard.play(&bots, &config, seed.wrapping_add(leaderboard.games as u64)) {
+            Ok(game) => game,
+            Err(error) => {
+                print!("{}", error);
+                return;
+            }
+        };
+        let order: Vec<&str> = game.final_placements().iter()
+            .filter_map(|&(player_id, _)| game.get_player(player_id).map(|player| player.name.as_str()))
+            .collect();
+        match game.victory() {
+            Some(_) => println!("Rated game {}: {}", leaderboard.games, order.join(" > ")),
+            None => println!("A game between {} ran too long and wasn't rated.", order.join(", ")),
+        }
+        if let Err(error) = leaderboard.save_to(&mut storage, &board_name) {
+            println!("Couldn't save the leaderboard to {}: {}", data_file(Leaderboard::LEADERBOARDS, &board_name), error);
+        }
+        *board.lock().expect("The leaderboard lock is never poisoned") = leaderboard;
+    });
+    serve_api(port, seed, name, Some(shared));
+}
+
+/// Joins a game hosted with `--host` and plays this player's seat from here.
+fn join_game(address: &str) {
+    let name = read_line_input(&text(MessageKey::PromptYourName, &[]));
+    let mut host = match TcpStream::connect(address).and_then(Connection::new) {
+        Ok(host) => host,
+        Err(error) => {
+            println!("Couldn't connect to {}: {}", address, error);
+            return;
+        }
+    };
+    if let Err(error) = host.send(&ClientMessage::Join { name }) {
+        println!("Couldn't join: {}", error);
+        return;
+    }
+    println!("Connected to {}. Waiting for the game to start...", address);
+    let mut console = Console { local: false, ..Console::new() };
+    let mut game: Option<Game> = None;
+    let mut seat: Option<u32> = None;
+    loop {
+        match host.receive() {
+            Ok(HostMessage::Welcome { protocol_version, .. }) if protocol_version != PROTOCOL_VERSION => {
+                println!("The host speaks version {} of the network protocol, but this build speaks version {}.", protocol_version, PROTOCOL_VERSION);
+                return;
+            }
+            Ok(HostMessage::Welcome { player_id, .. }) => {
+                seat = Some(player_id);
+                println!("The game has started. You are player {}.", player_id);
+            }
+            Ok(HostMessage::State { game: json }) => match Game::from_view_json(&json) {
+                Ok(state) => {
+                    console.show_events(&state);
+                    game = Some(state);
+                }
+                Err(error) => {
+                    println!("The host sent a game this version can't read: {}", error);
+                    return;
+                }
+            },
+            Ok(HostMessage::Decide { player_id, decision }) => {
+                let Some(state) = game.as_ref() else {
+                    continue;
+                };
+                if seat != Some(player_id) || decision.player_id() != player_id {
+                    println!("    The host asked for player {}'s decision, which isn't yours to make.", player_id);
+                    continue;
+                }
+                let action = decision.ask(&mut console, state);
+                if let Err(error) = host.send(&ClientMessage::Act { action }) {
+                    println!("\nLost the connection to the host: {}", error);
+                    return;
+                }
+            }
+            Ok(HostMessage::Rejected { reason }) if seat.is_none() => {
+                println!("The host turned you away: {}", reason);
+                return;
+            }
+            Ok(HostMessage::Rejected { reason }) => println!("    The host refused that: {}", reason),
+            Ok(HostMessage::GameOver { .. }) => break,
+            Err(error) => {
+                println!("\nLost the connection to the host: {}", error);
+                return;
+            }
+        }
+    }
+    if let Some(state) = game.as_ref() {
+        print_game_report(state, locale());
+    }
+}
+
+/// Gives each bot its seat and the console every other one. A game already under way
+/// only has its new events printed.
+fn seat_players(game: &Game, bots: &HashMap<String, AgentKind>) -> Seats {
+    let seat_bots: HashMap<u32, Box<dyn Agent>> = game.players.iter()
+        .filter_map(|p| bots.get(&p.name).map(|kind| (p.id, kind.build(game.seed.wrapping_add(p.id as u64)))))
+        .collect();
+    let shown = if game.turn == 0 { 0 } else { game.history.len() };
+    let seats = game.players.len() - seat_bots.len();
+    Seats { console: Console { shown, undo_limit: game.rules.undo_limit, seats, ..Console::new() }, bots: seat_bots }
+}
+
+/// Prints the standings, score tally and post-game reports for one game.
+fn print_game_report(game: &Game, locale: Locale) {
+    // --- Final Standings ---
+    println!("\n--- {} ---", text(MessageKey::StandingsHeading, &[]));
+    if game.practice {
+        println!("{}", text(MessageKey::PracticeUnranked, &[]));
+    }
+    for (player_id, placement) in game.final_placements() {
+        let player = game.get_player(player_id).expect("Placed player must exist");
+        let how = if player.hp == 0 { MessageKey::StandingEliminated } else { MessageKey::StandingSurvived };
+        println!("{:>4}  {}", locale.ordinal(placement), text(how, &[("player", named(&player.name)), ("score", Arg::Number(player.victory_points as u32))]));
+    }
+    println!("{}", text(MessageKey::TiesBroken, &[("policy", named(TieBreak::describe(&game.rules.tie_break)))]));
+
+    // --- Final Tally ---
+    println!("\n--- {} ---", text(MessageKey::ScoresHeading, &[]));
+    for player in &game.players {
+        let monster = player.monster.map_or(String::new(), |m| format!(" ({})", m.name()));
+        println!("- {}", text(MessageKey::ScoreLine, &[
+            ("player", named(format!("{}{}", player.name, monster))), ("score", Arg::Number(player.victory_points as u32)),
+            ("hp", Arg::Number(player.hp as u32)), ("energy", Arg::Number(player.energy as u32)),
+        ]));
+        println!("    {}", text(MessageKey::VpSources, &[("list", named(player.vp_breakdown()))]));
+        if !player.cards.is_empty() {
+            let cards: Vec<&str> = player.cards.iter().map(|card| card.name).collect();
+            println!("    {}", text(MessageKey::CardsKept, &[("list", named(cards.join(", ")))]));
+        }
+    }
+    print_statistics(game, locale);
+    print_dice_luck(game, locale);
+    print_damage_report(game);
+    print_phase_timings(game, locale);
+    print_game_clock(game, locale);
+}
+
+/// Prints how long the game took and each player's thinking time.
+fn print_game_clock(game: &Game, locale: Locale) {
+    println!("\n--- {} ---", text(MessageKey::ClockHeading, &[]));
+    let started = game.history.first()
+        .and_then(|e| e.wall_clock.checked_sub(e.elapsed))
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok());
+    if let Some(started) = started {
+        println!("{}", text(MessageKey::StartedAt, &[("time", named(started.as_secs()))]));
+    }
+    println!("{}", text(MessageKey::GameLength, &[("duration", named(locale.duration(game.duration())))]));
+    for (player_id, thinking) in game.thinking_times() {
+        let name = &game.get_player(player_id).expect("Player must exist").name;
+        println!("- {}", text(MessageKey::ThinkingTime, &[("player", named(name)), ("duration", named(locale.duration(thinking)))]));
+    }
+}
+
+// --- 7. Machine-Readable Play ---
+
+/// Writes one message as a line of JSON on stdout.
+fn send_json(message: &HostMessage) {
+    let line = serde_json::to_string(message).expect("Host messages are always serializable");
+    println!("{}", line);
+    io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Reads the next message from stdin. None once stdin is closed; a line that isn't a
+/// message is refused and the next one read.
+fn receive_json() -> Option<ClientMessage> {
+    loop {
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) if line.trim().is_empty() => continue,
+            Ok(_) => match serde_json::from_str(&line) {
+                Ok(message) => return Some(message),
+                Err(error) => send_json(&HostMessage::Rejected { reason: format!("not a message: {}", error) }),
+            },
+        }
+    }
+}
+
+/// Plays a whole game over stdin and stdout in the network protocol, for frontends that
+/// drive the engine as a subprocess. Every seat is answered by the frontend: it sends one
+/// `Join` per seat, in seat order, then an `Act` for each `Decide`, answering for the seat
+/// it names. Nothing else is printed.
+fn json_game(players: usize, config: GameConfig, seed: u64) {
+    let mut names: Vec<String> = Vec::new();
+    while names.len() < players {
+        match receive_json() {
+            Some(ClientMessage::Join { name }) => {
+                let mut candidate: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+                candidate.push(&name);
+                match validate_player_names(&candidate) {
+                    Ok(()) => names.push(name),
+                    Err(error) => send_json(&HostMessage::Rejected { reason: error.to_string().trim().to_string() }),
+                }
+            }
+            Some(ClientMessage::Act { .. }) => {
+                send_json(&HostMessage::Rejected { reason: format!("expected {} join message(s) first", players) });
+            }
+            None => return,
+        }
+    }
+
+    let player_refs: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    let mut game = match Game::new(&player_refs, seed, config) {
+        Ok(game) => game,
+        Err(error) => {
+            send_json(&HostMessage::Rejected { reason: error.to_string().trim().to_string() });
+            return;
+        }
+    };
+    for player_id in 1..=players as u32 {
+        send_json(&HostMessage::Welcome { player_id, protocol_version: PROTOCOL_VERSION });
+    }
+
+    let victory = loop {
+        let decision = game.pending();
+        let action = match decision {
+            Some(decision) => {
+                send_json(&HostMessage::State { game: game.view_json(Some(decision.player_id())) });
+                send_json(&HostMessage::Decide { player_id: decision.player_id(), decision });
+                match receive_json() {
+                    Some(ClientMessage::Act { action }) => Some(action),
+                    Some(ClientMessage::Join { .. }) => {
+                        send_json(&HostMessage::Rejected { reason: "every seat has already joined".to_string() });
+                        continue;
+                    }
+                    None => {
+                        game.abandon();
+                        break None;
+                    }
+                }
+            }
+            None => None,
+        };
+        match game.advance(action) {
+            Ok(Progress::GameOver(victory)) => break Some(victory),
+            Ok(Progress::TurnEnded) => send_json(&HostMessage::State { game: game.view_json(None) }),
+            Ok(_) => {}
+            Err(error) => send_json(&HostMessage::Rejected { reason: error.to_string() }),
+        }
+    };
+    send_json(&HostMessage::State { game: game.view_json(None) });
+    send_json(&HostMessage::GameOver { victory });
+}
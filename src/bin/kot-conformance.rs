@@ -0,0 +1,69 @@
+//! Checks a third-party implementation of the network protocol, for authors who want to
+//! know their host or client will play with this one. `host <address> [name]` joins the
+//! host at `address` and plays one seat to the end; `client <port> [seed]` waits on `port`
+//! for one client and hosts it a two-player game under the official rules against a bot.
+//! Prints PASS or FAIL for each check and exits with status 1 if any failed.
+
+use kingoftokyo::{check_client, check_host, Connection, ConformanceReport, GameConfig};
+use std::net::{TcpListener, TcpStream};
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// How long either side may take to send its next message before the run gives up on it.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(120);
+
+fn connect(stream: std::io::Result<TcpStream>) -> Result<Connection, String> {
+    let stream = stream.map_err(|error| error.to_string())?;
+    stream.set_read_timeout(Some(MESSAGE_TIMEOUT)).map_err(|error| error.to_string())?;
+    Connection::new(stream).map_err(|error| error.to_string())
+}
+
+/// Runs the suite `args` ask for; None if they don't make sense.
+fn run(args: &[String]) -> Option<Result<ConformanceReport, String>> {
+    let run = match args {
+        [mode, address, rest @ ..] if mode == "host" && rest.len() <= 1 => {
+            let name = rest.first().map_or("Conformance", |name| name.as_str());
+            connect(TcpStream::connect(address.as_str()))
+                .map_err(|error| format!("couldn't connect to {}: {}", address, error))
+                .map(|mut host| {
+                    println!("Joined {} as {}; playing the seat to the end...", address, name);
+                    check_host(&mut host, name, 0)
+                })
+        }
+        [mode, port, rest @ ..] if mode == "client" && rest.len() <= 1 => serve(port, rest.first()),
+        _ => return None,
+    };
+    Some(run)
+}
+
+/// Waits on `port` for one client and checks it, in a game seeded by `seed` (0 if not given).
+fn serve(port: &str, seed: Option<&String>) -> Result<ConformanceReport, String> {
+    let port: u16 = port.parse().map_err(|_| format!("{} isn't a port number", port))?;
+    let seed = match seed {
+        Some(seed) => seed.parse().map_err(|_| format!("{} isn't a seed", seed))?,
+        None => 0,
+    };
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|error| format!("couldn't listen on port {}: {}", port, error))?;
+    println!("Waiting for a client on port {}...", port);
+    let mut client = connect(listener.accept().map(|(stream, _)| stream))?;
+    Ok(check_client(&mut client, GameConfig::official(), seed))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Some(Ok(report)) => {
+            print!("{}", report);
+            if report.passed() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        None => {
+            println!("usage: kot-conformance host <address> [name]");
+            println!("       kot-conformance client <port> [seed]");
+            ExitCode::from(2)
+        }
+        Some(Err(error)) => {
+            println!("FAIL {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Checks a recorded game against the rules, for tournaments that accept games played
+//! with third-party clients. Takes a replay (as written by `--record`) or a saved game,
+//! plays every action through the engine and prints PASS, or FAIL with the first action
+//! that breaks the rules. Exits with status 1 on FAIL.
+
+use kingoftokyo::{Game, Replay, Victory};
+use std::process::ExitCode;
+
+/// Reads `json` as a replay or, failing that, a saved game. Replays from other clients
+/// may leave out the checksum; one that is there has to match.
+fn read_recording(json: &str) -> Result<Replay, String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|error| format!("not JSON: {}", error))?;
+    if value.get("config").is_some() {
+        let replay = if value.get("checksum").is_some() { Replay::from_json(json) } else { serde_json::from_value(value) };
+        return replay.map_err(|error| format!("not a valid replay: {}", error));
+    }
+    let game = Game::from_json(json).map_err(|error| format!("neither a replay nor a saved game: {}", error))?;
+    Replay::from_game(&game).ok_or_else(|| "the saved game was resumed from a save without recorded actions".to_string())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [path] = args.as_slice() else {
+        println!("usage: kot-verify <replay or save file>");
+        return ExitCode::from(2);
+    };
+    let replay = match std::fs::read_to_string(path).map_err(|error| error.to_string()).and_then(|json| read_recording(&json)) {
+        Ok(replay) => replay,
+        Err(error) => {
+            println!("FAIL {}: {}", path, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match replay.verify() {
+        Ok(game) => {
+            let name = |id: u32| game.get_player(id).map_or("?", |p| p.name.as_str());
+            let result = match game.victory() {
+                Some(Victory::Points { player_id, max_vp }) => format!("{} reached {} VP", name(player_id), max_vp),
+                Some(Victory::LastStanding { player_id }) => format!("{} was the last monster standing", name(player_id)),
+                Some(Victory::NoSurvivors) => "no monster survived".to_string(),
+                None => "the game was left unfinished".to_string(),
+            };
+            println!("PASS {}: {} actions over {} turns; {}", path, replay.actions.len(), game.turn, result);
+            ExitCode::SUCCESS
+        }
+        Err(violation) => {
+            println!("FAIL {}: {}", path, violation);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Power cards: the starter deck and the three-card shop they are bought from.
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Whether a card is used up when bought or stays in front of its owner.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CardKind {
+    Discard, // Takes effect once, then goes to the discard pile
+    Keep,    // Stays with the buyer for the rest of the game
+}
+
+/// Something a card does the moment it is bought.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CardEffect {
+    GainVp(u8),
+    GainEnergy(u8),
+    Heal(u8),         // Works in Tokyo too, but not in overtime
+    DamageOthers(u8), // Every other living monster, wherever they are
+    RaiseMaxHp(u8),   // Also heals the same amount
+    ExtraTurn,
+}
+
+impl CardEffect {
+    /// This effect `n` times over, e.g. for an Energy action bought several times at once.
+    /// An extra turn is only ever granted once.
+    pub fn times(self, n: u8) -> CardEffect {
+        match self {
+            CardEffect::GainVp(amount) => CardEffect::GainVp(amount.saturating_mul(n)),
+            CardEffect::GainEnergy(amount) => CardEffect::GainEnergy(amount.saturating_mul(n)),
+            CardEffect::Heal(amount) => CardEffect::Heal(amount.saturating_mul(n)),
+            CardEffect::DamageOthers(damage) => CardEffect::DamageOthers(damage.saturating_mul(n)),
+            CardEffect::RaiseMaxHp(amount) => CardEffect::RaiseMaxHp(amount.saturating_mul(n)),
+            CardEffect::ExtraTurn => CardEffect::ExtraTurn,
+        }
+    }
+
+    /// What the effect does, e.g. "+1 VP" or "heal 2 HP".
+    pub fn describe(&self) -> String {
+        match self {
+            CardEffect::GainVp(amount) => format!("+{} VP", amount),
+            CardEffect::GainEnergy(amount) => format!("+{} Energy", amount),
+            CardEffect::Heal(amount) => format!("heal {} HP", amount),
+            CardEffect::DamageOthers(damage) => format!("{} damage to every other monster", damage),
+            CardEffect::RaiseMaxHp(amount) => format!("+{} max HP", amount),
+            CardEffect::ExtraTurn => "take another turn".to_string(),
+        }
+    }
+}
+
+/// A lasting ability granted by a Keep card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum Passive {
+    BonusEnergy,   // +1 Energy whenever you roll any Energy
+    BonusHeal,     // +1 HP whenever your Hearts heal you
+    BonusDamage,   // +1 damage whenever you attack
+    Armor,         // Ignore any hit of exactly 1 damage
+    ExtraReroll,   // One more reroll each turn
+    SolarPowered,  // End of turn: +1 Energy if you have none
+    EnergyHoarder, // End of turn: +1 VP for every 6 Energy you hold
+    Herbivore,     // End of turn: +1 VP if you dealt no damage this turn
+    Discount,      // Power cards cost 1 less Energy
+}
+
+impl Passive {
+    pub const ALL: [Passive; 9] = [
+        Passive::BonusEnergy, Passive::BonusHeal, Passive::BonusDamage, Passive::Armor,
+        Passive::ExtraReroll, Passive::SolarPowered, Passive::EnergyHoarder, Passive::Herbivore, Passive::Discount,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Passive::BonusEnergy => "Bonus Energy",
+            Passive::BonusHeal => "Bonus Heal",
+            Passive::BonusDamage => "Bonus Damage",
+            Passive::Armor => "Armor",
+            Passive::ExtraReroll => "Extra Reroll",
+            Passive::SolarPowered => "Solar Powered",
+            Passive::EnergyHoarder => "Energy Hoarder",
+            Passive::Herbivore => "Herbivore",
+            Passive::Discount => "Discount",
+        }
+    }
+}
+
+/// A power card from the deck.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Card {
+    pub name: &'static str,
+    pub cost: u8, // Energy
+    pub kind: CardKind,
+    pub description: &'static str,
+    pub effects: &'static [CardEffect], // Applied on purchase, in order
+    pub passive: Option<Passive>,       // Keep cards only
+}
+
+const fn discard(name: &'static str, cost: u8, description: &'static str, effects: &'static [CardEffect]) -> Card {
+    Card { name, cost, kind: CardKind::Discard, description, effects, passive: None }
+}
+
+const fn keep(name: &'static str, cost: u8, description: &'static str, passive: Option<Passive>) -> Card {
+    Card { name, cost, kind: CardKind::Keep, description, effects: &[], passive }
+}
+
+/// One copy of each card in the starter set; the deck is shuffled from these.
+pub const STARTER_CARDS: [Card; 20] = [
+    discard("Corner Store", 3, "+1 VP", &[CardEffect::GainVp(1)]),
+    discard("Commuter Train", 4, "+2 VP", &[CardEffect::GainVp(2)]),
+    discard("Apartment Building", 5, "+3 VP", &[CardEffect::GainVp(3)]),
+    discard("Skyscraper", 6, "+4 VP", &[CardEffect::GainVp(4)]),
+    discard("Energize", 8, "+9 Energy", &[CardEffect::GainEnergy(9)]),
+    discard("Heal", 3, "Heal 2 HP", &[CardEffect::Heal(2)]),
+    discard("Fire Blast", 3, "2 damage to every other monster", &[CardEffect::DamageOthers(2)]),
+    discard("Gas Refinery", 6, "+2 VP and 3 damage to every other monster", &[CardEffect::GainVp(2), CardEffect::DamageOthers(3)]),
+    discard("Nuclear Power Plant", 6, "+2 VP and heal 3 HP", &[CardEffect::GainVp(2), CardEffect::Heal(3)]),
+    discard("Frenzy", 7, "Take another turn after this one", &[CardEffect::ExtraTurn]),
+    Card { effects: &[CardEffect::RaiseMaxHp(2)], ..keep("Even Bigger", 4, "+2 max HP (and heal 2 HP)", None) },
+    keep("Friend of Children", 3, "+1 Energy whenever you roll Energy", Some(Passive::BonusEnergy)),
+    keep("Regeneration", 4, "+1 HP whenever your Hearts heal you", Some(Passive::BonusHeal)),
+    keep("Acid Attack", 6, "+1 damage whenever you attack", Some(Passive::BonusDamage)),
+    keep("Armor Plating", 4, "Ignore any hit of exactly 1 damage", Some(Passive::Armor)),
+    keep("Giant Brain", 5, "One extra reroll each turn", Some(Passive::ExtraReroll)),
+    keep("Solar Powered", 2, "End of turn: +1 Energy if you have none", Some(Passive::SolarPowered)),
+    keep("Energy Hoarder", 3, "End of turn: +1 VP per 6 Energy you hold", Some(Passive::EnergyHoarder)),
+    keep("Herbivore", 5, "End of turn: +1 VP if you dealt no damage", Some(Passive::Herbivore)),
+    keep("Alien Metabolism", 3, "Power cards cost you 1 less Energy", Some(Passive::Discount)),
+];
+
+/// Cards are saved by name and looked up in the card list again when loaded.
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        STARTER_CARDS.iter()
+            .find(|card| card.name == name)
+            .copied()
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown card \"{}\"", name)))
+    }
+}
+
+/// The draw deck, the face-up cards on sale, and the discard pile. Spent cards are not
+/// reshuffled, so the shop can run dry in a long game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shop {
+    #[serde(default)]
+    deck: Vec<Card>,     // Top of the deck is the end of the Vec
+    face_up: Vec<Card>,  // At most `Shop::SIZE` cards, in slot order
+    discards: Vec<Card>, // Swept and used-up cards
+    #[serde(default = "crate::hidden_stream")]
+    rng: ChaCha12Rng,         // The deck's own stream, derived from the game seed
+}
+
+impl Shop {
+    /// Cards on sale at once.
+    pub const SIZE: usize = 3;
+    /// Energy it costs to discard the whole shop and reveal new cards.
+    pub const SWEEP_COST: u8 = 2;
+
+    /// Shuffles `cards` with `seed` and lays out the first cards. Returns the shop and the
+    /// cards revealed.
+    pub(crate) fn new(cards: &[Card], seed: u64) -> (Self, Vec<Card>) {
+        let mut shop = Shop { deck: cards.to_vec(), face_up: Vec::new(), discards: Vec::new(), rng: ChaCha12Rng::seed_from_u64(seed) };
+        shop.deck.shuffle(&mut shop.rng);
+        let revealed = shop.refill();
+        (shop, revealed)
+    }
+
+    /// The cards for sale, in slot order.
+    pub fn face_up(&self) -> &[Card] {
+        &self.face_up
+    }
+
+    /// Cards left in the draw deck.
+    pub fn deck_len(&self) -> usize {
+        self.deck.len()
+    }
+
+    /// Deals cards until every slot is filled or the deck is empty, returning the new ones.
+    fn refill(&mut self) -> Vec<Card> {
+        let mut revealed = Vec::new();
+        while self.face_up.len() < Shop::SIZE {
+            match self.deck.pop() {
+                Some(card) => {
+                    self.face_up.push(card);
+                    revealed.push(card);
+                }
+                None => break,
+            }
+        }
+        revealed
+    }
+
+    /// Takes the card in `slot` off the shop and deals its replacement.
+    pub(crate) fn take(&mut self, slot: usize) -> Option<(Card, Vec<Card>)> {
+        if slot >= self.face_up.len() {
+            return None;
+        }
+        let card = self.face_up.remove(slot);
+        Some((card, self.refill()))
+    }
+
+    /// Shuffles the cards still in the deck with a new stream from `seed`. The face-up cards
+    /// and discards stay where they are.
+    pub(crate) fn reshuffle(&mut self, seed: u64) {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+        self.deck.shuffle(&mut self.rng);
+    }
+
+    /// Puts a used-up card on the discard pile.
+    pub(crate) fn discard(&mut self, card: Card) {
+        self.discards.push(card);
+    }
+
+    /// Discards every face-up card and deals a fresh set, returning the new ones.
+    pub(crate) fn sweep(&mut self) -> Vec<Card> {
+        self.discards.append(&mut self.face_up);
+        self.refill()
+    }
+}
@@ -0,0 +1,63 @@
+//! Where a game gets the time from, for event timestamps and phase timings. Embedders
+//! can pass in their own `Clock`, e.g. one reading `performance.now()` in a browser, where
+//! the standard library has no clock to offer.
+
+use std::time::{Duration, SystemTime};
+
+/// A source of time for a `Game`. See `Game::set_clock`.
+pub trait Clock {
+    /// Time since some fixed moment of the clock's choosing, never going backwards. Only
+    /// differences between readings are used.
+    fn monotonic(&self) -> Duration;
+
+    /// The real-world time now.
+    fn wall_clock(&self) -> SystemTime;
+}
+
+/// The operating system's clocks. The default everywhere but WebAssembly.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock { origin: std::time::Instant::now() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn monotonic(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn wall_clock(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that never moves: everything takes no time and happens at the UNIX epoch. The
+/// default on WebAssembly, and handy wherever timestamps should be reproducible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StoppedClock;
+
+impl Clock for StoppedClock {
+    fn monotonic(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn wall_clock(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH
+    }
+}
+
+/// The clock a new or loaded game starts with.
+pub(crate) fn default_clock() -> Box<dyn Clock> {
+    #[cfg(not(target_arch = "wasm32"))]
+    return Box::new(SystemClock::default());
+    #[cfg(target_arch = "wasm32")]
+    return Box::new(StoppedClock);
+}
@@ -0,0 +1,360 @@
+//! A conformance suite for the network protocol (see `HostMessage` and `ClientMessage`),
+//! for authors of third-party hosts and clients. `check_host` joins a host as one client
+//! and plays its seat to the end; `check_client` hosts a two-player game for a client
+//! against a bot. Both check the handshake (including the `PROTOCOL_VERSION` it names),
+//! that every line fits the message schema, and that refusals go as documented: a host
+//! refuses an action that doesn't answer its decision, or a second `Join`, and asks again;
+//! a client answers a `Decide` sent again after a `Rejected`. `check_host` also checks that
+//! every `State` is filtered: a view with nothing in it a spectator or another seat
+//! mustn't see.
+//!
+//! Reconnection isn't checked because it isn't part of the protocol yet: in version 1 a
+//! client that drops ends the game, and a client can't take its seat back. It is left
+//! for a later protocol version, which will add a check for it here.
+use crate::view::view_leak;
+use crate::{validate_player_names, Action, AgentKind, ClientMessage, Connection, Decision, Game, GameConfig, HostMessage, Progress, PROTOCOL_VERSION};
+use std::fmt;
+use std::io;
+
+/// Refused messages in a row after which `check_client` stops the game.
+pub const MAX_REFUSALS: usize = 10;
+
+/// The name `check_client` gives the bot playing against the client.
+const BOT_NAME: &str = "Conformance Bot";
+
+/// One thing the suite checks, and how it went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolCheck {
+    pub name: &'static str,
+    pub result: Result<(), String>, // Err says what broke it, or why it was never reached
+}
+
+/// Every check of one run, in the order they're listed.
+#[derive(Debug, Clone)]
+pub struct ConformanceReport {
+    pub checks: Vec<ProtocolCheck>,
+    pub messages: usize, // Messages read from the implementation under test
+}
+
+impl ConformanceReport {
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.result {
+                Ok(()) => writeln!(f, "PASS {}", check.name)?,
+                Err(reason) => writeln!(f, "FAIL {}: {}", check.name, reason)?,
+            }
+        }
+        let failed = self.checks.iter().filter(|check| check.result.is_err()).count();
+        writeln!(f, "{} of {} checks passed, {} messages read.", self.checks.len() - failed, self.checks.len(), self.messages)
+    }
+}
+
+/// The checks of a run as they go: None until one passes or fails. The first failure sticks.
+struct Checks {
+    results: Vec<(&'static str, Option<Result<(), String>>)>,
+    messages: usize,
+}
+
+impl Checks {
+    fn new(names: &[&'static str]) -> Self {
+        Checks { results: names.iter().map(|&name| (name, None)).collect(), messages: 0 }
+    }
+
+    fn slot(&mut self, name: &'static str) -> &mut Option<Result<(), String>> {
+        &mut self.results.iter_mut().find(|(check, _)| *check == name).expect("Checks are listed up front").1
+    }
+
+    fn pass(&mut self, name: &'static str) {
+        self.slot(name).get_or_insert(Ok(()));
+    }
+
+    fn fail(&mut self, name: &'static str, reason: impl Into<String>) {
+        let slot = self.slot(name);
+        if !matches!(slot, Some(Err(_))) {
+            *slot = Some(Err(reason.into()));
+        }
+    }
+
+    /// The report, counting every check still open as never reached, for `why`.
+    fn finish(self, why: &str) -> ConformanceReport {
+        let checks = self.results.into_iter()
+            .map(|(name, result)| ProtocolCheck { name, result: result.unwrap_or_else(|| Err(format!("never reached: {}", why))) })
+            .collect();
+        ConformanceReport { checks, messages: self.messages }
+    }
+}
+
+/// An action of the wrong kind for `decision`, which every host has to refuse.
+fn mismatched(decision: &Decision) -> Action {
+    match decision {
+        Decision::Spectate { .. } => Action::Enter(false),
+        _ => Action::Spectate(false),
+    }
+}
+
+/// A refusal `check_host` provoked on purpose: the decision it was provoked on, and
+/// whether the `Rejected` has come yet.
+struct Provoked {
+    check: &'static str,
+    decision: Decision,
+    rejected: bool,
+}
+
+/// Joins the host on the other end of `host` as `name` and plays that seat to the end
+/// with a random bot seeded by `seed`. Checks, in order:
+///
+/// - handshake: the host answers the `Join` with one `Welcome` naming a seat and this
+///   build's `PROTOCOL_VERSION`.
+/// - schema: every line the host sends is a host message.
+/// - state: every `State` is a view of a game (see `Game::view_json`) with this client's seat in it.
+/// - spectator filtering: no `State` carries the seed, an RNG stream, the action record,
+///   the shop deck or another seat's evolution hand, whether in place or in its history.
+/// - decisions: every `Decide` is for this client's seat and is the decision its `State` is waiting on.
+/// - illegal action: an action that doesn't answer the first decision is refused, and the decision asked again.
+/// - second join: a `Join` sent in answer to the next decision is refused, and the decision asked again.
+/// - game over: the game ends with a `GameOver` naming the winner its last `State` shows.
+pub fn check_host(host: &mut Connection, name: &str, seed: u64) -> ConformanceReport {
+    let mut checks = Checks::new(&["handshake", "schema", "state", "spectator filtering", "decisions", "illegal action", "second join", "game over"]);
+    if let Err(error) = host.send(&ClientMessage::Join { name: name.to_string() }) {
+        return checks.finish(&format!("the join couldn't be sent ({})", error));
+    }
+    let mut bot = AgentKind::Random.build(seed);
+    let mut seat: Option<u32> = None;
+    let mut game: Option<Game> = None;
+    let mut probes = ["illegal action", "second join"].into_iter();
+    let mut provoked: Option<Provoked> = None;
+    let why = loop {
+        let message = match host.receive::<HostMessage>() {
+            Ok(message) => message,
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                checks.fail("schema", format!("the host sent a line that isn't a host message: {}", error));
+                break "the host's messages couldn't be read";
+            }
+            Err(error) => break if error.kind() == io::ErrorKind::UnexpectedEof { "the host closed the connection" } else { "the connection failed" },
+        };
+        checks.messages += 1;
+        checks.pass("schema");
+
+        let Some(player_id) = seat else {
+            match message {
+                HostMessage::Welcome { protocol_version, .. } if protocol_version != PROTOCOL_VERSION => {
+                    checks.fail("handshake", format!("the host speaks protocol version {}, but this suite checks version {}", protocol_version, PROTOCOL_VERSION));
+                }
+                HostMessage::Welcome { player_id, .. } if player_id >= 1 => {
+                    seat = Some(player_id);
+                    checks.pass("handshake");
+                    continue;
+                }
+                HostMessage::Rejected { reason } => checks.fail("handshake", format!("the host turned the join away: {}", reason)),
+                other => checks.fail("handshake", format!("expected a welcome, got {:?}", other)),
+            }
+            break "the handshake failed";
+        };
+        match message {
+            HostMessage::Welcome { .. } => checks.fail("handshake", "the host sent a second welcome"),
+            HostMessage::State { game: json } => {
+                if let Ok(view) = serde_json::from_str(&json) {
+                    match view_leak(&view, Some(player_id)) {
+                        Some(leak) => checks.fail("spectator filtering", format!("a state gives away more than player {} may see: {}", player_id, leak)),
+                        None => checks.pass("spectator filtering"),
+                    }
+                }
+                match Game::from_view_json(&json) {
+                    Ok(state) if state.get_player(player_id).is_some() => {
+                        checks.pass("state");
+                        game = Some(state);
+                    }
+                    Ok(_) => checks.fail("state", format!("a state has no player {}, the seat the host gave", player_id)),
+                    Err(error) => checks.fail("state", format!("a state isn't a view of a game: {}", error)),
+                }
+            }
+            HostMessage::Rejected { reason } => match provoked.as_mut() {
+                Some(probe) if !probe.rejected => probe.rejected = true,
+                _ => checks.fail("decisions", format!("the host refused a proper answer: {}", reason)),
+            },
+            HostMessage::Decide { player_id: asked, decision } => {
+                if asked != player_id || decision.player_id() != player_id {
+                    checks.fail("decisions", format!("asked for player {}'s decision, but this client is player {}", decision.player_id(), player_id));
+                }
+                let Some(state) = game.as_ref() else {
+                    checks.fail("decisions", "a decide came before any state");
+                    break "there was no state to decide from";
+                };
+                if state.pending() == Some(decision) {
+                    checks.pass("decisions");
+                } else {
+                    checks.fail("decisions", format!("asked {:?}, but the state before it is waiting on {:?}", decision, state.pending()));
+                }
+                if let Some(probe) = provoked.take() {
+                    if !probe.rejected {
+                        checks.fail(probe.check, format!("the host moved on without refusing; it asked {:?} next", decision));
+                    } else if probe.decision != decision {
+                        checks.fail(probe.check, format!("after refusing it asked {:?} instead of {:?} again", decision, probe.decision));
+                    } else {
+                        checks.pass(probe.check);
+                    }
+                }
+                let sent = match probes.next() {
+                    Some(check) => {
+                        provoked = Some(Provoked { check, decision, rejected: false });
+                        match check {
+                            "illegal action" => host.send(&ClientMessage::Act { action: mismatched(&decision) }),
+                            _ => host.send(&ClientMessage::Join { name: name.to_string() }),
+                        }
+                    }
+                    None => host.send(&ClientMessage::Act { action: decision.ask(bot.as_mut(), state) }),
+                };
+                if sent.is_err() {
+                    break "the connection failed";
+                }
+            }
+            HostMessage::GameOver { victory } => {
+                match game.as_ref().map(|state| state.victory()) {
+                    Some(last) if last == victory => checks.pass("game over"),
+                    Some(last) => checks.fail("game over", format!("the game over names {:?}, but the last state shows {:?}", victory, last)),
+                    None => checks.fail("game over", "the game ended before any state"),
+                }
+                break "the game ended first";
+            }
+        }
+    };
+    checks.finish(why)
+}
+
+/// Hosts a two-player game under `config` for the client on the other end of `client`:
+/// the client takes the first seat and a random bot seeded by `seed` the second. Checks,
+/// in order:
+///
+/// - handshake: the client's first message is a `Join` with a name a game accepts.
+/// - schema: every line the client sends is a client message.
+/// - answers: every `Act` answers the decision it was sent for, and nothing else is sent mid-game.
+/// - rejection: when its first answer is refused, the client answers the `Decide` sent again.
+///
+/// The client is sent a `State` before each of its decisions and at the end of every
+/// turn, as `--host` does. After `MAX_REFUSALS` refused messages in a row the game is
+/// abandoned.
+pub fn check_client(client: &mut Connection, config: GameConfig, seed: u64) -> ConformanceReport {
+    let mut checks = Checks::new(&["handshake", "schema", "answers", "rejection"]);
+    let name = match client.receive::<ClientMessage>() {
+        Ok(ClientMessage::Join { name }) => name,
+        Ok(other) => {
+            checks.messages += 1;
+            checks.fail("handshake", format!("expected a join, got {:?}", other));
+            let _ = client.send(&HostMessage::Rejected { reason: "expected a join message".to_string() });
+            return checks.finish("the handshake failed");
+        }
+        Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+            checks.fail("schema", format!("the client sent a line that isn't a client message: {}", error));
+            return checks.finish("the handshake failed");
+        }
+        Err(error) => return checks.finish(&format!("the client sent nothing ({})", error)),
+    };
+    checks.messages += 1;
+    checks.pass("schema");
+    let names = [name.as_str(), BOT_NAME];
+    let mut game = match validate_player_names(&names).and_then(|()| Game::new(&names, seed, config)) {
+        Ok(game) => game,
+        Err(error) => {
+            let reason = error.to_string().trim().to_string();
+            checks.fail("handshake", format!("the join was refused: {}", reason));
+            let _ = client.send(&HostMessage::Rejected { reason });
+            return checks.finish("the handshake failed");
+        }
+    };
+    if client.send(&HostMessage::Welcome { player_id: 1, protocol_version: PROTOCOL_VERSION }).is_err() {
+        return checks.finish("the connection failed");
+    }
+    checks.pass("handshake");
+
+    let mut bot = AgentKind::Random.build(seed);
+    let mut refuse_next = true;
+    let mut refused = false;
+    let mut refusals = 0;
+    let victory = loop {
+        if refusals >= MAX_REFUSALS {
+            checks.fail("answers", format!("{} messages in a row were refused; the game was abandoned", refusals));
+            game.abandon();
+            break None;
+        }
+        let decision = game.pending();
+        let action = match decision {
+            Some(decision) if decision.player_id() == 1 => {
+                let answer = client.send(&HostMessage::State { game: game.view_json(Some(1)) })
+                    .and_then(|_| client.send(&HostMessage::Decide { player_id: 1, decision }))
+                    .and_then(|_| client.receive::<ClientMessage>());
+                let answer = match answer {
+                    Ok(ClientMessage::Act { action }) => {
+                        checks.messages += 1;
+                        checks.pass("schema");
+                        if refused {
+                            checks.pass("rejection");
+                            refused = false;
+                        }
+                        if refuse_next {
+                            refuse_next = false;
+                            refused = true;
+                            Err("the conformance suite refuses the first answer, to check it is asked again".to_string())
+                        } else {
+                            Ok(action)
+                        }
+                    }
+                    Ok(ClientMessage::Join { .. }) => {
+                        checks.messages += 1;
+                        checks.fail("answers", "the client sent a join mid-game");
+                        Err("already joined".to_string())
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                        checks.fail("schema", format!("the client sent a line that isn't a client message: {}", error));
+                        Err(format!("not a message: {}", error))
+                    }
+                    Err(_) => {
+                        game.abandon();
+                        break None;
+                    }
+                };
+                match answer {
+                    Ok(action) => Some(action),
+                    Err(reason) => {
+                        refusals += 1;
+                        if client.send(&HostMessage::Rejected { reason }).is_err() {
+                            game.abandon();
+                            break None;
+                        }
+                        continue;
+                    }
+                }
+            }
+            Some(decision) => Some(decision.ask(bot.as_mut(), &game)),
+            None => None,
+        };
+        let progress = game.advance(action);
+        if progress.is_ok() {
+            refusals = 0;
+        }
+        match progress {
+            Ok(Progress::GameOver(victory)) => break Some(victory),
+            Ok(Progress::TurnEnded) => {
+                let _ = client.send(&HostMessage::State { game: game.view_json(Some(1)) });
+            }
+            Ok(Progress::Decide(_)) => {}
+            Err(error) => {
+                checks.fail("answers", format!("an answer was refused: {}", error));
+                refusals += 1;
+                let _ = client.send(&HostMessage::Rejected { reason: error.to_string() });
+            }
+        }
+    };
+    let _ = client.send(&HostMessage::State { game: game.view_json(Some(1)) });
+    let _ = client.send(&HostMessage::GameOver { victory });
+    if victory.is_some() {
+        checks.pass("answers");
+    }
+    checks.finish(if victory.is_some() { "the game ended first" } else { "the client left before the game ended" })
+}
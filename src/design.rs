@@ -0,0 +1,91 @@
+//! A kit for monsters that don't come in the box. The official monsters' abilities are
+//! `Passive`s running through the `GameEffect` hooks, and a `MonsterDesign` is built from
+//! the same hooks, so a third-party crate can define a monster without touching the engine:
+//!
+//! ```
+//! use kingoftokyo::{CardEffect, Game, GameConfig, GameEffect, MonsterDesign, TokyoZone};
+//!
+//! /// Scores 1 VP whenever it moves into Tokyo.
+//! struct Showboat;
+//!
+//! impl GameEffect for Showboat {
+//!     fn name(&self) -> &str {
+//!         "Showboat"
+//!     }
+//!
+//!     fn on_enter_tokyo(&self, _game: &Game, _owner: u32, _zone: TokyoZone) -> Vec<CardEffect> {
+//!         vec![CardEffect::GainVp(1)]
+//!     }
+//! }
+//!
+//! let design = MonsterDesign::new("Space Penguin", "+1 VP whenever you enter Tokyo").with_effect(Showboat);
+//! assert!(design.validate().is_ok());
+//!
+//! let mut game = Game::new(&["Ann", "Bo"], 7, GameConfig::official()).unwrap();
+//! game.add_monster(1, design).unwrap();
+//! assert_eq!(game.designed_monster(1), Some("Space Penguin"));
+//! assert_eq!(game.effects_of(1)[0].name(), "Showboat");
+//! ```
+
+use crate::{ConfigError, ConfigIssue, GameEffect, Monster, Passive};
+
+/// A custom monster: its name, its rules text for setup menus, and the effects that carry
+/// the ability out. Register one for a player with `Game::add_monster`.
+pub struct MonsterDesign {
+    pub name: String,
+    pub ability: String, // One line of rules text, like `Monster::ability`
+    pub effects: Vec<Box<dyn GameEffect>>,
+}
+
+impl MonsterDesign {
+    /// A design with no effects yet; add them with `with_effect`.
+    pub fn new(name: &str, ability: &str) -> Self {
+        MonsterDesign { name: name.to_string(), ability: ability.to_string(), effects: Vec::new() }
+    }
+
+    /// Adds an effect to the ability. Effects run in the order they were added.
+    pub fn with_effect(mut self, effect: impl GameEffect + 'static) -> Self {
+        self.effects.push(Box::new(effect));
+        self
+    }
+
+    /// Every problem with the design: a blank name or rules text, a name taken by an
+    /// official monster, no effects, or an effect with a blank name or the name of a
+    /// built-in ability (which would make `DamageBlocked` events ambiguous).
+    pub fn issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let name = self.name.trim();
+        if name.is_empty() {
+            issues.push(ConfigIssue::new("name", "the monster has no name".to_string(), "give it a name to show at setup".to_string()));
+        } else if Monster::ALL.iter().any(|monster| monster.name().eq_ignore_ascii_case(name)) {
+            issues.push(ConfigIssue::new("name", format!("{} is an official monster", name), "pick a name of its own".to_string()));
+        }
+        if self.ability.trim().is_empty() {
+            issues.push(ConfigIssue::new("ability", "the ability has no rules text".to_string(), "describe it in one line for setup menus".to_string()));
+        }
+        if self.effects.is_empty() {
+            issues.push(ConfigIssue::new("effects", "the monster has no effects".to_string(), "add one with `with_effect`".to_string()));
+        }
+        for (i, effect) in self.effects.iter().enumerate() {
+            let field = format!("effects[{}]", i + 1);
+            let effect_name = effect.name().trim();
+            if effect_name.is_empty() {
+                issues.push(ConfigIssue::new(&field, "the effect has no name".to_string(), "return one from `GameEffect::name`".to_string()));
+            } else if Passive::ALL.iter().any(|passive| passive.name().eq_ignore_ascii_case(effect_name)) {
+                issues.push(ConfigIssue::new(&field, format!("\"{}\" is the name of a built-in ability", effect_name),
+                    "give the effect a name of its own".to_string()));
+            }
+        }
+        issues
+    }
+
+    /// Checks the design, reporting every issue at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let issues = self.issues();
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { source: format!("monster design \"{}\"", self.name), issues })
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Effect hooks: the fixed points of a turn where cards, monster abilities and house rules
+//! change what happens. Every built-in `Passive` is a `GameEffect`, and embedders can
+//! register their own with `Game::add_effect` for custom cards and variants, without
+//! touching the dice resolution itself.
+
+use crate::{Card, CardEffect, DamageSource, DieResult, Game, Passive, TokyoZone};
+
+/// One hit about to land, as `GameEffect::modify_damage` sees it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit {
+    pub attacker_id: u32,
+    pub target_id: u32,
+    pub damage: u8,
+    pub source: DamageSource,
+}
+
+/// A roll just made, as `GameEffect::on_dice_rolled` sees it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roll {
+    pub roller: u32,
+    pub dice: [DieResult; 6],
+    pub kept: [bool; 6], // Held back from this roll
+    pub roll: u8,        // From 1
+    pub rolls: u8,       // Rolls this turn; the last one is `roll == rolls`
+}
+
+/// A change an effect makes to dice that were just rolled, by index into the dice. Kept
+/// dice stay as they are, whatever an effect asks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceChange {
+    Reroll(usize),         // Rolled again with the roller's own dice
+    Set(usize, DieResult), // Turned to this face
+}
+
+/// A rule change hooked into the turn. Every hook defaults to changing nothing, so an
+/// effect only implements the ones it needs. Hooks other than `on_dice_rolled` are only
+/// called for the effects of the player they concern (the `owner`), in the order
+/// `Game::effects_of` lists them, and each `modify_` hook is handed the amount as the
+/// effects before it left it. The `CardEffect`s an `on_` hook returns are applied at once,
+/// in order, before the next effect is asked.
+pub trait GameEffect {
+    /// Shown when the effect blocks a hit, and credited for any card damage it deals.
+    fn name(&self) -> &str;
+
+    /// The owner's upkeep, after any VP for holding Tokyo.
+    fn on_turn_start(&self, _game: &Game, _owner: u32) -> Vec<CardEffect> {
+        Vec::new()
+    }
+
+    /// Rerolls the owner gets on top of `rules.rerolls`.
+    fn extra_rerolls(&self, _game: &Game, _owner: u32) -> u8 {
+        0
+    }
+
+    /// Energy the owner gains from rolling `dice` (only asked if they rolled any).
+    fn modify_energy(&self, _game: &Game, _owner: u32, _dice: &[DieResult; 6], amount: u8) -> u8 {
+        amount
+    }
+
+    /// HP the owner heals with the Hearts they rolled (only asked if they can heal).
+    fn modify_healing(&self, _game: &Game, _owner: u32, _dice: &[DieResult; 6], amount: u8) -> u8 {
+        amount
+    }
+
+    /// `roll.roller` (the owner or anyone else) has just rolled. Unlike the other hooks
+    /// this one is asked of every living monster's effects, the roller's first and then the
+    /// others' in seating order, each seeing the dice as the changes before it left them.
+    /// The changes are applied before the roller decides what to keep, or before the dice
+    /// count.
+    fn on_dice_rolled(&self, _game: &Game, _owner: u32, _roll: &Roll) -> Vec<DiceChange> {
+        Vec::new()
+    }
+
+    /// The owner's dice are scored and healed, and the attack comes next.
+    fn on_roll_resolved(&self, _game: &Game, _owner: u32, _dice: &[DieResult; 6]) -> Vec<CardEffect> {
+        Vec::new()
+    }
+
+    /// Damage each target of the owner's Claw attack takes.
+    fn modify_attack(&self, _game: &Game, _owner: u32, damage: u8) -> u8 {
+        damage
+    }
+
+    /// Damage the owner is about to take from `hit`. Bringing it to 0 blocks the hit.
+    fn modify_damage(&self, _game: &Game, _owner: u32, hit: &Hit) -> u8 {
+        hit.damage
+    }
+
+    /// What `card` costs the owner, handed the price as the economy and the effects before
+    /// this one left it. Discounts and penalties may take it below 0 along the way; see
+    /// `card_cost_floor` for where it ends up.
+    fn modify_card_cost(&self, _game: &Game, _owner: u32, _card: &Card, cost: i16) -> i16 {
+        cost
+    }
+
+    /// The lowest price `card` may reach for the owner, if this effect changes it from 0.
+    /// The lowest floor any of the owner's effects names wins; a price below 0 pays the owner.
+    fn card_cost_floor(&self, _game: &Game, _owner: u32, _card: &Card) -> Option<i16> {
+        None
+    }
+
+    /// The owner has just entered `zone` of Tokyo.
+    fn on_enter_tokyo(&self, _game: &Game, _owner: u32, _zone: TokyoZone) -> Vec<CardEffect> {
+        Vec::new()
+    }
+
+    /// The owner's buy phase is over. `dealt_damage` says whether they hurt anyone this turn.
+    fn on_turn_end(&self, _game: &Game, _owner: u32, _dealt_damage: bool) -> Vec<CardEffect> {
+        Vec::new()
+    }
+}
+
+impl GameEffect for Passive {
+    fn name(&self) -> &str {
+        Passive::name(*self)
+    }
+
+    fn extra_rerolls(&self, _game: &Game, _owner: u32) -> u8 {
+        (*self == Passive::ExtraReroll) as u8
+    }
+
+    fn modify_energy(&self, _game: &Game, _owner: u32, _dice: &[DieResult; 6], amount: u8) -> u8 {
+        amount.saturating_add((*self == Passive::BonusEnergy) as u8)
+    }
+
+    fn modify_healing(&self, _game: &Game, _owner: u32, _dice: &[DieResult; 6], amount: u8) -> u8 {
+        amount.saturating_add((*self == Passive::BonusHeal) as u8)
+    }
+
+    fn modify_attack(&self, _game: &Game, _owner: u32, damage: u8) -> u8 {
+        damage.saturating_add((*self == Passive::BonusDamage) as u8)
+    }
+
+    fn modify_damage(&self, _game: &Game, _owner: u32, hit: &Hit) -> u8 {
+        if *self == Passive::Armor && hit.damage == 1 { 0 } else { hit.damage }
+    }
+
+    fn modify_card_cost(&self, _game: &Game, _owner: u32, _card: &Card, cost: i16) -> i16 {
+        cost - (*self == Passive::Discount) as i16
+    }
+
+    fn on_turn_end(&self, game: &Game, owner: u32, dealt_damage: bool) -> Vec<CardEffect> {
+        let Some(player) = game.get_player(owner) else {
+            return Vec::new();
+        };
+        match self {
+            Passive::SolarPowered if player.energy == 0 && game.rules.energy => vec![CardEffect::GainEnergy(1)],
+            Passive::EnergyHoarder if player.energy >= 6 => vec![CardEffect::GainVp(player.energy / 6)],
+            Passive::Herbivore if !dealt_damage => vec![CardEffect::GainVp(1)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Makes every other monster roll again one unkept die showing `face`, after each of their
+/// rolls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jinx {
+    pub face: DieResult,
+}
+
+impl GameEffect for Jinx {
+    fn name(&self) -> &str {
+        "Jinx"
+    }
+
+    fn on_dice_rolled(&self, _game: &Game, owner: u32, roll: &Roll) -> Vec<DiceChange> {
+        if roll.roller == owner {
+            return Vec::new();
+        }
+        (0..6).find(|&i| !roll.kept[i] && roll.dice[i] == self.face).map(DiceChange::Reroll).into_iter().collect()
+    }
+}
+
+/// Turns one of the owner's dice to `face` after their first roll of each turn, picking
+/// the first die that shows something else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadedDie {
+    pub face: DieResult,
+}
+
+impl GameEffect for LoadedDie {
+    fn name(&self) -> &str {
+        "Loaded Die"
+    }
+
+    fn on_dice_rolled(&self, _game: &Game, owner: u32, roll: &Roll) -> Vec<DiceChange> {
+        if roll.roller != owner || roll.roll != 1 {
+            return Vec::new();
+        }
+        (0..6).find(|&i| roll.dice[i] != self.face).map(|i| DiceChange::Set(i, self.face)).into_iter().collect()
+    }
+}
@@ -0,0 +1,129 @@
+//! The Power Up! expansion: every monster has its own small deck of evolution cards. Rolling
+//! three or more Hearts draws one into the player's hand, and it can be played for free in
+//! any later buy phase.
+
+use crate::{derive_stream_seed, CardEffect, DieResult, Expansion, Game, GameEvent, Monster, Passive, RuleSet};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Whether an evolution stays in front of its owner once played.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EvolutionKind {
+    Permanent, // Stays in play for the rest of the game
+    Temporary, // Takes effect once, then is discarded
+}
+
+/// An evolution card from one monster's deck. Effects and abilities work just like a
+/// power card's.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Evolution {
+    pub name: &'static str,
+    pub monster: Monster, // Whose deck it belongs to
+    pub kind: EvolutionKind,
+    pub description: &'static str,
+    pub effects: &'static [CardEffect], // Applied when played, in order
+    pub passive: Option<Passive>,       // Permanent evolutions only
+}
+
+const fn temporary(name: &'static str, monster: Monster, description: &'static str, effects: &'static [CardEffect]) -> Evolution {
+    Evolution { name, monster, kind: EvolutionKind::Temporary, description, effects, passive: None }
+}
+
+const fn permanent(name: &'static str, monster: Monster, description: &'static str, passive: Option<Passive>) -> Evolution {
+    Evolution { name, monster, kind: EvolutionKind::Permanent, description, effects: &[], passive }
+}
+
+/// Every evolution, three per monster.
+pub const EVOLUTIONS: [Evolution; 18] = [
+    temporary("Cosmic Harvest", Monster::Alienoid, "+3 Energy", &[CardEffect::GainEnergy(3)]),
+    temporary("Tractor Beam", Monster::Alienoid, "+1 VP and 1 damage to every other monster", &[CardEffect::GainVp(1), CardEffect::DamageOthers(1)]),
+    permanent("Alien Brain", Monster::Alienoid, "One extra reroll each turn", Some(Passive::ExtraReroll)),
+    temporary("Carrot Cache", Monster::CyberBunny, "+2 Energy and +1 VP", &[CardEffect::GainEnergy(2), CardEffect::GainVp(1)]),
+    permanent("Bunny Shield", Monster::CyberBunny, "Ignore any hit of exactly 1 damage", Some(Passive::Armor)),
+    permanent("Laser Ears", Monster::CyberBunny, "+1 damage whenever you attack", Some(Passive::BonusDamage)),
+    temporary("Primal Bellow", Monster::Gigazaur, "+2 VP", &[CardEffect::GainVp(2)]),
+    temporary("Radioactive Snack", Monster::Gigazaur, "+2 Energy and heal 1 HP", &[CardEffect::GainEnergy(2), CardEffect::Heal(1)]),
+    permanent("Saurian Hide", Monster::Gigazaur, "+1 HP whenever your Hearts heal you", Some(Passive::BonusHeal)),
+    temporary("Tidal Wave", Monster::Kraken, "2 damage to every other monster", &[CardEffect::DamageOthers(2)]),
+    temporary("Deep Dive", Monster::Kraken, "Heal 3 HP", &[CardEffect::Heal(3)]),
+    permanent("Ink Cloud", Monster::Kraken, "Ignore any hit of exactly 1 damage", Some(Passive::Armor)),
+    temporary("Breath of Doom", Monster::MekaDragon, "+1 VP and 1 damage to every other monster", &[CardEffect::GainVp(1), CardEffect::DamageOthers(1)]),
+    Evolution { effects: &[CardEffect::RaiseMaxHp(2)], ..permanent("Reinforced Plating", Monster::MekaDragon, "+2 max HP (and heal 2 HP)", None) },
+    permanent("Targeting System", Monster::MekaDragon, "+1 damage whenever you attack", Some(Passive::BonusDamage)),
+    temporary("Giant Banana", Monster::TheKing, "Heal 2 HP and +1 Energy", &[CardEffect::Heal(2), CardEffect::GainEnergy(1)]),
+    temporary("Chest Thumping", Monster::TheKing, "+2 VP", &[CardEffect::GainVp(2)]),
+    permanent("Jungle Instincts", Monster::TheKing, "+1 Energy whenever you roll Energy", Some(Passive::BonusEnergy)),
+];
+
+impl Evolution {
+    /// `monster`'s evolution deck, unshuffled.
+    pub fn deck(monster: Monster) -> Vec<Evolution> {
+        EVOLUTIONS.iter().filter(|evolution| evolution.monster == monster).copied().collect()
+    }
+}
+
+/// Power Up! as an `Expansion`, turned on with `rules.power_up`.
+pub struct PowerUp;
+
+impl Expansion for PowerUp {
+    fn id(&self) -> &'static str {
+        "power_up"
+    }
+
+    fn name(&self) -> &'static str {
+        "Power Up!"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every monster gets a deck of evolution cards; three or more Hearts draw one"
+    }
+
+    fn enabled(&self, rules: &RuleSet) -> bool {
+        rules.power_up
+    }
+
+    /// Deals the monster's evolution deck, shuffled with the player's own stream.
+    fn on_monster_chosen(&self, game: &mut Game, player_id: u32, monster: Monster) {
+        let stream = Game::EVOLUTION_STREAMS + player_id as u64;
+        let mut rng = ChaCha12Rng::seed_from_u64(derive_stream_seed(game.seed, stream));
+        if let Some(player) = game.get_player_mut(player_id) {
+            player.evolution_deck = Evolution::deck(monster);
+            player.evolution_deck.shuffle(&mut rng);
+        }
+    }
+
+    /// Draws the top evolution, if any are left, on three or more Hearts. Works in Tokyo and
+    /// in overtime too; only the healing is lost there.
+    fn on_dice_counted(&self, game: &mut Game, player_id: u32, counts: &[u8; 6]) {
+        if counts[DieResult::Heart as usize] < 3 {
+            return;
+        }
+        let Some(player) = game.get_player_mut(player_id) else {
+            return;
+        };
+        let Some(evolution) = player.evolution_deck.pop() else {
+            return;
+        };
+        player.evolutions.push(evolution);
+        game.log(GameEvent::EvolutionDrawn { player_id, evolution: evolution.name.to_string() });
+    }
+}
+
+/// Evolutions are saved by name and looked up in the evolution list again when loaded.
+impl Serialize for Evolution {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Evolution {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        EVOLUTIONS.iter()
+            .find(|evolution| evolution.name == name)
+            .copied()
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown evolution \"{}\"", name)))
+    }
+}
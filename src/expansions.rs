@@ -0,0 +1,40 @@
+//! Expansions packaged as units. Each one lives in a module of its own with its content,
+//! and plugs into the game through the `Expansion` hooks while its rules option is on; the
+//! engine calls the hooks of every enabled expansion and has no branches of its own for any
+//! of them. Turning expansions on and off is a config change, e.g.
+//! `{"rules": {"power_up": true}}`.
+
+use crate::{Game, Monster, PowerUp, RuleSet};
+
+/// One expansion: what it is, the option that turns it on, and the points of the game it
+/// hooks into. Every hook defaults to doing nothing.
+pub trait Expansion: Sync {
+    /// Short name, also the `rules` option that turns it on, e.g. "power_up".
+    fn id(&self) -> &'static str;
+
+    /// The name on the box.
+    fn name(&self) -> &'static str;
+
+    /// What it adds, in one line for setup menus.
+    fn description(&self) -> &'static str;
+
+    /// Whether `rules` turn it on.
+    fn enabled(&self, rules: &RuleSet) -> bool;
+
+    /// `player_id` has just been given `monster` at setup.
+    fn on_monster_chosen(&self, _game: &mut Game, _player_id: u32, _monster: Monster) {}
+
+    /// `player_id`'s final dice have been counted (`counts` by face, as `DieResult::tally`
+    /// gives them), after Energy is paid and before Hearts heal.
+    fn on_dice_counted(&self, _game: &mut Game, _player_id: u32, _counts: &[u8; 6]) {}
+}
+
+/// Every expansion this build ships, in the order their hooks are called.
+pub static EXPANSIONS: [&dyn Expansion; 1] = [&PowerUp];
+
+impl Game {
+    /// The expansions this game's rules turn on.
+    pub fn expansions(&self) -> Vec<&'static dyn Expansion> {
+        EXPANSIONS.iter().copied().filter(|expansion| expansion.enabled(&self.rules)).collect()
+    }
+}
@@ -0,0 +1,73 @@
+//! Which optional parts of a session are still working. Narration files, profiles, bot
+//! packs, standings and event subscribers all add to a game without being needed for it,
+//! so when one fails it is switched off with a warning and the game carries on without it.
+
+use std::fmt;
+
+/// Whether a subsystem is still in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubsystemState {
+    Running,
+    Disabled { reason: String }, // Why it was switched off, e.g. the I/O error
+}
+
+/// One optional subsystem, by the name warnings use for it (e.g. "narration").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subsystem {
+    pub name: String,
+    pub state: SubsystemState,
+}
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.state {
+            SubsystemState::Running => write!(f, "{}: running", self.name),
+            SubsystemState::Disabled { reason } => write!(f, "{}: disabled ({})", self.name, reason),
+        }
+    }
+}
+
+/// The optional subsystems in use, in the order they were registered or first failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HealthRegistry {
+    subsystems: Vec<Subsystem>,
+}
+
+impl HealthRegistry {
+    pub const fn new() -> Self {
+        HealthRegistry { subsystems: Vec::new() }
+    }
+
+    /// Starts tracking `name` as running. A subsystem already known keeps its state.
+    pub fn register(&mut self, name: &str) {
+        if !self.subsystems.iter().any(|subsystem| subsystem.name == name) {
+            self.subsystems.push(Subsystem { name: name.to_string(), state: SubsystemState::Running });
+        }
+    }
+
+    /// Whether `name` may still be used. Subsystems never registered count as running.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.subsystems.iter().all(|subsystem| subsystem.name != name || subsystem.state == SubsystemState::Running)
+    }
+
+    /// Switches `name` off because of `reason`, registering it if need be. Returns the
+    /// warning to show, or None if it was already off, so each failure is reported once.
+    pub fn disable(&mut self, name: &str, reason: &str) -> Option<String> {
+        if !self.is_enabled(name) {
+            return None;
+        }
+        self.register(name);
+        let subsystem = self.subsystems.iter_mut().find(|subsystem| subsystem.name == name).expect("Registered above");
+        subsystem.state = SubsystemState::Disabled { reason: reason.to_string() };
+        Some(format!("{} failed and is switched off ({})", name, reason))
+    }
+
+    pub fn subsystems(&self) -> &[Subsystem] {
+        &self.subsystems
+    }
+
+    /// The subsystems switched off so far.
+    pub fn disabled(&self) -> impl Iterator<Item = &Subsystem> {
+        self.subsystems.iter().filter(|subsystem| subsystem.state != SubsystemState::Running)
+    }
+}
@@ -0,0 +1,147 @@
+//! What must hold between any two steps of a game, and a fuzzer that plays many random
+//! games checking it. Debug builds check after every step of `Game::advance`, so a rule
+//! change that breaks one shows up in the first game that trips it rather than as a
+//! strange score much later.
+
+use crate::{AgentKind, Game, GameConfig, Monster, Progress, PRESETS};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::fmt;
+
+impl Game {
+    /// Every invariant the game breaks as it stands, in words (empty if it is sound): HP
+    /// and VP within each monster's limits, each zone held by one living monster at most,
+    /// nobody in both zones, Tokyo Bay only used while it is open, and no decision left
+    /// pending once the game is over.
+    pub fn invariant_violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+        for player in &self.players {
+            if player.hp > player.max_hp {
+                violations.push(format!("{} has {} HP, over their maximum of {}", player.name, player.hp, player.max_hp));
+            }
+            if player.victory_points > player.max_vp {
+                violations.push(format!("{} has {} VP, over their target of {}", player.name, player.victory_points, player.max_vp));
+            }
+        }
+        for (zone, occupant_id) in self.tokyo.occupants() {
+            match self.get_player(occupant_id) {
+                None => violations.push(format!("{} is held by player {}, who isn't in the game", zone.label(), occupant_id)),
+                Some(player) if player.hp == 0 => violations.push(format!("{} is held by {}, who has been eliminated", zone.label(), player.name)),
+                Some(_) => {}
+            }
+        }
+        if let (Some(city), Some(bay)) = (self.tokyo.city, self.tokyo.bay) {
+            if city == bay {
+                violations.push(format!("player {} holds both Tokyo City and Tokyo Bay", city));
+            }
+        }
+        if self.tokyo.bay.is_some() && !self.tokyo.bay_open {
+            violations.push("Tokyo Bay is held while it is closed".to_string());
+        }
+        if self.victory().is_some() && self.pending().is_some() {
+            violations.push("a decision is pending after the game ended".to_string());
+        }
+        violations
+    }
+
+    /// Panics listing every broken invariant, in debug builds only; release builds skip the
+    /// check. `advance` calls it after every step.
+    pub fn debug_assert_invariants(&self) {
+        if cfg!(debug_assertions) {
+            let violations = self.invariant_violations();
+            assert!(violations.is_empty(), "game invariants broken on turn {}: {}", self.turn, violations.join("; "));
+        }
+    }
+}
+
+/// How a fuzzing run went when every game held up.
+#[derive(Debug, Clone)]
+pub struct FuzzReport {
+    pub games: usize,
+    pub decisions: u64, // Decisions answered over every game
+    pub longest: u32,   // Turns in the longest game
+}
+
+impl fmt::Display for FuzzReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} random games played, {} decisions checked, longest game {} turns: every invariant held and every game ended.",
+                 self.games, self.decisions, self.longest)
+    }
+}
+
+/// The first game a fuzzing run caught misbehaving, with what it takes to play it again.
+#[derive(Debug, Clone)]
+pub struct FuzzFailure {
+    pub game: usize,             // Which game of the run, from 0
+    pub seed: u64,               // The game's own seed
+    pub config: Box<GameConfig>, // Its rules
+    pub players: usize,
+    pub turn: u32,
+    pub reason: String,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Game {} (seed {}, {} players, {} rules) failed on turn {}: {}",
+                 self.game + 1, self.seed, self.players, self.config.preset, self.turn, self.reason)
+    }
+}
+
+/// Decisions a fuzzed game may take before it counts as stuck.
+pub const FUZZ_DECISION_LIMIT: u64 = 200_000;
+
+/// Plays `games` games between random bots, each with a random table size, preset,
+/// house rules and monsters drawn from `seed`, checking the invariants after every step
+/// and that every game ends within `FUZZ_DECISION_LIMIT` decisions. Stops at the first
+/// failure, which names the seed and setup needed to reproduce it.
+pub fn fuzz(games: usize, seed: u64) -> Result<FuzzReport, FuzzFailure> {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    let mut report = FuzzReport { games, decisions: 0, longest: 0 };
+    for n in 0..games {
+        let game_seed = rng.gen();
+        let players = rng.gen_range(2..=6);
+        let mut config = (PRESETS.choose(&mut rng).expect("There are presets").build)();
+        config.rules.optional_entry = rng.gen();
+        config.rules.yield_unhurt = rng.gen();
+        config.rules.power_up = rng.gen();
+        config.rules.immediate_victory = rng.gen();
+        if config.rules.energy {
+            config.rules.energy_per_vp = rng.gen::<bool>().then_some(5);
+            config.rules.energy_per_heal = rng.gen::<bool>().then_some(2);
+        }
+        let failure = |turn: u32, reason: String| FuzzFailure { game: n, seed: game_seed, config: Box::new(config.clone()), players, turn, reason };
+
+        let names: Vec<String> = (1..=players).map(|seat| format!("Fuzzer {}", seat)).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        let mut game = Game::new(&names, game_seed, config.clone()).map_err(|error| failure(0, error.to_string()))?;
+        let mut monsters = Monster::ALL.to_vec();
+        monsters.shuffle(&mut rng);
+        for (player_id, monster) in (1..=players as u32).zip(monsters) {
+            if rng.gen() {
+                game.choose_monster(player_id, monster);
+            }
+        }
+        let mut bots: Vec<_> = (0..players).map(|seat| AgentKind::Random.build(game_seed.wrapping_add(seat as u64 + 1))).collect();
+
+        let mut decisions = 0;
+        loop {
+            let action = game.pending().map(|decision| decision.ask(bots[decision.player_id() as usize - 1].as_mut(), &game));
+            decisions += action.is_some() as u64;
+            let progress = game.advance(action).map_err(|error| failure(game.turn, format!("a random bot's answer was refused: {}", error)))?;
+            let violations = game.invariant_violations();
+            if !violations.is_empty() {
+                return Err(failure(game.turn, violations.join("; ")));
+            }
+            if let Progress::GameOver(_) = progress {
+                break;
+            }
+            if decisions > FUZZ_DECISION_LIMIT {
+                return Err(failure(game.turn, format!("the game didn't end within {} decisions", FUZZ_DECISION_LIMIT)));
+            }
+        }
+        report.decisions += decisions;
+        report.longest = report.longest.max(game.turn);
+    }
+    Ok(report)
+}
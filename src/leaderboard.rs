@@ -0,0 +1,408 @@
+//! A standing bot leaderboard: registered bots play rated games against each other, for
+//! as long as the leaderboard runs, and every game moves their Elo ratings and
+//! head-to-head records. The built-in bots are always registered. Other bots are programs
+//! registered by command line: they play the client side of the network protocol on
+//! stdin and stdout with the same messages as over TCP (`Join`, then an `Act` for each
+//! `Decide`). The board is kept as JSON in a `Storage`, so it survives restarts.
+
+use crate::{Action, Agent, AgentKind, ClientMessage, ConfigError, ConfigIssue, Decision, Game, GameConfig, HostMessage, Progress, Storage,
+            Victory, FUZZ_DECISION_LIMIT, MAX_REFUSALS, PROTOCOL_VERSION};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// How long an external bot may take over one message before it counts as a fault.
+pub const BOT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most bots seated at one rated game.
+pub const MAX_RATED_TABLE: usize = 4;
+
+/// An external bot: a name and the command that starts it, with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotRegistration {
+    pub name: String,
+    pub command: Vec<String>,
+}
+
+impl BotRegistration {
+    /// Collection that `save_to` and `load_all` use.
+    pub const BOTS: &'static str = "bots";
+
+    /// Registers `command` under `name`. Refuses blank names, the built-in bots' names and
+    /// empty commands.
+    pub fn new(name: &str, command: &[String]) -> Result<Self, ConfigError> {
+        let mut issues = Vec::new();
+        if name.trim().is_empty() {
+            issues.push(ConfigIssue::new("name", "the name is blank".to_string(), "give the bot a name".to_string()));
+        } else if AgentKind::ALL.iter().any(|kind| kind.label().eq_ignore_ascii_case(name.trim())) {
+            issues.push(ConfigIssue::new("name", format!("\"{}\" is a built-in bot", name.trim()), "pick another name".to_string()));
+        }
+        if command.is_empty() {
+            issues.push(ConfigIssue::new("command", "no command given".to_string(), "give the program to run, e.g. python3 bot.py".to_string()));
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError { source: "the bot registration".to_string(), issues });
+        }
+        Ok(BotRegistration { name: name.trim().to_string(), command: command.to_vec() })
+    }
+
+    /// Saves the registration in `storage` under its name.
+    pub fn save_to(&self, storage: &mut dyn Storage) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Registrations are always serializable");
+        storage.put(BotRegistration::BOTS, &self.name, &json)
+    }
+
+    /// Every registration kept in `storage`, by name.
+    pub fn load_all(storage: &dyn Storage) -> io::Result<Vec<Self>> {
+        let mut registrations = Vec::new();
+        for key in storage.keys(BotRegistration::BOTS)? {
+            if let Some(json) = storage.get(BotRegistration::BOTS, &key)? {
+                registrations.push(serde_json::from_str(&json)?);
+            }
+        }
+        Ok(registrations)
+    }
+}
+
+/// A bot that can be seated at rated games.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RatedBot {
+    Builtin(AgentKind),
+    External(BotRegistration),
+}
+
+impl RatedBot {
+    /// The name it plays and is rated under: the label for built-in bots.
+    pub fn name(&self) -> &str {
+        match self {
+            RatedBot::Builtin(kind) => kind.label(),
+            RatedBot::External(registration) => &registration.name,
+        }
+    }
+
+    /// The built-in bots, then every bot registered in `storage`.
+    pub fn registered(storage: &dyn Storage) -> io::Result<Vec<Self>> {
+        let mut bots: Vec<RatedBot> = AgentKind::ALL.into_iter().map(RatedBot::Builtin).collect();
+        bots.extend(BotRegistration::load_all(storage)?.into_iter().map(RatedBot::External));
+        Ok(bots)
+    }
+}
+
+/// One bot's place on the board.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BotRating {
+    pub name: String,
+    pub rating: f64,
+    pub games: u32,
+    pub wins: u32,
+    pub faults: u32, // Games it crashed, stalled or kept sending refused answers in
+}
+
+/// How one bot has done against another, over the games they played together.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub bot: String,
+    pub opponent: String,
+    pub games: u32,
+    pub ahead: u32,  // Games `bot` placed above `opponent`
+    pub behind: u32, // Games it placed below; the rest were level
+}
+
+/// Every bot's rating and head-to-head record so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub games: u32, // Rated games recorded
+    pub bots: Vec<BotRating>,
+    pub head_to_head: Vec<HeadToHead>, // Both ways round for every pair that has met
+}
+
+impl Leaderboard {
+    /// Collection that `save_to` and `load_from` use.
+    pub const LEADERBOARDS: &'static str = "leaderboards";
+
+    /// Every bot's rating before its first game.
+    pub const INITIAL_RATING: f64 = 1500.0;
+
+    /// How far one game can move a rating.
+    pub const K_FACTOR: f64 = 32.0;
+
+    /// Adds a finished game to the board. Each pair of bots at the table counts as an Elo
+    /// match won by whoever placed higher (drawn when they placed level), each worth
+    /// `K_FACTOR / (players - 1)`, so a game moves a rating as far as one head-to-head
+    /// match would. `faulted` names the bots that faulted in it. Unfinished and practice
+    /// games don't count. Returns whether it was counted.
+    pub fn record(&mut self, game: &Game, faulted: &[String]) -> bool {
+        if game.practice || game.victory().is_none() {
+            return false;
+        }
+        let placements: Vec<(String, u8)> = game.final_placements().into_iter()
+            .filter_map(|(player_id, placement)| game.get_player(player_id).map(|player| (player.name.clone(), placement)))
+            .collect();
+        for (name, _) in &placements {
+            if !self.bots.iter().any(|bot| bot.name == *name) {
+                self.bots.push(BotRating { name: name.clone(), rating: Leaderboard::INITIAL_RATING, games: 0, wins: 0, faults: 0 });
+            }
+        }
+        let rating = |board: &Leaderboard, name: &str| board.bots.iter().find(|bot| bot.name == name).map_or(Leaderboard::INITIAL_RATING, |bot| bot.rating);
+        let k = Leaderboard::K_FACTOR / (placements.len().max(2) - 1) as f64;
+        let mut changes = vec![0.0; placements.len()];
+        for (i, (name, placement)) in placements.iter().enumerate() {
+            for (opponent, their_placement) in placements.iter().filter(|(other, _)| other != name) {
+                let score = match placement.cmp(their_placement) {
+                    std::cmp::Ordering::Less => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Greater => 0.0,
+                };
+                let expected = 1.0 / (1.0 + 10f64.powf((rating(self, opponent) - rating(self, name)) / 400.0));
+                changes[i] += k * (score - expected);
+                self.meeting(name, opponent, placement.cmp(their_placement));
+            }
+        }
+        for ((name, placement), change) in placements.iter().zip(changes) {
+            let bot = self.bots.iter_mut().find(|bot| bot.name == *name).expect("Every bot at the table was added");
+            bot.rating += change;
+            bot.games += 1;
+            bot.wins += (*placement == 1) as u32;
+            bot.faults += faulted.contains(name) as u32;
+        }
+        self.games += 1;
+        true
+    }
+
+    /// Counts one meeting in `bot`'s record against `opponent`.
+    fn meeting(&mut self, bot: &str, opponent: &str, order: std::cmp::Ordering) {
+        let index = match self.head_to_head.iter().position(|record| record.bot == bot && record.opponent == opponent) {
+            Some(index) => index,
+            None => {
+                self.head_to_head.push(HeadToHead { bot: bot.to_string(), opponent: opponent.to_string(), games: 0, ahead: 0, behind: 0 });
+                self.head_to_head.len() - 1
+            }
+        };
+        let record = &mut self.head_to_head[index];
+        record.games += 1;
+        record.ahead += order.is_lt() as u32;
+        record.behind += order.is_gt() as u32;
+    }
+
+    /// The bots best first: highest rating, then most wins.
+    pub fn standings(&self) -> Vec<&BotRating> {
+        let mut standings: Vec<&BotRating> = self.bots.iter().collect();
+        standings.sort_by(|a, b| b.rating.total_cmp(&a.rating).then(b.wins.cmp(&a.wins)));
+        standings
+    }
+
+    /// `bot`'s record against each bot it has met, in the order they first met.
+    pub fn head_to_head_of(&self, bot: &str) -> Vec<&HeadToHead> {
+        self.head_to_head.iter().filter(|record| record.bot.eq_ignore_ascii_case(bot)).collect()
+    }
+
+    /// Seats a random table of 2 to `MAX_RATED_TABLE` of `bots` drawn from `seed`, plays it
+    /// under `config` and records it. External bots are started for the game and stopped
+    /// after it; one that faults is replaced by a random bot for the rest of the game.
+    /// Returns the game, which may be unfinished (and so not counted) if it ran past
+    /// `FUZZ_DECISION_LIMIT` decisions.
+    pub fn play(&mut self, bots: &[RatedBot], config: &GameConfig, seed: u64) -> Result<Game, ConfigError> {
+        if bots.len() < 2 {
+            let issue = ConfigIssue::new("bots", format!("{} bot(s) registered", bots.len()), "register at least 2 bots".to_string());
+            return Err(ConfigError { source: "the leaderboard".to_string(), issues: vec![issue] });
+        }
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let size = rng.gen_range(2..=bots.len().min(MAX_RATED_TABLE));
+        let table: Vec<&RatedBot> = bots.choose_multiple(&mut rng, size).collect();
+        let names: Vec<&str> = table.iter().map(|bot| bot.name()).collect();
+        let mut game = Game::new(&names, seed, config.clone())?;
+        let mut seats: Vec<Seat> = table.iter().enumerate()
+            .map(|(i, bot)| Seat::start(bot, i as u32 + 1, seed.wrapping_add(i as u64 + 1)))
+            .collect();
+
+        let mut decisions = 0;
+        let victory = loop {
+            let decision = game.pending();
+            let action = decision.map(|decision| seats[decision.player_id() as usize - 1].answer(&game, decision));
+            decisions += action.is_some() as u64;
+            match game.advance(action) {
+                Ok(Progress::GameOver(victory)) => break Some(victory),
+                Ok(_) if decisions > FUZZ_DECISION_LIMIT => {
+                    game.abandon();
+                    break None;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    let player_id = decision.expect("Only answers are refused").player_id();
+                    seats[player_id as usize - 1].refused(error.to_string());
+                }
+            }
+        };
+        let mut faulted = Vec::new();
+        for (seat, bot) in seats.iter_mut().zip(&table) {
+            if seat.finish(&game, victory) {
+                faulted.push(bot.name().to_string());
+            }
+        }
+        self.record(&game, &faulted);
+        Ok(game)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Leaderboards are always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the board in `storage` under `name`.
+    pub fn save_to(&self, storage: &mut dyn Storage, name: &str) -> io::Result<()> {
+        storage.put(Leaderboard::LEADERBOARDS, name, &self.to_json())
+    }
+
+    /// The board saved under `name`, if there is one.
+    pub fn load_from(storage: &dyn Storage, name: &str) -> io::Result<Option<Self>> {
+        match storage.get(Leaderboard::LEADERBOARDS, name)? {
+            Some(json) => Ok(Some(Leaderboard::from_json(&json)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Leaderboard ({} rated game{}):", self.games, if self.games == 1 { "" } else { "s" })?;
+        writeln!(f, "{:>4}  {:<20} {:>6} {:>6} {:>5} {:>7}", "", "Bot", "Rating", "Games", "Wins", "Faults")?;
+        for (place, bot) in self.standings().into_iter().enumerate() {
+            writeln!(f, "{:>4}  {:<20} {:>6.0} {:>6} {:>5} {:>7}", place + 1, bot.name, bot.rating, bot.games, bot.wins, bot.faults)?;
+        }
+        Ok(())
+    }
+}
+
+/// An external bot's process, with its output read line by line on a thread of its own so
+/// a bot that stalls can be timed out.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<io::Result<String>>,
+}
+
+impl Process {
+    fn start(command: &[String]) -> io::Result<Self> {
+        let (program, args) = command.split_first().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no command"))?;
+        let mut child = Command::new(program).args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Process { child, stdin, lines })
+    }
+
+    fn send(&mut self, message: &HostMessage) -> io::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes())?;
+        self.stdin.flush()
+    }
+
+    /// The bot's next message, waiting `BOT_TIMEOUT` at most. Blank lines are skipped.
+    fn receive(&mut self) -> io::Result<ClientMessage> {
+        loop {
+            match self.lines.recv_timeout(BOT_TIMEOUT) {
+                Ok(line) if line.as_ref().is_ok_and(|line| line.trim().is_empty()) => continue,
+                Ok(line) => return Ok(serde_json::from_str(&line?)?),
+                Err(RecvTimeoutError::Timeout) => return Err(io::Error::new(io::ErrorKind::TimedOut, "the bot took too long to answer")),
+                Err(RecvTimeoutError::Disconnected) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the bot exited")),
+            }
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Who answers for one seat of a rated game.
+enum Seat {
+    Builtin(Box<dyn Agent>),
+    External { process: Process, player_id: u32, refusals: usize, seed: u64 },
+    Substitute(Box<dyn Agent>), // A random bot playing on for an external bot that faulted
+}
+
+impl Seat {
+    /// Starts `bot` for `player_id`: an external bot has to send its `Join` and is then
+    /// welcomed to the seat.
+    fn start(bot: &RatedBot, player_id: u32, seed: u64) -> Self {
+        match bot {
+            RatedBot::Builtin(kind) => Seat::Builtin(kind.build(seed)),
+            RatedBot::External(registration) => {
+                let started = Process::start(&registration.command).and_then(|mut process| match process.receive()? {
+                    ClientMessage::Join { .. } => {
+                        process.send(&HostMessage::Welcome { player_id, protocol_version: PROTOCOL_VERSION })?;
+                        Ok(process)
+                    }
+                    ClientMessage::Act { .. } => Err(io::Error::new(io::ErrorKind::InvalidData, "the bot acted before joining")),
+                });
+                match started {
+                    Ok(process) => Seat::External { process, player_id, refusals: 0, seed },
+                    Err(_) => Seat::Substitute(AgentKind::Random.build(seed)),
+                }
+            }
+        }
+    }
+
+    /// This seat's answer to `decision`. An external bot that crashes, stalls or answers
+    /// with something other than an `Act` is replaced on the spot.
+    fn answer(&mut self, game: &Game, decision: Decision) -> Action {
+        if let Seat::External { process, player_id, seed, .. } = self {
+            let answer = process.send(&HostMessage::State { game: game.view_json(Some(*player_id)) })
+                .and_then(|_| process.send(&HostMessage::Decide { player_id: *player_id, decision }))
+                .and_then(|_| process.receive());
+            match answer {
+                Ok(ClientMessage::Act { action }) => return action,
+                Ok(ClientMessage::Join { .. }) | Err(_) => *self = Seat::Substitute(AgentKind::Random.build(*seed)),
+            }
+        }
+        match self {
+            Seat::Builtin(agent) | Seat::Substitute(agent) => decision.ask(agent.as_mut(), game),
+            Seat::External { .. } => unreachable!("External seats answered above"),
+        }
+    }
+
+    /// The game refused this seat's last answer. An external bot is told why and asked
+    /// again, until it has been refused `MAX_REFUSALS` times in one game.
+    fn refused(&mut self, reason: String) {
+        if let Seat::External { process, refusals, seed, .. } = self {
+            *refusals += 1;
+            if *refusals >= MAX_REFUSALS || process.send(&HostMessage::Rejected { reason }).is_err() {
+                *self = Seat::Substitute(AgentKind::Random.build(*seed));
+            }
+        }
+    }
+
+    /// Tells an external bot how the game ended. Returns whether the seat faulted.
+    fn finish(&mut self, game: &Game, victory: Option<Victory>) -> bool {
+        match self {
+            Seat::External { process, player_id, .. } => {
+                let _ = process.send(&HostMessage::State { game: game.view_json(Some(*player_id)) });
+                let _ = process.send(&HostMessage::GameOver { victory });
+                false
+            }
+            Seat::Substitute(_) => true,
+            Seat::Builtin(_) => false,
+        }
+    }
+}
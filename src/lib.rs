@@ -0,0 +1,3169 @@
+//! King of Tokyo rules engine.
+//!
+//! The engine never reads or prints anything itself. Frontends drive a [`Game`] one turn at
+//! a time with [`Game::play_turn`], answer the choices the rules leave to players through an
+//! [`Agent`] per seat (see [`PlayerController`]), and render what happened from the
+//! [`GameEvent`] history. [`RandomAgent`] and [`HeuristicAgent`] are built-in bots.
+//!
+//! [`prelude`] gathers the types most code needs and that are kept stable from release to
+//! release; the rest of the API is there to use but may move as the engine grows.
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+mod advisor;
+#[cfg(feature = "ai")]
+mod analysis;
+mod agents;
+mod cards;
+mod clock;
+#[cfg(feature = "net")]
+mod conformance;
+mod design;
+mod effects;
+mod evolutions;
+mod expansions;
+mod health;
+mod invariants;
+#[cfg(feature = "net")]
+mod leaderboard;
+mod monsters;
+mod narration;
+#[cfg(feature = "storage")]
+mod paths;
+mod pricing;
+#[cfg(feature = "net")]
+mod net;
+mod personas;
+#[cfg(feature = "ai")]
+mod prediction;
+pub mod prelude;
+mod probability;
+mod profile;
+mod replay;
+mod report;
+mod review;
+mod rulebook;
+mod series;
+#[cfg(feature = "net")]
+mod server;
+#[cfg(feature = "ai")]
+mod simulate;
+#[cfg(feature = "storage")]
+mod storage;
+mod tiebreak;
+mod view;
+mod web;
+
+pub use advisor::{advise, Advice};
+#[cfg(feature = "ai")]
+pub use analysis::{analyze_replays, MonsterStat, ReplayAnalysis, SeatStat, MAX_LISTED_FAILURES};
+pub use agents::{AgentKind, HeuristicAgent, RandomAgent};
+pub use cards::{Card, CardEffect, CardKind, Passive, Shop, STARTER_CARDS};
+#[cfg(not(target_arch = "wasm32"))]
+pub use clock::SystemClock;
+pub use clock::{Clock, StoppedClock};
+#[cfg(feature = "net")]
+pub use conformance::{check_client, check_host, ConformanceReport, ProtocolCheck, MAX_REFUSALS};
+pub use design::MonsterDesign;
+pub use effects::{DiceChange, GameEffect, Hit, Jinx, LoadedDie, Roll};
+pub use evolutions::{Evolution, EvolutionKind, PowerUp, EVOLUTIONS};
+pub use expansions::{Expansion, EXPANSIONS};
+pub use health::{HealthRegistry, Subsystem, SubsystemState};
+pub use invariants::{fuzz, FuzzFailure, FuzzReport, FUZZ_DECISION_LIMIT};
+#[cfg(feature = "net")]
+pub use leaderboard::{BotRating, BotRegistration, HeadToHead, Leaderboard, RatedBot, BOT_TIMEOUT, MAX_RATED_TABLE};
+pub use monsters::Monster;
+pub use narration::{message, narrate, narrate_in, Arg, Locale, Message, MessageKey};
+#[cfg(feature = "net")]
+pub use net::{ClientMessage, Connection, HostMessage, PROTOCOL_VERSION};
+#[cfg(feature = "storage")]
+pub use paths::DataDir;
+pub use personas::{BotIdentity, BotPack};
+#[cfg(feature = "ai")]
+pub use prediction::{Calibration, Outcome, Prediction, PredictionLog, SeatPrediction, Track, DEFAULT_HISTORY_WEIGHT, MAX_OUTCOMES, MIN_CALIBRATION_GAMES, PREDICTION_GAMES};
+pub use pricing::{CardCost, CostStep};
+pub use probability::{expected_hits, face_chance, roll_luck};
+pub use profile::{KeyBindings, Profile, Theme};
+pub use replay::{Playback, Replay, Violation};
+pub use report::{CardStat, NotableGame, RoundResult, Standing, TournamentReport};
+pub use review::{cite, review_turn, turn_events};
+pub use rulebook::RuleId;
+pub use series::{Series, SeriesFormat, SeriesRecord};
+#[cfg(feature = "net")]
+pub use server::{ApiResponse, ApiServer, MAX_REQUEST_BODY};
+#[cfg(feature = "ai")]
+pub use simulate::{compare, simulate, simulate_from, Comparison, SimulationReport};
+#[cfg(feature = "storage")]
+pub use storage::{FileStorage, Storage};
+pub use tiebreak::{TieBreak, TieKeys};
+pub use view::GameView;
+pub use web::JsonGame;
+
+// --- 1. Core Data Structures ---
+
+/// Represents the six possible outcomes of a single die roll.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum DieResult {
+    One,
+    Two,
+    Three,
+    Energy,     // In-game currency
+    Claw,       // Attack/Tokyo
+    Heart,      // +1 HP
+}
+
+impl DieResult {
+    /// How many of `dice` show each face, indexed by `face as usize`.
+    pub fn tally(dice: &[DieResult; 6]) -> [u8; 6] {
+        let mut counts = [0; 6];
+        for &die in dice {
+            counts[die as usize] += 1;
+        }
+        counts
+    }
+}
+
+/// Where a Victory Point came from, used for the end-of-game breakdown.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum VpSource {
+    Triples,    // Matched numbers (3 of a kind and up)
+    EnterTokyo, // +1 for taking Tokyo
+    HoldTokyo,  // +2 for starting a turn in Tokyo
+    Cards,      // Bought in the buy phase
+}
+
+impl VpSource {
+    pub const ALL: [VpSource; 4] = [VpSource::Triples, VpSource::EnterTokyo, VpSource::HoldTokyo, VpSource::Cards];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VpSource::Triples => "Triples",
+            VpSource::EnterTokyo => "Entering Tokyo",
+            VpSource::HoldTokyo => "Holding Tokyo",
+            VpSource::Cards => "Cards",
+        }
+    }
+}
+
+/// Represents a single Kaiju player's state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub id: u32,
+    pub name: String,
+    pub hp: u8,          // Starts at the config's start_hp, capped at max_hp
+    pub max_hp: u8,      // 12 unless an effect changes it
+    pub victory_points: u8, // Capped at max_vp
+    pub max_vp: u8,      // 20 unless an effect changes it; reaching it wins
+    pub energy: u8,      // Currency
+    pub vp_by_source: HashMap<VpSource, u8>, // VP actually gained, per source
+    pub cards: Vec<Card>, // Keep cards in play, in the order they were bought
+    pub monster: Option<Monster>, // Chosen at setup; None plays without an ability
+    #[serde(default)]
+    pub evolutions: Vec<Evolution>, // Power Up!: drawn and not yet played, in the order drawn
+    pub evolved: Vec<Evolution>,    // Power Up!: permanent evolutions in play
+    #[serde(default)]
+    evolution_deck: Vec<Evolution>, // Power Up!: this monster's shuffled deck; top is the end of the Vec
+    #[serde(default = "hidden_stream")]
+    rng: ChaCha12Rng, // This seat's own dice stream, derived from the game seed
+}
+
+impl Player {
+    fn new(id: u32, name: &str, game_seed: u64, start_hp: u8, max_hp: u8, max_vp: u8) -> Self {
+        Player {
+            id,
+            name: name.to_string(),
+            hp: start_hp.min(max_hp),
+            max_hp,
+            victory_points: 0,
+            max_vp,
+            energy: 0,
+            vp_by_source: HashMap::new(),
+            cards: Vec::new(),
+            monster: None,
+            evolutions: Vec::new(),
+            evolved: Vec::new(),
+            evolution_deck: Vec::new(),
+            rng: ChaCha12Rng::seed_from_u64(derive_stream_seed(game_seed, id as u64)),
+        }
+    }
+
+    /// Adds VP (clamped to this player's `max_vp`) and records how much was actually gained from `source`.
+    fn gain_vp(&mut self, amount: u8, source: VpSource) -> u8 {
+        let before = self.victory_points;
+        self.victory_points = self.victory_points.saturating_add(amount).min(self.max_vp);
+        let gained = self.victory_points - before;
+        *self.vp_by_source.entry(source).or_insert(0) += gained;
+        gained
+    }
+
+    /// Heals up to this player's `max_hp`, returning the HP actually gained.
+    fn heal(&mut self, amount: u8) -> u8 {
+        let before = self.hp;
+        self.hp = self.hp.saturating_add(amount).min(self.max_hp);
+        self.hp - before
+    }
+
+    /// Raises or lowers max HP (never below 1), pulling current HP down if it no longer fits.
+    fn adjust_max_hp(&mut self, delta: i16) {
+        self.max_hp = (self.max_hp as i16 + delta).clamp(1, u8::MAX as i16) as u8;
+        self.hp = self.hp.min(self.max_hp);
+    }
+
+    /// Raises or lowers the VP this player needs to win (never below 1). Current VP is kept,
+    /// so lowering the target below it means they win at the next victory check.
+    fn adjust_max_vp(&mut self, delta: i16) {
+        self.max_vp = (self.max_vp as i16 + delta).clamp(1, u8::MAX as i16) as u8;
+    }
+
+    /// Whether this player's monster, one of their Keep cards or a permanent evolution
+    /// grants `passive`.
+    pub fn has_passive(&self, passive: Passive) -> bool {
+        self.monster.is_some_and(|monster| monster.passive() == passive)
+            || self.cards.iter().any(|card| card.passive == Some(passive))
+            || self.evolved.iter().any(|evolution| evolution.passive == Some(passive))
+    }
+
+    /// Evolutions left to draw.
+    pub fn evolution_deck_len(&self) -> usize {
+        self.evolution_deck.len()
+    }
+
+    /// Formats the VP breakdown as e.g. "Triples 4, Entering Tokyo 1, Holding Tokyo 6".
+    pub fn vp_breakdown(&self) -> String {
+        VpSource::ALL.iter()
+            .map(|source| format!("{} {}", source.label(), self.vp_by_source.get(source).copied().unwrap_or(0)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// How a point of damage was dealt, for the damage ledger.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum DamageSource {
+    ClawsFromTokyo, // A Tokyo occupant attacking everyone outside
+    ClawsIntoTokyo, // A monster outside attacking the Tokyo occupants
+    Cards,          // A power card bought in the buy phase
+}
+
+impl DamageSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DamageSource::ClawsFromTokyo => "claws from Tokyo",
+            DamageSource::ClawsIntoTokyo => "claws into Tokyo",
+            DamageSource::Cards => "cards",
+        }
+    }
+}
+
+/// The two places a monster can occupy in Tokyo.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum TokyoZone {
+    City, // Always in play
+    Bay,  // Only in play with 5 or more monsters
+}
+
+impl TokyoZone {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TokyoZone::City => "Tokyo City",
+            TokyoZone::Bay => "Tokyo Bay",
+        }
+    }
+}
+
+/// Who is in Tokyo. City always fills before Bay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tokyo {
+    city: Option<u32>, // ID of the player in Tokyo City (or None)
+    bay: Option<u32>,  // ID of the player in Tokyo Bay (or None)
+    bay_open: bool,    // Tokyo Bay is only used while 5+ monsters are in the game
+}
+
+impl Tokyo {
+    fn new(num_players: usize) -> Self {
+        Tokyo { city: None, bay: None, bay_open: num_players >= 5 }
+    }
+
+    /// The zone `player_id` occupies, if any.
+    pub fn zone_of(&self, player_id: u32) -> Option<TokyoZone> {
+        if self.city == Some(player_id) {
+            Some(TokyoZone::City)
+        } else if self.bay == Some(player_id) {
+            Some(TokyoZone::Bay)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, player_id: u32) -> bool {
+        self.zone_of(player_id).is_some()
+    }
+
+    /// Current occupants with their zones, City first.
+    pub fn occupants(&self) -> Vec<(TokyoZone, u32)> {
+        let mut occupants = Vec::new();
+        if let Some(id) = self.city {
+            occupants.push((TokyoZone::City, id));
+        }
+        if let Some(id) = self.bay {
+            occupants.push((TokyoZone::Bay, id));
+        }
+        occupants
+    }
+
+    /// The zone the next monster entering Tokyo must take: City first, then Bay if it's in play.
+    pub fn first_open_zone(&self) -> Option<TokyoZone> {
+        if self.city.is_none() {
+            Some(TokyoZone::City)
+        } else if self.bay_open && self.bay.is_none() {
+            Some(TokyoZone::Bay)
+        } else {
+            None
+        }
+    }
+
+    fn occupy(&mut self, zone: TokyoZone, player_id: u32) {
+        match zone {
+            TokyoZone::City => self.city = Some(player_id),
+            TokyoZone::Bay => self.bay = Some(player_id),
+        }
+    }
+
+    /// Takes Tokyo Bay out of play, returning whoever was sent out of it.
+    fn close_bay(&mut self) -> Option<u32> {
+        self.bay_open = false;
+        self.bay.take()
+    }
+
+    /// Removes `player_id` from Tokyo, returning the zone they left.
+    fn vacate(&mut self, player_id: u32) -> Option<TokyoZone> {
+        let zone = self.zone_of(player_id)?;
+        match zone {
+            TokyoZone::City => self.city = None,
+            TokyoZone::Bay => self.bay = None,
+        }
+        Some(zone)
+    }
+}
+
+/// One entry in the damage ledger: who hurt whom, how, and whether it was the knockout blow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageRecord {
+    pub attacker_id: u32,
+    pub target_id: u32,
+    pub amount: u8,      // HP actually lost (never more than the target had left)
+    pub source: DamageSource,
+    pub knockout: bool,  // This hit took the target to 0 HP
+}
+
+/// One player's totals for the game so far, as summed up by `Game::player_stats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub player_id: u32,
+    pub damage_dealt: u32,  // HP actually taken off other monsters
+    pub damage_taken: u32,
+    pub knockouts: u32,     // Monsters this player finished off
+    pub energy_earned: u32, // From dice and cards
+    pub energy_spent: u32,  // On cards, sweeps and pseudo-cards
+    pub turns: u32,         // Turns started, extra turns included
+    pub turns_in_tokyo: u32, // Turns started in Tokyo
+    pub vp_by_source: Vec<(VpSource, u8)>, // In `VpSource::ALL` order
+    #[serde(default)]
+    pub dice_luck: f64, // Rerolled dice that hit a kept face, less the expected number (see `roll_luck`)
+}
+
+/// Tunable Tokyo rules. Start from one of the rule sets below and adjust from there.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RuleSet {
+    pub tokyo_entry_vp: u8, // VP for entering Tokyo
+    pub tokyo_hold_vp: u8,  // VP for starting a turn in Tokyo
+    pub heal_in_tokyo: bool, // Whether Hearts heal while in Tokyo
+    pub energy_per_vp: Option<u8>, // House rule: Energy Exchange pseudo-card cost (None = off)
+    #[serde(default)]
+    pub energy_per_heal: Option<u8>, // House rule: Energy Heal pseudo-card cost, outside Tokyo (None = off)
+    pub turn_limit: u32,    // Turns before sudden-death overtime starts
+    pub rerolls: u8,        // Rerolls allowed after the first roll
+    pub energy: bool,       // Energy dice pay out and can be spent in the buy phase (off: no Energy, no cards)
+    #[serde(default = "RuleSet::legacy_optional_entry")]
+    pub optional_entry: bool, // House rule: an attacker may decline a vacant Tokyo (official: they must enter)
+    #[serde(default)]
+    pub economy: Economy,
+    #[serde(default)]
+    pub undo_limit: u8,     // House rule: decisions that can be taken back with `Game::undo` (0 = off)
+    #[serde(default)]
+    pub power_up: bool,     // Power Up! expansion: three Hearts draw an evolution card
+    #[serde(default = "RuleSet::legacy_yield_unhurt")]
+    pub yield_unhurt: bool, // House rule: occupants may yield to an attack that cost them no HP (official: only after losing HP)
+    #[serde(default = "RuleSet::legacy_tie_break")]
+    pub tie_break: Vec<TieBreak>, // What separates monsters level on the result, in order (see `TieBreak`)
+    #[serde(default)]
+    pub immediate_victory: bool, // The game ends the moment anyone wins, mid-turn if need be (off: only checked between turns)
+}
+
+/// What Energy is worth: how much the dice pay and what the shop charges. For variant
+/// designers; every preset uses the printed values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Economy {
+    pub energy_per_die: u8,     // Energy paid for each Energy die
+    pub card_cost_percent: u16, // Power card prices as a percentage of the printed cost
+    pub sweep_cost: u8,         // Energy to discard the shop and reveal new cards
+}
+
+impl Default for Economy {
+    fn default() -> Self {
+        Economy { energy_per_die: 1, card_cost_percent: 100, sweep_cost: Shop::SWEEP_COST }
+    }
+}
+
+impl Economy {
+    /// What `card` sells for: its printed cost scaled by `card_cost_percent`, rounded to
+    /// the nearest Energy and never below 1.
+    pub fn card_cost(&self, card: &Card) -> u8 {
+        let scaled = (card.cost as u32 * self.card_cost_percent as u32 + 50) / 100;
+        scaled.clamp(1, u8::MAX as u32) as u8
+    }
+}
+
+impl RuleSet {
+    /// The rules as printed in the box.
+    pub fn official() -> Self {
+        RuleSet { tokyo_entry_vp: 1, tokyo_hold_vp: 2, heal_in_tokyo: false, energy_per_vp: None, energy_per_heal: None, turn_limit: 1000, rerolls: 2, energy: true, optional_entry: false, economy: Economy::default(), undo_limit: 0, power_up: false, yield_unhurt: false, tie_break: TieBreak::DEFAULT.to_vec(), immediate_victory: true }
+    }
+
+    /// Slower, more forgiving games: holding Tokyo pays less but Hearts always heal.
+    pub fn gentle() -> Self {
+        RuleSet { tokyo_entry_vp: 1, tokyo_hold_vp: 1, heal_in_tokyo: true, energy_per_vp: None, energy_per_heal: None, turn_limit: 1000, rerolls: 2, energy: true, optional_entry: false, economy: Economy::default(), undo_limit: 0, power_up: false, yield_unhurt: false, tie_break: TieBreak::DEFAULT.to_vec(), immediate_victory: true }
+    }
+
+    /// For young players: Hearts heal everywhere and there is no Energy or shopping to track.
+    pub fn kids() -> Self {
+        RuleSet { energy: false, ..RuleSet::gentle() }
+    }
+
+    /// Tokyo is worth fighting over: bigger rewards for taking and holding it.
+    pub fn brutal() -> Self {
+        RuleSet { tokyo_entry_vp: 2, tokyo_hold_vp: 3, heal_in_tokyo: false, energy_per_vp: None, energy_per_heal: None, turn_limit: 1000, rerolls: 2, energy: true, optional_entry: false, economy: Economy::default(), undo_limit: 0, power_up: false, yield_unhurt: false, tie_break: TieBreak::DEFAULT.to_vec(), immediate_victory: true }
+    }
+
+    /// Replays recorded before `optional_entry` existed always let attackers decline.
+    fn legacy_optional_entry() -> bool {
+        true
+    }
+
+    /// Replays recorded before `yield_unhurt` existed asked every occupant, hurt or not.
+    fn legacy_yield_unhurt() -> bool {
+        true
+    }
+
+    /// Games recorded before `tie_break` existed ranked by VP, then HP, and tied monsters
+    /// shared a placement.
+    fn legacy_tie_break() -> Vec<TieBreak> {
+        vec![TieBreak::Vp, TieBreak::Hp]
+    }
+
+    /// Every problem with these rules against a `max_vp` target (empty if they're playable).
+    pub fn issues(&self, max_vp: u8) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        if self.tokyo_entry_vp >= max_vp {
+            issues.push(ConfigIssue::new("rules.tokyo_entry_vp",
+                format!("entering Tokyo gives {} VP, which would win a {}-VP game outright", self.tokyo_entry_vp, max_vp),
+                format!("use a value below max_vp ({})", max_vp)));
+        }
+        if self.tokyo_hold_vp >= max_vp {
+            issues.push(ConfigIssue::new("rules.tokyo_hold_vp",
+                format!("holding Tokyo gives {} VP, which would win a {}-VP game in one upkeep", self.tokyo_hold_vp, max_vp),
+                format!("use a value below max_vp ({})", max_vp)));
+        }
+        if self.energy_per_vp == Some(0) {
+            issues.push(ConfigIssue::new("rules.energy_per_vp",
+                "Energy Exchange costs 0 Energy, so VP would be free".to_string(),
+                "use a cost of at least 1, or turn the house rule off".to_string()));
+        }
+        if self.energy_per_heal == Some(0) {
+            issues.push(ConfigIssue::new("rules.energy_per_heal",
+                "Energy Heal costs 0 Energy, so healing would be free".to_string(),
+                "use a cost of at least 1, or turn the house rule off".to_string()));
+        }
+        if self.economy.card_cost_percent == 0 {
+            issues.push(ConfigIssue::new("rules.economy.card_cost_percent",
+                "cards would cost 0% of their price".to_string(),
+                "use a percentage of at least 1 (official: 100)".to_string()));
+        }
+        if self.economy.sweep_cost == 0 {
+            issues.push(ConfigIssue::new("rules.economy.sweep_cost",
+                "sweeping the shop would be free, so a player could sweep forever".to_string(),
+                "use a cost of at least 1 (official: 2)".to_string()));
+        }
+        if self.tokyo_entry_vp == 0 && self.tokyo_hold_vp == 0 && self.heal_in_tokyo {
+            issues.push(ConfigIssue::new("rules.heal_in_tokyo",
+                "Tokyo gives no VP and doesn't stop healing, so nobody can be forced to fight".to_string(),
+                "give Tokyo an entry or hold reward, or turn off healing in Tokyo".to_string()));
+        }
+        for (i, step) in self.tie_break.iter().enumerate() {
+            if self.tie_break[..i].contains(step) {
+                issues.push(ConfigIssue::new("rules.tie_break", format!("{} is listed more than once", step.label()),
+                    "list each tie-break once, e.g. [\"vp\", \"hp\", \"energy\", \"seat\"]".to_string()));
+            }
+        }
+        issues
+    }
+}
+
+/// One problem found while checking a config, pointing at the offending field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub field: String,   // Dotted path of the field, e.g. "rules.tokyo_hold_vp"
+    pub message: String, // What is wrong
+    pub help: String,    // How to fix it
+    pub position: Option<(usize, usize)>, // (line, column) from 1 in the file it was read from, if it came from one
+}
+
+impl ConfigIssue {
+    pub fn new(field: &str, message: String, help: String) -> Self {
+        ConfigIssue { field: field.to_string(), message, help, position: None }
+    }
+}
+
+/// Why a game could not be set up: every issue found, not just the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub source: String, // What was being checked, e.g. `preset "speed"`
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error: {} can't be used ({} problem(s))", self.source, self.issues.len())?;
+        for issue in &self.issues {
+            writeln!(f, "  × {}: {}", issue.field, issue.message)?;
+            if let Some((line, column)) = issue.position {
+                writeln!(f, "    --> line {}, column {}", line, column)?;
+            }
+            writeln!(f, "    help: {}", issue.help)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl ConfigError {
+    /// Points each issue at where its field is set in `json`, the text the config was read
+    /// from. Issues about fields the text doesn't set (left to the preset) keep no position.
+    pub fn locate(mut self, json: &str) -> Self {
+        let keys = key_positions(json);
+        for issue in self.issues.iter_mut().filter(|issue| issue.position.is_none()) {
+            issue.position = keys.iter()
+                .filter(|(path, _)| issue.field == *path || issue.field.starts_with(&format!("{}.", path)))
+                .max_by_key(|(path, _)| path.len())
+                .map(|&(_, position)| position);
+        }
+        self
+    }
+}
+
+/// Where each object key in `json` is written, by dotted path, as (line, column) from 1.
+/// Keys inside arrays are left out, as config paths never go through one.
+fn key_positions(json: &str) -> Vec<(String, (usize, usize))> {
+    let position = |offset: usize| {
+        let before = &json[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+    };
+    let mut positions = Vec::new();
+    let mut open: Vec<Option<String>> = Vec::new(); // Per open object the last key read; None for arrays
+    let mut key_next = false;
+    let bytes = json.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => {
+                open.push(Some(String::new()));
+                key_next = true;
+            }
+            b'[' => {
+                open.push(None);
+                key_next = false;
+            }
+            b'}' | b']' => {
+                open.pop();
+                key_next = false;
+            }
+            b',' => key_next = matches!(open.last(), Some(Some(_))),
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if key_next {
+                    let key: String = serde_json::from_str(json.get(start..=i).unwrap_or_default()).unwrap_or_default();
+                    if let Some(Some(last)) = open.last_mut() {
+                        *last = key;
+                    }
+                    if open.iter().all(Option::is_some) {
+                        let path: Vec<&str> = open.iter().flatten().map(|key| key.as_str()).collect();
+                        positions.push((path.join("."), position(start)));
+                    }
+                    key_next = false;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    positions
+}
+
+/// Everything needed to set up a game: the starting maximums and the rules, plus the
+/// name of the preset it came from so logs can say which variant was played.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct GameConfig {
+    pub preset: String, // Name of the preset this config started from
+    #[serde(default = "GameConfig::default_start_hp")]
+    pub start_hp: u8,   // HP every monster starts with
+    pub max_hp: u8,     // Starting max HP for every monster
+    pub max_vp: u8,     // Starting VP target for every monster
+    pub rules: RuleSet,
+    #[serde(default)]
+    pub privacy: Privacy, // Not a rule: changing it doesn't make the game `+custom`
+}
+
+/// What a game lets into its logs: the event history, the timeline, replays and
+/// narration. For clubs sharing a machine, where the next group shouldn't find out who
+/// played or when. Saves keep real names so a game can still be picked back up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct Privacy {
+    pub anonymize_names: bool, // Logs call players "Player 1", "Player 2"... by seat
+    pub wall_clock: bool,      // Events are stamped with the real-world time (off: the UNIX epoch)
+}
+
+impl Default for Privacy {
+    fn default() -> Self {
+        Privacy { anonymize_names: false, wall_clock: true }
+    }
+}
+
+/// A named rules variant that can be picked with `--preset` or at setup.
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub build: fn() -> GameConfig,
+}
+
+/// Every selectable preset, in the order they are listed to players.
+pub const PRESETS: [Preset; 7] = [
+    Preset { name: "official", description: "The 2015 edition rules: 12 HP, 20 VP, Tokyo +1/+2", build: GameConfig::official },
+    Preset { name: "house-casual", description: "Gentle Tokyo plus the 5 Energy -> 1 VP exchange", build: GameConfig::house_casual },
+    Preset { name: "speed", description: "Race to 15 VP with a 200-turn overtime clock", build: GameConfig::speed },
+    Preset { name: "hardcore", description: "10 max HP and brutal Tokyo rewards", build: GameConfig::hardcore },
+    Preset { name: "gentle", description: "Tokyo pays +1/+1 and Hearts heal everywhere", build: GameConfig::gentle },
+    Preset { name: "brutal", description: "Tokyo pays +2/+3", build: GameConfig::brutal },
+    Preset { name: "kids", description: "Race to 10 VP with no Energy or cards; Hearts heal everywhere", build: GameConfig::kids },
+];
+
+impl GameConfig {
+    pub fn official() -> Self {
+        GameConfig { preset: "official".to_string(), start_hp: 10, max_hp: 12, max_vp: 20, rules: RuleSet::official(), privacy: Privacy::default() }
+    }
+
+    /// Configs saved before `start_hp` existed always started monsters on 10 HP.
+    fn default_start_hp() -> u8 {
+        10
+    }
+
+    pub fn house_casual() -> Self {
+        let rules = RuleSet { energy_per_vp: Some(5), ..RuleSet::gentle() };
+        GameConfig { preset: "house-casual".to_string(), rules, ..GameConfig::official() }
+    }
+
+    pub fn speed() -> Self {
+        let rules = RuleSet { turn_limit: 200, ..RuleSet::official() };
+        GameConfig { preset: "speed".to_string(), max_vp: 15, rules, ..GameConfig::official() }
+    }
+
+    pub fn hardcore() -> Self {
+        GameConfig { preset: "hardcore".to_string(), max_hp: 10, rules: RuleSet::brutal(), ..GameConfig::official() }
+    }
+
+    pub fn gentle() -> Self {
+        GameConfig { preset: "gentle".to_string(), rules: RuleSet::gentle(), ..GameConfig::official() }
+    }
+
+    pub fn brutal() -> Self {
+        GameConfig { preset: "brutal".to_string(), rules: RuleSet::brutal(), ..GameConfig::official() }
+    }
+
+    pub fn kids() -> Self {
+        GameConfig { preset: "kids".to_string(), max_vp: 10, rules: RuleSet::kids(), ..GameConfig::official() }
+    }
+
+    /// Builds the named preset (case-insensitive).
+    pub fn from_preset(name: &str) -> Option<Self> {
+        PRESETS.iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name))
+            .map(|preset| (preset.build)())
+    }
+
+    /// This config with `overrides` applied: a JSON object holding just the fields to change,
+    /// nested like the config itself, e.g. `{"max_vp": 15, "rules": {"rerolls": 3}}`. Every
+    /// unknown field or value of the wrong type is reported. A config that ends up different
+    /// from its preset is renamed "<preset>+custom", so logs don't pass it off as the preset.
+    pub fn with_overrides(&self, overrides: &serde_json::Value) -> Result<Self, ConfigError> {
+        let base = serde_json::to_value(self).expect("Configs are always serializable");
+        let mut merged = base.clone();
+        let mut issues = Vec::new();
+        let mut leaves = Vec::new();
+        collect_leaves(overrides, String::new(), &mut leaves);
+        for (path, value) in leaves {
+            let Some(slot) = path.split('.').try_fold(&mut merged, |node, key| node.get_mut(key)) else {
+                issues.push(ConfigIssue::new(&path, "there is no such option".to_string(),
+                                             format!("use one of: {}", Engine::capabilities().rule_options.join(", "))));
+                continue;
+            };
+            let previous = std::mem::replace(slot, value.clone());
+            if let Err(error) = serde_json::from_value::<GameConfig>(merged.clone()) {
+                issues.push(ConfigIssue::new(&path, format!("{} can't be used: {}", value, error),
+                                             format!("use a value like {}", previous)));
+                let slot = path.split('.').try_fold(&mut merged, |node, key| node.get_mut(key)).expect("The field was just found");
+                *slot = previous;
+            }
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError { source: "the config overrides".to_string(), issues });
+        }
+
+        let mut config: GameConfig = serde_json::from_value(merged).expect("Every override was checked");
+        let rules_changed = GameConfig { privacy: self.privacy, ..config.clone() } != *self;
+        if rules_changed && !config.preset.ends_with("+custom") {
+            config.preset = format!("{}+custom", config.preset);
+        }
+        Ok(config)
+    }
+
+    /// This config with one option changed, named by its dotted path as in
+    /// `Engine::capabilities().rule_options`, e.g. `("rules.economy.sweep_cost", 3)`.
+    pub fn with_option(&self, option: &str, value: serde_json::Value) -> Result<Self, ConfigError> {
+        let overrides = option.rsplit('.').fold(value, |value, key| serde_json::json!({ key: value }));
+        self.with_overrides(&overrides)
+    }
+
+    /// Reads a config file: a JSON object naming a `preset` to start from (official if left
+    /// out) and any fields to change, as for `with_overrides`. The result is validated, and
+    /// each issue points at the line and column of the file that caused it.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let file_error = |message: String, help: &str| ConfigError {
+            source: "the config file".to_string(),
+            issues: vec![ConfigIssue::new("(file)", message, help.to_string())],
+        };
+        let mut overrides: serde_json::Value = serde_json::from_str(json).map_err(|error| {
+            let mut error_at = file_error(format!("it isn't valid JSON: {}", error), "check the file's brackets, commas and quotes");
+            error_at.issues[0].position = Some((error.line(), error.column()));
+            error_at
+        })?;
+        let Some(fields) = overrides.as_object_mut() else {
+            return Err(file_error("it isn't a JSON object".to_string(), "write the options as {\"max_vp\": 15, ...}"));
+        };
+        let base = match fields.remove("preset") {
+            None => GameConfig::official(),
+            Some(name) => match name.as_str().and_then(GameConfig::from_preset) {
+                Some(config) => config,
+                None => {
+                    let names: Vec<&str> = PRESETS.iter().map(|preset| preset.name).collect();
+                    let issue = ConfigIssue::new("preset", format!("{} isn't a preset", name), format!("use one of: {}", names.join(", ")));
+                    return Err(ConfigError { source: "the config file".to_string(), issues: vec![issue] }.locate(json));
+                }
+            },
+        };
+        let config = base.with_overrides(&overrides).map_err(|error| error.locate(json))?;
+        config.validate().map_err(|error| error.locate(json))?;
+        Ok(config)
+    }
+
+    /// Rejects configs that can't produce a fair game, listing every problem at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+        if self.max_hp == 0 {
+            issues.push(ConfigIssue::new("max_hp", "max HP is 0, so every monster starts eliminated".to_string(),
+                                         "use a value of at least 1 (official: 12)".to_string()));
+        }
+        if self.start_hp == 0 {
+            issues.push(ConfigIssue::new("start_hp", "starting HP is 0, so every monster starts eliminated".to_string(),
+                                         "use a value of at least 1 (official: 10)".to_string()));
+        } else if self.start_hp > self.max_hp && self.max_hp > 0 {
+            issues.push(ConfigIssue::new("start_hp", format!("monsters would start on {} HP, above the {} HP maximum", self.start_hp, self.max_hp),
+                                         format!("use a value up to max_hp ({})", self.max_hp)));
+        }
+        if self.max_vp == 0 {
+            issues.push(ConfigIssue::new("max_vp", "the VP target is 0, so the game is won before it starts".to_string(),
+                                         "use a value of at least 1 (official: 20)".to_string()));
+        } else {
+            issues.extend(self.rules.issues(self.max_vp));
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { source: format!("preset \"{}\"", self.preset), issues })
+        }
+    }
+}
+
+/// An Energy action: a built-in purchase on offer in the buy phase besides the power card
+/// shop. It is never used up, so it can be bought several times a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PseudoCard {
+    pub name: &'static str,
+    pub cost: u8,            // Energy per use
+    pub effect: CardEffect,  // What each use does
+    pub outside_tokyo: bool, // Only on offer to monsters outside Tokyo
+}
+
+impl PseudoCard {
+    /// The Energy Exchange house rule: spend `cost` Energy for 1 VP.
+    pub fn energy_exchange(cost: u8) -> Self {
+        PseudoCard { name: "Energy Exchange", cost, effect: CardEffect::GainVp(1), outside_tokyo: false }
+    }
+
+    /// The Energy Heal house rule: spend `cost` Energy to heal 1 HP outside Tokyo.
+    pub fn energy_heal(cost: u8) -> Self {
+        PseudoCard { name: "Energy Heal", cost, effect: CardEffect::Heal(1), outside_tokyo: true }
+    }
+}
+
+/// The phases of a single turn, in the order they happen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum TurnPhase {
+    StartTurn, // Tokyo upkeep VP
+    Roll,      // Rolling the dice
+    Resolve,   // Scoring, healing, attacking and Tokyo decisions
+    Buy,       // Spending Energy
+}
+
+impl TurnPhase {
+    pub const ALL: [TurnPhase; 4] = [TurnPhase::StartTurn, TurnPhase::Roll, TurnPhase::Resolve, TurnPhase::Buy];
+}
+
+/// Callbacks fired at the start and end of every turn phase. Frontends register these
+/// on the `Game` to drive chess clocks, animations or stream overlays.
+pub trait PhaseHook {
+    fn on_phase_begin(&mut self, _player_id: u32, _phase: TurnPhase) {}
+    fn on_phase_end(&mut self, _player_id: u32, _phase: TurnPhase, _elapsed: Duration) {}
+}
+
+/// Receives every `GameEvent` the moment it is logged, for frontends that would rather be
+/// pushed events (live UIs, streaming to spectators, analysis) than read `Game::history`.
+pub trait EventSubscriber {
+    /// Name the subscriber goes by in `Game::health`, e.g. "webhook".
+    fn name(&self) -> &str {
+        "event subscriber"
+    }
+
+    /// Takes in one event. An error (say, the endpoint being down) unsubscribes it and
+    /// marks it disabled in `Game::health`; the game itself carries on.
+    fn on_event(&mut self, event: &TimedEvent) -> Result<(), String>;
+}
+
+/// Typed callbacks for the moments analytics usually care about, for embedders who would
+/// rather not match on `GameEvent`. Every method does nothing unless overridden. Register
+/// one with `Game::add_observer`.
+pub trait Observer {
+    /// `player_id` starts turn number `turn` with this much HP and VP.
+    fn on_turn_start(&mut self, _turn: u32, _player_id: u32, _hp: u8, _victory_points: u8) {}
+    /// `target_id` lost up to `damage` HP to `attacker_id` and has `hp` left.
+    fn on_damage(&mut self, _attacker_id: u32, _target_id: u32, _damage: u8, _hp: u8, _source: DamageSource) {}
+    /// `player_id` bought the power card `card` for `cost` Energy.
+    fn on_purchase(&mut self, _player_id: u32, _card: &str, _cost: u8) {}
+    fn on_game_end(&mut self, _victory: Victory) {}
+}
+
+/// Feeds an `Observer` from the event stream, so it sees exactly what subscribers do.
+struct ObserverSubscriber(Box<dyn Observer>);
+
+impl EventSubscriber for ObserverSubscriber {
+    fn name(&self) -> &str {
+        "observer"
+    }
+
+    fn on_event(&mut self, event: &TimedEvent) -> Result<(), String> {
+        match event.event {
+            GameEvent::TurnStarted { turn, player_id, hp, victory_points } => self.0.on_turn_start(turn, player_id, hp, victory_points),
+            GameEvent::DamageDealt { attacker_id, target_id, damage, hp, source, .. } => {
+                self.0.on_damage(attacker_id, target_id, damage, hp, source)
+            }
+            GameEvent::CardPurchased { player_id, ref card, cost, .. } => self.0.on_purchase(player_id, card, cost),
+            GameEvent::GameOver { victory } => self.0.on_game_end(victory),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Answers the choices the rules leave to players. The engine asks whenever a decision is
+/// due and waits for the answer, so an implementation can prompt a person, run a bot or
+/// forward the question over a network. Each call gets a read-only view of the game.
+pub trait Agent {
+    /// Which dice `player_id` keeps before reroll number `reroll` (all `true` stops rolling).
+    fn choose_dice_to_keep(&mut self, game: &Game, player_id: u32, dice: &[DieResult; 6], reroll: u8) -> [bool; 6];
+    /// Whether `player_id` leaves `zone` after attacking from it.
+    fn decide_concede_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone) -> bool;
+    /// Whether `player_id` yields `zone` after being hit by `attacker_id`.
+    fn decide_yield_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone, attacker_id: u32) -> bool;
+    /// Whether `player_id` takes the vacant `zone`. Only asked under the `optional_entry` house rule.
+    fn decide_enter_tokyo(&mut self, game: &Game, player_id: u32, zone: TokyoZone) -> bool;
+    /// In a practice game, the faces `player_id` sets for roll number `roll`. Kept dice stay
+    /// as they are whatever is returned; None rolls the rest as usual.
+    fn choose_forced_dice(&mut self, _game: &Game, _player_id: u32, _dice: &[DieResult; 6], _keep: &[bool; 6], _roll: u8) -> Option<[DieResult; 6]> {
+        None
+    }
+    /// What `player_id` does next in the power card shop. Asked until they finish.
+    fn choose_shop_action(&mut self, game: &Game, player_id: u32) -> ShopAction;
+    /// How many times (0 to `affordable`) `player_id` buys `card`.
+    fn choose_purchases(&mut self, game: &Game, player_id: u32, card: &PseudoCard, affordable: u8) -> u8;
+    /// Whether the just-eliminated `player_id` stays on as a spectator.
+    fn decide_spectate(&mut self, _game: &Game, _player_id: u32) -> bool {
+        false
+    }
+}
+
+/// Routes each decision to the agent playing that seat, so people and bots can share a game.
+pub trait PlayerController {
+    /// The agent that answers for `player_id`.
+    fn agent_for(&mut self, player_id: u32) -> &mut dyn Agent;
+}
+
+/// A single agent can play every seat (hot-seat play, or a bot playing itself).
+impl<A: Agent> PlayerController for A {
+    fn agent_for(&mut self, _player_id: u32) -> &mut dyn Agent {
+        self
+    }
+}
+
+/// A move in the power card shop during the buy phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ShopAction {
+    Buy(usize), // Buy the face-up card in this slot (0-based)
+    Sweep,      // Pay `Game::sweep_cost` to replace every face-up card
+    Evolve(usize), // Power Up!: play the evolution in this hand position (0-based), for free
+    Done,       // Stop shopping (so does any move that can't be paid for)
+}
+
+/// A choice the game is waiting on, one per `Agent` method. See `Game::advance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Decision {
+    SetDice { player_id: u32, dice: [DieResult; 6], keep: [bool; 6], roll: u8 }, // Practice games only
+    KeepDice { player_id: u32, dice: [DieResult; 6], reroll: u8 },
+    ConcedeTokyo { player_id: u32, zone: TokyoZone },
+    YieldTokyo { player_id: u32, zone: TokyoZone, attacker_id: u32 },
+    EnterTokyo { player_id: u32, zone: TokyoZone },
+    Shop { player_id: u32 },
+    Purchase { player_id: u32, card: usize, affordable: u8 }, // card: index into `Game::pseudo_cards`
+    Spectate { player_id: u32 },
+}
+
+impl Decision {
+    /// The player who has to decide.
+    pub fn player_id(&self) -> u32 {
+        match *self {
+            Decision::SetDice { player_id, .. }
+            | Decision::KeepDice { player_id, .. }
+            | Decision::ConcedeTokyo { player_id, .. }
+            | Decision::YieldTokyo { player_id, .. }
+            | Decision::EnterTokyo { player_id, .. }
+            | Decision::Shop { player_id }
+            | Decision::Purchase { player_id, .. }
+            | Decision::Spectate { player_id } => player_id,
+        }
+    }
+
+    /// Puts this decision to `agent` and returns its answer as an `Action`.
+    pub fn ask(&self, agent: &mut dyn Agent, game: &Game) -> Action {
+        match *self {
+            Decision::SetDice { player_id, dice, keep, roll } => {
+                Action::SetDice(agent.choose_forced_dice(game, player_id, &dice, &keep, roll))
+            }
+            Decision::KeepDice { player_id, dice, reroll } => Action::Keep(agent.choose_dice_to_keep(game, player_id, &dice, reroll)),
+            Decision::ConcedeTokyo { player_id, zone } => Action::Concede(agent.decide_concede_tokyo(game, player_id, zone)),
+            Decision::YieldTokyo { player_id, zone, attacker_id } => {
+                Action::Yield(agent.decide_yield_tokyo(game, player_id, zone, attacker_id))
+            }
+            Decision::EnterTokyo { player_id, zone } => Action::Enter(agent.decide_enter_tokyo(game, player_id, zone)),
+            Decision::Shop { player_id } => Action::Shop(agent.choose_shop_action(game, player_id)),
+            Decision::Purchase { player_id, card, affordable } => {
+                let card = game.pseudo_cards()[card];
+                Action::Purchase(agent.choose_purchases(game, player_id, &card, affordable))
+            }
+            Decision::Spectate { player_id } => Action::Spectate(agent.decide_spectate(game, player_id)),
+        }
+    }
+}
+
+/// An answer to a `Decision`: `SetDice` answers `SetDice`, `Keep` answers `KeepDice`,
+/// and so on down the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Action {
+    SetDice(Option<[DieResult; 6]>), // None rolls as usual
+    Keep([bool; 6]), // All `true` stops rolling
+    Concede(bool),
+    Yield(bool),
+    Enter(bool),
+    Shop(ShopAction),
+    Purchase(u8), // Capped at what the player can afford
+    Spectate(bool),
+}
+
+/// Where the game stands after `Game::advance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Progress {
+    Decide(Decision), // Waiting on this decision
+    TurnEnded,        // Between turns; advance with no action to start the next one
+    GameOver(Victory),
+}
+
+/// Why `Game::advance` refused to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AdvanceError {
+    Missing(Decision),                               // A decision is pending, but no action was given
+    Mismatch { expected: Decision, action: Action }, // The action doesn't answer the pending decision
+    Unexpected(Action),                              // An action was given with no decision pending
+}
+
+impl std::fmt::Display for AdvanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdvanceError::Missing(decision) => write!(f, "waiting on {:?}, but no action was given", decision),
+            AdvanceError::Mismatch { expected, action } => write!(f, "{:?} doesn't answer {:?}", action, expected),
+            AdvanceError::Unexpected(action) => write!(f, "{:?} was given, but no decision is pending", action),
+        }
+    }
+}
+
+impl std::error::Error for AdvanceError {}
+
+/// Anything that can go wrong driving the engine from outside: a setup that can't be
+/// played, an action that doesn't fit, a player ID that doesn't exist, or input that
+/// can't be read. Frontends can use `?` on every fallible engine call with this.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GameError {
+    Config(ConfigError),
+    Advance(AdvanceError),
+    UnknownPlayer(u32),
+    InvalidInput { input: String, expected: String }, // expected: what would have been accepted
+    NothingToUndo,
+    NothingToRedo,
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::Config(error) => write!(f, "{}", error),
+            GameError::Advance(error) => write!(f, "{}", error),
+            GameError::UnknownPlayer(player_id) => write!(f, "there is no player {} in this game", player_id),
+            GameError::InvalidInput { input, expected } => write!(f, "\"{}\" isn't {}", input, expected),
+            GameError::NothingToUndo => write!(f, "there is nothing to undo"),
+            GameError::NothingToRedo => write!(f, "there is nothing to redo"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GameError::Config(error) => Some(error),
+            GameError::Advance(error) => Some(error),
+            GameError::UnknownPlayer(_) | GameError::InvalidInput { .. } | GameError::NothingToUndo | GameError::NothingToRedo => None,
+        }
+    }
+}
+
+impl From<ConfigError> for GameError {
+    fn from(error: ConfigError) -> Self {
+        GameError::Config(error)
+    }
+}
+
+impl From<AdvanceError> for GameError {
+    fn from(error: AdvanceError) -> Self {
+        GameError::Advance(error)
+    }
+}
+
+/// Where the game is paused: between turns, over, or waiting on one of the decisions.
+/// Everything between two decisions runs in one go, so there is no state for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum Step {
+    BeginTurn,
+    SetDice { player_id: u32, dice: [DieResult; 6], keep: [bool; 6], roll: u8, rerolls: u8 },
+    KeepDice { player_id: u32, dice: [DieResult; 6], reroll: u8, rerolls: u8 },
+    ConcedeTokyo { player_id: u32, zone: TokyoZone },
+    YieldTokyo { attacker_id: u32, zone: TokyoZone, occupant_id: u32, next: Option<(TokyoZone, u32)> }, // next: the other occupant, asked after
+    EnterTokyo { player_id: u32, zone: TokyoZone },
+    Shop { player_id: u32 },
+    Purchase { player_id: u32, card: usize, affordable: u8 },
+    GameOver,
+}
+
+/// How a game was won.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Victory {
+    Points { player_id: u32, max_vp: u8 }, // Reached their VP target
+    LastStanding { player_id: u32 },       // Everyone else was eliminated
+    NoSurvivors,                           // The last monsters fell together
+}
+
+/// Structured record of what happened during the game, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum GameEvent {
+    GameStarted { seed: u64, preset: String },
+    PracticeStarted, // Dice can be set by hand from here on; the game is unranked
+    MonsterChosen { player_id: u32, monster: Monster },
+    TurnStarted { turn: u32, player_id: u32, hp: u8, victory_points: u8 },
+    RoundStarted, // Every living monster is queued for another turn
+    PhaseEnded { player_id: u32, phase: TurnPhase, duration: Duration },
+    DiceRolled { player_id: u32, roll: u8, rolls: u8, dice: [DieResult; 6], kept: [bool; 6] }, // Before each keep decision
+    DiceResolved { player_id: u32, dice: [DieResult; 6], kept: [bool; 6] }, // kept: going into the last roll
+    DiceChanged { player_id: u32, by: u32, effect: String, die: usize, face: DieResult, rerolled: bool }, // An effect of `by`'s changed a die just rolled
+    TriplesScored { player_id: u32, vp: u8, total: u8 },
+    EnergyGained { player_id: u32, amount: u8, total: u8 },
+    Healed { player_id: u32, amount: u8, hp: u8 },
+    HeartsIgnored { player_id: u32, overtime: bool }, // In Tokyo, or no healing in overtime
+    AttackLaunched { player_id: u32, damage: u8, from_tokyo: bool, targets: Vec<u32> }, // Each target then gets a DamageDealt or DamageBlocked
+    DamageDealt { attacker_id: u32, target_id: u32, damage: u8, hp: u8, source: DamageSource, card: Option<String> }, // card: for card damage
+    TokyoHeld { player_id: u32, zone: TokyoZone, vp: u8, total: u8 }, // Upkeep VP
+    TokyoEntered { player_id: u32, zone: TokyoZone, vp: u8, total: u8 },
+    TokyoEntryDeclined { player_id: u32 },
+    TokyoConceded { player_id: u32, zone: TokyoZone },
+    TokyoBayClosed { player_id: Option<u32> }, // player_id: the occupant sent out, if any
+    TokyoVacated { player_id: u32, zone: TokyoZone }, // The occupant was eliminated
+    TokyoYielded { player_id: u32, zone: TokyoZone, attacker_id: u32 },
+    TokyoDefended { player_id: u32, zone: TokyoZone, attacker_id: u32 },
+    CardRevealed { card: String }, // A new card was dealt into the shop
+    EvolutionDrawn { player_id: u32, evolution: String }, // Power Up!: three Hearts were rolled
+    EvolutionPlayed { player_id: u32, evolution: String, permanent: bool },
+    CardPurchased { player_id: u32, card: String, cost: u8, energy: u8, price: CardCost }, // energy: left afterwards; price: how the printed cost became `cost`
+    ShopSwept { player_id: u32, energy: u8 },
+    VpGained { player_id: u32, amount: u8, source: VpSource, total: u8 }, // From card effects
+    DamageBlocked { player_id: u32, blocked_by: String, attacker_id: u32, damage: u8, source: DamageSource, card: Option<String> },
+    PseudoCardBought { player_id: u32, card: String, times: u8, spent: u8, energy: u8 }, // What it did is logged after it
+    OvertimeStarted { turn: u32, hold_vp: u8 }, // Healing is off from here on
+    TurnSkipped { player_id: u32 },
+    SkipScheduled { player_id: u32 },
+    PlayerEliminated { player_id: u32, placement: u8, turn: u32 }, // placement: 1 = winner
+    ExtraTurnGranted { player_id: u32 },
+    MaxHpChanged { player_id: u32, max_hp: u8, hp: u8 },
+    MaxVpChanged { player_id: u32, max_vp: u8 },
+    GameOver { victory: Victory },
+}
+
+impl GameEvent {
+    /// Whether this is a turning point worth lingering on when playing a game back:
+    /// Tokyo changing hands, eliminations, overtime and the end of the game.
+    pub fn is_major(&self) -> bool {
+        matches!(self,
+            GameEvent::TokyoEntered { .. }
+            | GameEvent::TokyoYielded { .. }
+            | GameEvent::TokyoConceded { .. }
+            | GameEvent::TokyoBayClosed { .. }
+            | GameEvent::TokyoVacated { .. }
+            | GameEvent::PlayerEliminated { .. }
+            | GameEvent::OvertimeStarted { .. }
+            | GameEvent::GameOver { .. })
+    }
+}
+
+/// A `GameEvent` stamped with when it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimedEvent {
+    pub elapsed: Duration,       // Monotonic time since the game started
+    pub wall_clock: SystemTime,  // Real-world time, for lining events up with anything outside the game
+    pub event: GameEvent,
+}
+
+/// One player's standing at the end of a turn. The game keeps a row per player per turn,
+/// so a saved game can be charted without replaying its history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineRow {
+    pub turn: u32,
+    pub player_id: u32,
+    pub name: String,
+    pub hp: u8,
+    pub victory_points: u8,
+    pub energy: u8,
+    pub tokyo: Option<TokyoZone>,
+}
+
+/// The central Game manager. Saves hold everything but the phase hooks and the clock of the
+/// phase in progress, so a game can be saved while it waits on any decision.
+#[derive(Serialize, Deserialize)]
+pub struct Game {
+    #[serde(default)]
+    pub seed: u64,        // Master seed; every RNG stream in the game is derived from it
+    pub players: Vec<Player>,
+    pub tokyo: Tokyo,
+    pub shop: Shop,
+    pub start_hp: u8, // HP every monster started with
+    pub max_hp: u8, // Starting max HP for every monster; effects change it per player
+    pub max_vp: u8, // Starting VP target for every monster; effects change it per player
+    pub preset: String, // Name of the preset the config came from
+    pub rules: RuleSet,
+    pub privacy: Privacy,
+    pub overtime: bool, // Sudden death: no healing, double Tokyo hold VP
+    pub turn: u32,      // Number of the turn in progress (0 before the first turn)
+    pub practice: bool, // Dice are set by hand; unranked and left out of stats
+    turn_queue: VecDeque<u32>,   // Upcoming turns this round, front first; refilled in seat order
+    pending_skips: HashMap<u32, u8>, // Turns each player still has to skip
+    pub eliminated: Vec<(u32, u8)>, // (player ID, placement) in elimination order
+    pub spectators: Vec<u32>,       // Eliminated players who asked to keep watching
+    pub damage_log: Vec<DamageRecord>, // Every point of damage dealt this game, in order
+    pub history: Vec<TimedEvent>,      // Structured, timestamped log of the game
+    pub timeline: Vec<TimelineRow>,    // Every player's standing after each turn
+    step: Step,                        // Where the game is paused
+    spectate_queue: VecDeque<u32>,     // Just-eliminated players yet to say whether they spectate
+    turn_damage_mark: usize,           // Length of `damage_log` when the turn in progress began
+    actions: Option<Vec<Action>>,      // Every action taken, for replays (None: resumed from an older save)
+    #[serde(skip, default = "clock::default_clock")]
+    clock: Box<dyn Clock>,
+    #[serde(skip)]
+    clock_mark: Duration,          // Clock reading when the game was set up, loaded or given its clock
+    #[serde(skip)]
+    elapsed_before: Duration,      // Game time already played at `clock_mark`
+    #[serde(skip)]
+    phase_hooks: Vec<Box<dyn PhaseHook>>,
+    #[serde(skip)]
+    subscribers: Vec<Box<dyn EventSubscriber>>,
+    #[serde(skip)]
+    effects: Vec<(u32, Box<dyn GameEffect>)>, // Effects registered with `add_effect`, with their owners
+    #[serde(skip)]
+    designs: Vec<(u32, String)>,              // Players given a custom monster with `add_monster`, and its name
+    #[serde(skip)]
+    health: HealthRegistry,                   // Subscribers still running, and why the others stopped
+    #[serde(skip)]
+    current_phase: Option<(u32, TurnPhase, Duration)>, // Player, phase and the clock reading when it began
+    #[serde(skip)]
+    undo_stack: Vec<(Decision, Action, String)>, // Recent decisions, their answers and the game (as JSON) before them
+    #[serde(skip)]
+    redo_stack: Vec<Action>,                     // Answers taken back by `undo`, most recent last
+    #[serde(skip)]
+    search: bool,                                // A `clone_for_search` copy: no log, timeline, actions or undo
+}
+
+/// What this build of the engine supports, so frontends can adapt their UI to it.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Capabilities {
+    pub engine_version: &'static str,
+    pub presets: Vec<&'static str>,
+    pub rule_options: Vec<&'static str>, // Fields of `GameConfig`/`RuleSet` that can be changed
+    pub min_players: usize,
+    pub max_players: usize,
+    pub tokyo_zones: Vec<&'static str>,
+    pub expansions: Vec<&'static str>,   // Turned on with a `rules` option of the same name
+    pub card_sets: Vec<&'static str>,    // Energy Exchange is a pseudo-card, not part of any set
+    pub features: Vec<&'static str>,     // Cargo features this build was compiled with
+    pub protocol_version: Option<u32>,   // Of the network protocol, see `PROTOCOL_VERSION` (None without `net`)
+}
+
+/// Entry point for questions about the engine itself rather than any one game.
+pub struct Engine;
+
+impl Engine {
+    pub const VERSION: &'static str = "0.0.1-alpha";
+
+    pub fn capabilities() -> Capabilities {
+        Capabilities {
+            engine_version: Engine::VERSION,
+            presets: PRESETS.iter().map(|preset| preset.name).collect(),
+            rule_options: vec![
+                "start_hp", "max_hp", "max_vp", "rules.tokyo_entry_vp", "rules.tokyo_hold_vp",
+                "rules.heal_in_tokyo", "rules.energy_per_vp", "rules.energy_per_heal", "rules.turn_limit", "rules.rerolls", "rules.energy",
+                "rules.optional_entry", "rules.economy.energy_per_die", "rules.economy.card_cost_percent",
+                "rules.economy.sweep_cost", "rules.undo_limit", "rules.power_up", "rules.yield_unhurt", "rules.tie_break", "rules.immediate_victory", "privacy.anonymize_names",
+                "privacy.wall_clock",
+            ],
+            min_players: 2,
+            max_players: 6,
+            tokyo_zones: vec![TokyoZone::City.label(), TokyoZone::Bay.label()],
+            expansions: EXPANSIONS.iter().map(|expansion| expansion.id()).collect(),
+            card_sets: vec!["starter"],
+            features: [("storage", cfg!(feature = "storage")), ("net", cfg!(feature = "net")), ("ai", cfg!(feature = "ai")), ("cli", cfg!(feature = "cli"))]
+                .into_iter()
+                .filter_map(|(feature, on)| on.then_some(feature))
+                .collect(),
+            #[cfg(feature = "net")]
+            protocol_version: Some(PROTOCOL_VERSION),
+            #[cfg(not(feature = "net"))]
+            protocol_version: None,
+        }
+    }
+}
+
+impl std::fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |items: &[&str]| if items.is_empty() { "none".to_string() } else { items.join(", ") };
+        writeln!(f, "engine version:   {}", self.engine_version)?;
+        writeln!(f, "players:          {}-{}", self.min_players, self.max_players)?;
+        writeln!(f, "presets:          {}", list(&self.presets))?;
+        writeln!(f, "rule options:     {}", list(&self.rule_options))?;
+        writeln!(f, "tokyo zones:      {}", list(&self.tokyo_zones))?;
+        writeln!(f, "expansions:       {}", list(&self.expansions))?;
+        writeln!(f, "card sets:        {}", list(&self.card_sets))?;
+        writeln!(f, "features:         {}", list(&self.features))?;
+        writeln!(f, "protocol version: {}", self.protocol_version.map_or("none".to_string(), |version| version.to_string()))
+    }
+}
+
+/// Reads a player count typed at setup, refusing anything outside what the engine
+/// supports rather than quietly clamping it.
+pub fn parse_player_count(input: &str) -> Result<usize, GameError> {
+    let capabilities = Engine::capabilities();
+    input.trim().parse::<usize>().ok()
+        .filter(|count| (capabilities.min_players..=capabilities.max_players).contains(count))
+        .ok_or_else(|| GameError::InvalidInput {
+            input: input.trim().to_string(),
+            expected: format!("a player count from {} to {}", capabilities.min_players, capabilities.max_players),
+        })
+}
+
+/// Refuses setups where players can't be told apart: blank names or the same name twice
+/// (ignoring case), since every prompt and report identifies players by name.
+pub fn validate_player_names(player_names: &[&str]) -> Result<(), ConfigError> {
+    let mut issues = Vec::new();
+    for (i, name) in player_names.iter().enumerate() {
+        let field = format!("players[{}]", i + 1);
+        if name.trim().is_empty() {
+            issues.push(ConfigIssue::new(&field, "the name is blank".to_string(), "give every player a name".to_string()));
+        } else if let Some(first) = player_names[..i].iter().position(|other| other.eq_ignore_ascii_case(name)) {
+            issues.push(ConfigIssue::new(&field, format!("\"{}\" is already Player {}'s name", name, first + 1),
+                                         "give every player a different name".to_string()));
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigError { source: "the player list".to_string(), issues })
+    }
+}
+
+/// Save migration 15 -> 16: Energy Exchange purchases used to carry the VP they brought;
+/// now they record the Energy spent, and a `VpGained` from cards follows them.
+fn split_pseudo_card_purchases(history: &mut Vec<serde_json::Value>, cost: u64) {
+    let mut i = 0;
+    while i < history.len() {
+        let Some(bought) = history[i]["event"].get_mut("PseudoCardBought").and_then(|b| b.as_object_mut()) else {
+            i += 1;
+            continue;
+        };
+        let (vp, total) = (bought.remove("vp"), bought.remove("total"));
+        let times = bought.get("times").and_then(|t| t.as_u64()).unwrap_or(0);
+        bought.insert("spent".to_string(), (times * cost).min(u8::MAX as u64).into());
+        let gained = serde_json::json!({ "VpGained": {
+            "player_id": bought["player_id"], "amount": vp, "source": "Cards", "total": total,
+        } });
+        let mut follow_up = history[i].clone();
+        follow_up["event"] = gained;
+        history.insert(i + 1, follow_up);
+        i += 2;
+    }
+}
+
+/// Save migration 11 -> 12: fills in what damage events didn't record yet. Every hit
+/// follows the attack or card purchase that caused it, and Armor only ever blocked 1 damage.
+fn add_damage_metadata(history: &mut [serde_json::Value]) {
+    let mut cause = serde_json::json!({ "attacker_id": 0, "source": "Cards", "card": null });
+    let mut attack: Option<usize> = None; // Index of the attack whose targets are being collected
+    for i in 0..history.len() {
+        let event = &history[i]["event"];
+        if let Some(launched) = event.get("AttackLaunched") {
+            let source = if launched["from_tokyo"].as_bool() == Some(true) { "ClawsFromTokyo" } else { "ClawsIntoTokyo" };
+            cause = serde_json::json!({ "attacker_id": launched["player_id"], "source": source, "card": null });
+            history[i]["event"]["AttackLaunched"]["targets"] = serde_json::json!([]);
+            attack = Some(i);
+        } else if let Some(purchased) = event.get("CardPurchased") {
+            cause = serde_json::json!({ "attacker_id": purchased["player_id"], "source": "Cards", "card": purchased["card"] });
+            attack = None;
+        } else if let Some(dealt) = event.get("DamageDealt") {
+            let (target_id, card) = (dealt["target_id"].clone(), cause["card"].clone());
+            history[i]["event"]["DamageDealt"]["card"] = card;
+            if let Some(attack) = attack {
+                history[attack]["event"]["AttackLaunched"]["targets"].as_array_mut().expect("Set above").push(target_id);
+            }
+        } else if let Some(blocked) = event.get("DamageBlocked") {
+            let target_id = blocked["player_id"].clone();
+            let blocked = &mut history[i]["event"]["DamageBlocked"];
+            blocked["attacker_id"] = cause["attacker_id"].clone();
+            blocked["damage"] = 1.into();
+            blocked["source"] = cause["source"].clone();
+            blocked["card"] = cause["card"].clone();
+            if let Some(attack) = attack {
+                history[attack]["event"]["AttackLaunched"]["targets"].as_array_mut().expect("Set above").push(target_id);
+            }
+        } else {
+            attack = None;
+        }
+    }
+}
+
+/// Every leaf of a JSON object as (dotted path, value), e.g. ("rules.rerolls", 3).
+fn collect_leaves(value: &serde_json::Value, prefix: String, leaves: &mut Vec<(String, serde_json::Value)>) {
+    match value.as_object() {
+        Some(fields) => {
+            for (key, value) in fields {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                collect_leaves(value, path, leaves);
+            }
+        }
+        None => leaves.push((prefix, value.clone())),
+    }
+}
+
+// --- 2. Dice Roll Implementation ---
+
+/// FNV-1a over the compact JSON of `save`. serde_json keeps object keys sorted, so the
+/// same state always hashes the same. This catches damage and casual edits, not forgery.
+fn save_checksum(save: &serde_json::Value) -> u64 {
+    save.to_string().bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Derives an independent seed for one RNG stream (a seat, or later the deck) from the game seed.
+/// Each stream only ever draws from its own generator, so an extra roll by one player
+/// can't shift anyone else's future dice. Uses the SplitMix64 finalizer to spread the bits.
+fn derive_stream_seed(game_seed: u64, stream: u64) -> u64 {
+    let mut z = game_seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Stands in for an RNG stream left out of a view (see `Game::view_json`), so a game read
+/// from one has no real luck to draw on.
+pub(crate) fn hidden_stream() -> ChaCha12Rng {
+    ChaCha12Rng::seed_from_u64(0)
+}
+
+fn roll_die<R: Rng>(rng: &mut R) -> DieResult {
+    match rng.gen_range(1..=6) {
+        1 => DieResult::One,
+        2 => DieResult::Two,
+        3 => DieResult::Three,
+        4 => DieResult::Energy,
+        5 => DieResult::Claw,
+        6 => DieResult::Heart,
+        _ => unreachable!(),
+    }
+}
+
+fn roll_dice<R: Rng>(rng: &mut R) -> [DieResult; 6] {
+    let mut results = [DieResult::One; 6];
+    for result in results.iter_mut() {
+        *result = roll_die(rng);
+    }
+    results
+}
+
+/// Rerolls every die not marked in `keep`, leaving kept dice as they are.
+fn reroll_dice<R: Rng>(rng: &mut R, dice: &mut [DieResult; 6], keep: &[bool; 6]) {
+    for (die, &kept) in dice.iter_mut().zip(keep) {
+        if !kept {
+            *die = roll_die(rng);
+        }
+    }
+}
+// --- 3. Game Logic Implementation ---
+
+impl Game {
+    /// Sets up a new game, refusing rule sets that can't produce a fair game.
+    pub fn new(player_names: &[&str], seed: u64, config: GameConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        validate_player_names(player_names)?;
+        let GameConfig { preset, start_hp, max_hp, max_vp, rules, privacy } = config;
+
+        let players: Vec<Player> = player_names.iter()
+            .enumerate()
+            .map(|(i, &name)| Player::new(i as u32 + 1, name, seed, start_hp, max_hp, max_vp))
+            .collect();
+
+        // Without Energy nobody could buy a card, so the shop stays empty
+        let cards: &[Card] = if rules.energy { &STARTER_CARDS } else { &[] };
+        let (shop, revealed) = Shop::new(cards, derive_stream_seed(seed, 0));
+        let mut game = Game {
+            seed,
+            players,
+            tokyo: Tokyo::new(player_names.len()),
+            shop,
+            start_hp,
+            max_hp,
+            max_vp,
+            preset,
+            rules,
+            privacy,
+            overtime: false,
+            turn: 0,
+            practice: false,
+            turn_queue: VecDeque::new(),
+            pending_skips: HashMap::new(),
+            eliminated: Vec::new(),
+            spectators: Vec::new(),
+            damage_log: Vec::new(),
+            history: Vec::new(),
+            timeline: Vec::new(),
+            step: Step::BeginTurn,
+            spectate_queue: VecDeque::new(),
+            turn_damage_mark: 0,
+            actions: Some(Vec::new()),
+            clock: clock::default_clock(),
+            clock_mark: Duration::ZERO,
+            elapsed_before: Duration::ZERO,
+            phase_hooks: Vec::new(),
+            subscribers: Vec::new(),
+            effects: Vec::new(),
+            designs: Vec::new(),
+            health: HealthRegistry::new(),
+            current_phase: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            search: false,
+        };
+        let preset = game.preset.clone();
+        game.log(GameEvent::GameStarted { seed, preset });
+        for card in revealed {
+            game.log(GameEvent::CardRevealed { card: card.name.to_string() });
+        }
+        Ok(game)
+    }
+
+    /// Evolution decks are shuffled with stream `EVOLUTION_STREAMS + player ID`, clear of
+    /// the seats' dice streams and the shop's.
+    const EVOLUTION_STREAMS: u64 = 1 << 32;
+
+    /// Version of the save format written by `to_json`. Bump it whenever a change to the
+    /// game state or its events would stop older saves from loading, add the matching step
+    /// to `migrate_save`, and check in a game saved in the new format as
+    /// `tests/fixtures/save_v{N}.json` so it is loaded and played on by the tests from then on.
+    pub const SAVE_FORMAT_VERSION: u32 = 22;
+
+    /// The whole game state as JSON, for saving. It is tagged with `SAVE_FORMAT_VERSION` and
+    /// the engine version, and carries a checksum over everything else so that a damaged or
+    /// edited save (and the replayable history in it) is caught when it is loaded.
+    pub fn to_json(&self) -> String {
+        let mut save = serde_json::to_value(self).expect("Game state is always serializable");
+        save["format_version"] = Game::SAVE_FORMAT_VERSION.into();
+        save["engine_version"] = Engine::VERSION.into();
+        save["checksum"] = format!("{:016x}", save_checksum(&save)).into();
+        save.to_string()
+    }
+
+    /// Restores a game saved with `to_json` by this or any earlier version of the crate
+    /// that checksummed its saves (format 4 on). The game clock carries on from where the
+    /// save left off. Its config is validated as in `new`, so a save whose rules can't be
+    /// played is refused rather than failing mid-game.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Game::load_json(json, false)
+    }
+
+    /// As `from_json`, but also takes saves from before format 4, which carry no checksum,
+    /// so nothing catches one that was edited. Only for saves from a source you trust.
+    pub fn from_legacy_json(json: &str) -> serde_json::Result<Self> {
+        Game::load_json(json, true)
+    }
+
+    fn load_json(json: &str, legacy: bool) -> serde_json::Result<Self> {
+        use serde::de::Error;
+        let mut save: serde_json::Value = serde_json::from_str(json)?;
+        Game::verify_save(&mut save, legacy)?;
+        Game::migrate_save(&mut save)?;
+        let mut game: Game = serde_json::from_value(save)?;
+        if let Err(error) = game.config().validate() {
+            return Err(serde_json::Error::custom(ConfigError { source: "the saved game".to_string(), ..error }));
+        }
+        game.clock_mark = game.clock.monotonic();
+        game.elapsed_before = game.duration();
+        Ok(game)
+    }
+
+    /// Checks and strips the checksum and engine version of a save. Saves from before
+    /// format 4 have no checksum, so they are only let through unchecked if `legacy` allows
+    /// it, and only if nothing in them is from a later format: a checksummed save with its
+    /// version turned back and its checksum taken out is refused.
+    fn verify_save(save: &mut serde_json::Value, legacy: bool) -> serde_json::Result<()> {
+        use serde::de::Error;
+        let version = save.get("format_version").and_then(|v| v.as_u64()).unwrap_or(1);
+        let Some(fields) = save.as_object_mut() else {
+            return Err(serde_json::Error::custom("a save must be a JSON object"));
+        };
+        let checksum = fields.remove("checksum");
+        let checksummed = version >= 4 || checksum.is_some() || fields.contains_key("engine_version");
+        if !checksummed {
+            if let Some(field) = Game::CHECKSUMMED_FIELDS.iter().find(|path| path.split('.').try_fold(&*save, |node, key| node.get(key)).is_some()) {
+                return Err(serde_json::Error::custom(format!(
+                    "save claims format {} but has {}, which only checksummed saves have: the file was edited",
+                    version, field
+                )));
+            }
+            if !legacy {
+                return Err(serde_json::Error::custom(format!(
+                    "save format {} has no checksum, so damage or edits can't be caught; load it as a legacy save only if you trust it",
+                    version
+                )));
+            }
+        }
+        if checksummed {
+            let expected = format!("{:016x}", save_checksum(save));
+            if checksum.as_ref().and_then(|c| c.as_str()) != Some(expected.as_str()) {
+                return Err(serde_json::Error::custom("save checksum doesn't match: the file was damaged or edited"));
+            }
+        }
+        if let Some(fields) = save.as_object_mut() {
+            fields.remove("engine_version");
+        }
+        Ok(())
+    }
+
+    /// Fields that only saves of format 4 or later have, by dotted path.
+    const CHECKSUMMED_FIELDS: [&'static str; 16] = [
+        "step", "spectate_queue", "turn_damage_mark", "actions", "practice", "start_hp", "privacy", "rules.energy",
+        "rules.optional_entry", "rules.economy", "rules.undo_limit", "rules.power_up", "rules.energy_per_heal",
+        "rules.yield_unhurt", "rules.tie_break", "rules.immediate_victory",
+    ];
+
+    /// Upgrades a save, one format version at a time, to `SAVE_FORMAT_VERSION`.
+    ///
+    /// - 1: the first save format, with no `format_version` field and no timeline.
+    /// - 2: adds the per-turn `timeline`.
+    /// - 3: adds each player's `monster`.
+    /// - 4: adds `engine_version` and the `checksum` checked by `verify_save`.
+    /// - 5: adds the turn `step` the game is paused at, so saves can be made mid-turn.
+    /// - 6: adds `rules.energy`, which was always on before.
+    /// - 7: adds the `actions` taken so far, for replays.
+    /// - 8: adds the `practice` flag.
+    /// - 9: adds `rules.optional_entry`; entering Tokyo was always optional before.
+    /// - 10: adds `start_hp`, which was always 10 before.
+    /// - 11: adds `rules.economy`; Energy and prices were always as printed before.
+    /// - 12: damage events name their attacker, source and card, and attacks list their
+    ///   targets; all of it is recovered from the events around them.
+    /// - 13: adds `rules.undo_limit`; there was no undo before.
+    /// - 14: adds `rules.power_up` and each player's evolution cards, for the Power Up! expansion.
+    /// - 15: adds the `privacy` settings; everything was logged before.
+    /// - 16: adds `rules.energy_per_heal`. Energy Exchange purchases log the Energy spent,
+    ///   and the VP they bring follows as its own event.
+    /// - 17: `DiceResolved` records which dice were kept going into the last roll. Older
+    ///   events are marked all kept, which leaves them out of the dice luck statistic.
+    /// - 18: `DamageBlocked` names the effect that blocked the hit; only Armor could before.
+    /// - 19: adds `rules.yield_unhurt`; occupants were always asked to yield before.
+    /// - 20: adds `rules.tie_break`; ties were broken by VP, then HP, before.
+    /// - 21: adds `rules.immediate_victory`; victory was only checked between turns before.
+    /// - 22: `CardPurchased` carries the price breakdown. Older purchases get one worked out
+    ///   from the printed cost and the economy, the only things that set prices before.
+    fn migrate_save(save: &mut serde_json::Value) -> serde_json::Result<()> {
+        use serde::de::Error;
+        let mut version = save.get("format_version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+        if version > Game::SAVE_FORMAT_VERSION {
+            return Err(serde_json::Error::custom(format!(
+                "save format version {} is newer than this build supports ({})",
+                version,
+                Game::SAVE_FORMAT_VERSION
+            )));
+        }
+        while version < Game::SAVE_FORMAT_VERSION {
+            match version {
+                1 => {
+                    if save.get("timeline").is_none() {
+                        save["timeline"] = serde_json::Value::Array(Vec::new());
+                    }
+                }
+                2 => {
+                    if let Some(players) = save.get_mut("players").and_then(|p| p.as_array_mut()) {
+                        for player in players {
+                            player["monster"] = serde_json::Value::Null;
+                        }
+                    }
+                }
+                3 => {} // Nothing to convert; older saves just weren't checksummed
+                4 => {
+                    // Older saves were always made between turns
+                    save["step"] = serde_json::to_value(Step::BeginTurn)?;
+                    save["spectate_queue"] = serde_json::Value::Array(Vec::new());
+                    save["turn_damage_mark"] = 0.into();
+                }
+                5 => {
+                    save["rules"]["energy"] = true.into();
+                }
+                6 => {
+                    // The moves before this save were never recorded, so it can't be replayed
+                    save["actions"] = serde_json::Value::Null;
+                }
+                7 => {
+                    save["practice"] = false.into();
+                }
+                8 => {
+                    save["rules"]["optional_entry"] = true.into();
+                }
+                9 => {
+                    save["start_hp"] = 10.into();
+                }
+                10 => {
+                    save["rules"]["economy"] = serde_json::to_value(Economy::default()).expect("Economies are always serializable");
+                }
+                11 => {
+                    if let Some(history) = save.get_mut("history").and_then(|h| h.as_array_mut()) {
+                        add_damage_metadata(history);
+                    }
+                }
+                12 => {
+                    save["rules"]["undo_limit"] = 0.into();
+                }
+                13 => {
+                    save["rules"]["power_up"] = false.into();
+                    if let Some(players) = save.get_mut("players").and_then(|p| p.as_array_mut()) {
+                        for player in players {
+                            for field in ["evolutions", "evolved", "evolution_deck"] {
+                                player[field] = serde_json::Value::Array(Vec::new());
+                            }
+                        }
+                    }
+                }
+                14 => {
+                    save["privacy"] = serde_json::to_value(Privacy::default()).expect("Privacy settings are always serializable");
+                }
+                15 => {
+                    save["rules"]["energy_per_heal"] = serde_json::Value::Null;
+                    let cost = save["rules"]["energy_per_vp"].as_u64().unwrap_or(0);
+                    if let Some(history) = save.get_mut("history").and_then(|h| h.as_array_mut()) {
+                        split_pseudo_card_purchases(history, cost);
+                    }
+                }
+                16 => {
+                    if let Some(history) = save.get_mut("history").and_then(|h| h.as_array_mut()) {
+                        for timed in history {
+                            if let Some(resolved) = timed["event"].get_mut("DiceResolved").and_then(|r| r.as_object_mut()) {
+                                resolved.insert("kept".to_string(), serde_json::json!([true, true, true, true, true, true]));
+                            }
+                        }
+                    }
+                }
+                17 => {
+                    if let Some(history) = save.get_mut("history").and_then(|h| h.as_array_mut()) {
+                        for timed in history {
+                            if let Some(blocked) = timed["event"].get_mut("DamageBlocked").and_then(|b| b.as_object_mut()) {
+                                let passive = blocked.remove("passive").map(serde_json::from_value::<Passive>).transpose()?;
+                                let blocked_by = passive.unwrap_or(Passive::Armor).name();
+                                blocked.insert("blocked_by".to_string(), blocked_by.into());
+                            }
+                        }
+                    }
+                }
+                18 => {
+                    save["rules"]["yield_unhurt"] = true.into();
+                }
+                19 => {
+                    save["rules"]["tie_break"] = serde_json::to_value(RuleSet::legacy_tie_break())?;
+                }
+                20 => {
+                    save["rules"]["immediate_victory"] = false.into();
+                }
+                21 => {
+                    let percent = save["rules"]["economy"]["card_cost_percent"].as_u64().unwrap_or(100) as u16;
+                    if let Some(history) = save.get_mut("history").and_then(|h| h.as_array_mut()) {
+                        for timed in history {
+                            if let Some(purchased) = timed["event"].get_mut("CardPurchased").and_then(|p| p.as_object_mut()) {
+                                let cost = purchased.get("cost").and_then(|c| c.as_u64()).unwrap_or(0) as u8;
+                                let card = purchased.get("card").and_then(|c| c.as_str());
+                                let printed = STARTER_CARDS.iter().find(|c| Some(c.name) == card).map_or(cost, |c| c.cost);
+                                let mut price = CardCost { printed, steps: Vec::new(), total: printed as i16 };
+                                price.apply(cost as i16, || CardCost::economy_source(percent));
+                                purchased.insert("price".to_string(), serde_json::to_value(price)?);
+                            }
+                        }
+                    }
+                }
+                _ => unreachable!("Every older save format has a migration step"),
+            }
+            version += 1;
+        }
+        if let Some(fields) = save.as_object_mut() {
+            fields.remove("format_version");
+        }
+        Ok(())
+    }
+
+    /// The config the game was set up with. Effects that change one monster's maximums
+    /// don't show here.
+    pub fn config(&self) -> GameConfig {
+        GameConfig {
+            preset: self.preset.clone(),
+            start_hp: self.start_hp,
+            max_hp: self.max_hp,
+            max_vp: self.max_vp,
+            rules: self.rules.clone(),
+            privacy: self.privacy,
+        }
+    }
+
+    /// Writes the game to `path` so it can be picked up later with `load_from_file`.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+
+    /// Loads a game written by `save_to_file`.
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(Game::from_json(&json)?)
+    }
+
+    /// The timeline as CSV, one row per player per turn, with a header line.
+    pub fn timeline_csv(&self) -> String {
+        let mut csv = String::from("turn,player_id,name,hp,victory_points,energy,tokyo\n");
+        for row in &self.timeline {
+            let tokyo = row.tokyo.map_or("", |zone| zone.label());
+            csv.push_str(&format!(
+                "{},{},\"{}\",{},{},{},{}\n",
+                row.turn, row.player_id, row.name.replace('"', "\"\""), row.hp, row.victory_points, row.energy, tokyo
+            ));
+        }
+        csv
+    }
+
+    /// The timeline as a JSON array of rows.
+    pub fn timeline_json(&self) -> String {
+        serde_json::to_string_pretty(&self.timeline).expect("Timeline is always serializable")
+    }
+
+    /// Takes the time from `clock` from now on. Game time and the phase in progress carry
+    /// on from where the old clock left them. Like hooks, the clock isn't saved.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        let now = self.clock.monotonic();
+        self.elapsed_before += now.saturating_sub(self.clock_mark);
+        self.clock_mark = clock.monotonic();
+        if let Some((_, _, started)) = self.current_phase.as_mut() {
+            *started = self.clock_mark.saturating_sub(now.saturating_sub(*started));
+        }
+        self.clock = clock;
+    }
+
+    /// Registers a hook to be told when each turn phase begins and ends.
+    pub fn add_phase_hook(&mut self, hook: Box<dyn PhaseHook>) {
+        self.phase_hooks.push(hook);
+    }
+
+    /// Registers a subscriber to be sent every event from now on, as it is logged.
+    pub fn add_subscriber(&mut self, subscriber: Box<dyn EventSubscriber>) {
+        self.health.register(subscriber.name());
+        self.subscribers.push(subscriber);
+    }
+
+    /// Which subscribers are still running, and why any that failed were dropped.
+    pub fn health(&self) -> &HealthRegistry {
+        &self.health
+    }
+
+    /// Registers a custom effect for `owner`, e.g. the ability of a card or monster the
+    /// engine doesn't know, or a house rule (give each player their own). Like subscribers,
+    /// effects aren't saved, so register them again after loading a game.
+    pub fn add_effect(&mut self, owner: u32, effect: Box<dyn GameEffect>) {
+        self.effects.push((owner, effect));
+    }
+
+    /// The effects that apply to `owner`, in the order their hooks are called: the
+    /// monster's ability, the abilities of their Keep cards in the order they were bought,
+    /// their permanent evolutions, then effects registered with `add_effect`. An ability
+    /// granted more than once counts once.
+    pub fn effects_of(&self, owner: u32) -> Vec<&dyn GameEffect> {
+        let mut passives: Vec<Passive> = Vec::new();
+        if let Some(player) = self.get_player(owner) {
+            let granted = player.monster.map(|monster| monster.passive()).into_iter()
+                .chain(player.cards.iter().filter_map(|card| card.passive))
+                .chain(player.evolved.iter().filter_map(|evolution| evolution.passive));
+            for passive in granted {
+                if !passives.contains(&passive) {
+                    passives.push(passive);
+                }
+            }
+        }
+        let builtin = passives.into_iter()
+            .filter_map(|passive| Passive::ALL.iter().find(|&&p| p == passive))
+            .map(|passive| passive as &dyn GameEffect);
+        let registered = self.effects.iter()
+            .filter(|(effect_owner, _)| *effect_owner == owner)
+            .map(|(_, effect)| effect.as_ref());
+        builtin.chain(registered).collect()
+    }
+
+    /// Calls `hook` on each of `owner`'s effects in turn, applying what it returns before
+    /// asking the next one.
+    fn run_effects(&mut self, owner: u32, hook: impl Fn(&dyn GameEffect, &Game) -> Vec<CardEffect>) {
+        let mut i = 0;
+        while let Some(effect) = self.effects_of(owner).get(i).copied() {
+            let name = effect.name().to_string();
+            let outcome = hook(effect, self);
+            for effect in outcome {
+                self.apply_card_effect(owner, &name, effect);
+            }
+            i += 1;
+        }
+    }
+
+    /// Registers an observer to be called from now on. Like subscribers, observers aren't
+    /// saved, so register them again after loading a game.
+    pub fn add_observer(&mut self, observer: Box<dyn Observer>) {
+        self.add_subscriber(Box::new(ObserverSubscriber(observer)));
+    }
+
+    /// Starts timing `phase` for `player_id`, ending whichever phase was still open.
+    fn begin_phase(&mut self, player_id: u32, phase: TurnPhase) {
+        self.end_phase();
+        for hook in self.phase_hooks.iter_mut() {
+            hook.on_phase_begin(player_id, phase);
+        }
+        self.current_phase = Some((player_id, phase, self.clock.monotonic()));
+    }
+
+    /// Ends the open phase (if any), notifies hooks and logs its duration.
+    fn end_phase(&mut self) {
+        if let Some((player_id, phase, started)) = self.current_phase.take() {
+            let duration = self.clock.monotonic().saturating_sub(started);
+            for hook in self.phase_hooks.iter_mut() {
+                hook.on_phase_end(player_id, phase, duration);
+            }
+            self.log(GameEvent::PhaseEnded { player_id, phase, duration });
+        }
+    }
+
+    /// Adds every player's current standing to the timeline.
+    fn record_timeline(&mut self) {
+        if self.search {
+            return;
+        }
+        let rows: Vec<TimelineRow> = self.players.iter()
+            .map(|player| TimelineRow {
+                turn: self.turn,
+                player_id: player.id,
+                name: self.logged_name(player.id),
+                hp: player.hp,
+                victory_points: player.victory_points,
+                energy: player.energy,
+                tokyo: self.tokyo.zone_of(player.id),
+            })
+            .collect();
+        self.timeline.extend(rows);
+    }
+
+    /// How logs name `player_id`: their own name, or "Player <seat>" when the `privacy`
+    /// settings anonymize names.
+    pub fn logged_name(&self, player_id: u32) -> String {
+        match self.players.iter().position(|p| p.id == player_id) {
+            Some(seat) if self.privacy.anonymize_names => format!("Player {}", seat + 1),
+            Some(seat) => self.players[seat].name.clone(),
+            None => "Someone".to_string(),
+        }
+    }
+
+    /// Appends `event` to the history with monotonic and wall-clock timestamps. Every
+    /// event goes through here, so this is where the `privacy` settings are applied.
+    fn log(&mut self, event: GameEvent) {
+        // A search copy only keeps the one event the rules read back: how the game ended
+        if self.search {
+            if let GameEvent::GameOver { .. } = event {
+                self.history.push(TimedEvent { elapsed: Duration::ZERO, wall_clock: SystemTime::UNIX_EPOCH, event });
+            }
+            return;
+        }
+        let timed = TimedEvent {
+            elapsed: self.elapsed_before + self.clock.monotonic().saturating_sub(self.clock_mark),
+            wall_clock: if self.privacy.wall_clock { self.clock.wall_clock() } else { SystemTime::UNIX_EPOCH },
+            event,
+        };
+        let health = &mut self.health;
+        self.subscribers.retain_mut(|subscriber| match subscriber.on_event(&timed) {
+            Ok(()) => true,
+            Err(reason) => {
+                health.disable(subscriber.name(), &reason);
+                false
+            }
+        });
+        self.history.push(timed);
+    }
+
+    /// Total time from game start to the last logged event.
+    pub fn duration(&self) -> Duration {
+        self.history.last().map_or(Duration::ZERO, |e| e.elapsed)
+    }
+
+    /// Time each player has spent on their own turns (rolling, resolving and buying), in seat order.
+    pub fn thinking_times(&self) -> Vec<(u32, Duration)> {
+        self.players.iter()
+            .map(|player| {
+                let total = self.history.iter()
+                    .filter_map(|e| match e.event {
+                        GameEvent::PhaseEnded { player_id, duration, .. } if player_id == player.id => Some(duration),
+                        _ => None,
+                    })
+                    .sum();
+                (player.id, total)
+            })
+            .collect()
+    }
+
+    /// Every player's totals so far, in seat order. Worked out from the history and the
+    /// damage ledger, so games saved before this existed get them too.
+    pub fn player_stats(&self) -> Vec<PlayerStats> {
+        let mut stats: Vec<PlayerStats> = self.players.iter()
+            .map(|player| PlayerStats {
+                player_id: player.id,
+                damage_dealt: 0,
+                damage_taken: 0,
+                knockouts: 0,
+                energy_earned: 0,
+                energy_spent: 0,
+                turns: 0,
+                turns_in_tokyo: 0,
+                vp_by_source: VpSource::ALL.iter().map(|&source| (source, player.vp_by_source.get(&source).copied().unwrap_or(0))).collect(),
+                dice_luck: 0.0,
+            })
+            .collect();
+        fn entry(stats: &mut [PlayerStats], player_id: u32) -> Option<&mut PlayerStats> {
+            stats.iter_mut().find(|s| s.player_id == player_id)
+        }
+
+        for record in &self.damage_log {
+            if let Some(attacker) = entry(&mut stats, record.attacker_id) {
+                attacker.damage_dealt += record.amount as u32;
+                attacker.knockouts += record.knockout as u32;
+            }
+            if let Some(target) = entry(&mut stats, record.target_id) {
+                target.damage_taken += record.amount as u32;
+            }
+        }
+        for timed in &self.history {
+            // Dice set by hand in practice games have no luck to speak of
+            let luck = match timed.event {
+                GameEvent::DiceRolled { player_id, ref dice, ref kept, .. } | GameEvent::DiceResolved { player_id, ref dice, ref kept } if !self.practice => {
+                    roll_luck(dice, kept).map(|luck| (player_id, luck))
+                }
+                _ => None,
+            };
+            if let Some((player_id, luck)) = luck {
+                if let Some(player) = entry(&mut stats, player_id) {
+                    player.dice_luck += luck;
+                }
+            }
+            let (player_id, turns, in_tokyo, earned, spent) = match timed.event {
+                GameEvent::TurnStarted { player_id, .. } => (player_id, 1, 0, 0, 0),
+                GameEvent::TokyoHeld { player_id, .. } => (player_id, 0, 1, 0, 0),
+                GameEvent::EnergyGained { player_id, amount, .. } => (player_id, 0, 0, amount as u32, 0),
+                GameEvent::CardPurchased { player_id, cost, .. } => (player_id, 0, 0, 0, cost as u32),
+                GameEvent::ShopSwept { player_id, .. } => (player_id, 0, 0, 0, self.sweep_cost() as u32),
+                GameEvent::PseudoCardBought { player_id, spent, .. } => (player_id, 0, 0, 0, spent as u32),
+                _ => continue,
+            };
+            if let Some(player) = entry(&mut stats, player_id) {
+                player.turns += turns;
+                player.turns_in_tokyo += in_tokyo;
+                player.energy_earned += earned;
+                player.energy_spent += spent;
+            }
+        }
+        stats
+    }
+
+    /// Finds a player by ID (used for getting mutable access).
+    fn get_player_mut(&mut self, player_id: u32) -> Option<&mut Player> {
+        self.players.iter_mut().find(|p| p.id == player_id)
+    }
+    
+    /// Finds a player by ID (used for getting read-only access).
+    pub fn get_player(&self, player_id: u32) -> Option<&Player> {
+        self.players.iter().find(|p| p.id == player_id)
+    }
+
+    /// Like `get_player`, but an unknown ID is an error, for frontends passing on IDs
+    /// they were given from outside.
+    pub fn player(&self, player_id: u32) -> Result<&Player, GameError> {
+        self.get_player(player_id).ok_or(GameError::UnknownPlayer(player_id))
+    }
+
+    /// Players still in the game, in seat order.
+    pub fn living_players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|p| p.hp > 0)
+    }
+
+    /// Records everyone who has dropped to 0 HP since the last check. Players knocked out
+    /// together share the best placement left (two of four falling at once both finish 3rd).
+    /// Any of them in Tokyo leave it, so their spot is open to the next monster to enter.
+    /// While the game goes on, each of them is asked whether to stay on as a spectator.
+    fn process_eliminations(&mut self) {
+        let newly_dead: Vec<u32> = self.players.iter()
+            .filter(|p| p.hp == 0 && !self.eliminated.iter().any(|&(id, _)| id == p.id))
+            .map(|p| p.id)
+            .collect();
+        if newly_dead.is_empty() {
+            return;
+        }
+
+        let placement = self.living_players().count() as u8 + 1;
+        for player_id in newly_dead {
+            self.eliminated.push((player_id, placement));
+            self.log(GameEvent::PlayerEliminated { player_id, placement, turn: self.turn });
+            if let Some(zone) = self.tokyo.vacate(player_id) {
+                self.log(GameEvent::TokyoVacated { player_id, zone });
+            }
+
+            if self.living_players().count() > 1 {
+                self.spectate_queue.push_back(player_id);
+            }
+        }
+
+        // Tokyo Bay only stays in play while 5 or more monsters are left
+        if self.tokyo.bay_open && self.living_players().count() < 5 {
+            let player_id = self.tokyo.close_bay();
+            self.log(GameEvent::TokyoBayClosed { player_id });
+        }
+    }
+
+    /// Effect hook for cards and abilities that change a monster's max HP (e.g. Even Bigger: +2).
+    pub fn adjust_max_hp(&mut self, player_id: u32, delta: i16) {
+        if let Some(player) = self.get_player_mut(player_id) {
+            player.adjust_max_hp(delta);
+            let event = GameEvent::MaxHpChanged { player_id, max_hp: player.max_hp, hp: player.hp };
+            self.log(event);
+        }
+    }
+
+    /// Effect hook for effects that change how many VP a monster needs to win.
+    pub fn adjust_max_vp(&mut self, player_id: u32, delta: i16) {
+        if let Some(player) = self.get_player_mut(player_id) {
+            player.adjust_max_vp(delta);
+            let event = GameEvent::MaxVpChanged { player_id, max_vp: player.max_vp };
+            self.log(event);
+        }
+    }
+
+    /// Advances the turn scheduler and returns whose turn it is, or None if nobody is left.
+    /// Eliminated players are dropped and pending skips are consumed here; once the queue runs
+    /// dry, the next round is queued in seat order.
+    fn next_turn(&mut self) -> Option<u32> {
+        loop {
+            if self.turn_queue.is_empty() {
+                self.turn_queue = self.living_players().map(|p| p.id).collect();
+                if self.turn_queue.is_empty() {
+                    return None;
+                }
+                self.log(GameEvent::RoundStarted);
+            }
+
+            let player_id = self.turn_queue.pop_front()?;
+            if self.get_player(player_id)?.hp == 0 {
+                continue;
+            }
+
+            if let Some(skips) = self.pending_skips.get_mut(&player_id).filter(|skips| **skips > 0) {
+                *skips -= 1;
+                self.log(GameEvent::TurnSkipped { player_id });
+                continue;
+            }
+
+            self.turn += 1;
+            if self.turn > self.rules.turn_limit {
+                self.enter_overtime(self.turn);
+            }
+            return Some(player_id);
+        }
+    }
+
+    /// Gives `player_id` a monster to play as. Only allowed before the first turn, and no
+    /// two players can be the same monster. Returns whether the monster was assigned.
+    pub fn choose_monster(&mut self, player_id: u32, monster: Monster) -> bool {
+        let taken = self.players.iter().any(|p| p.id != player_id && p.monster == Some(monster));
+        if self.turn > 0 || taken || self.designed_monster(player_id).is_some() {
+            return false;
+        }
+        let Some(player) = self.players.iter_mut().find(|p| p.id == player_id) else {
+            return false;
+        };
+        player.monster = Some(monster);
+        for expansion in self.expansions() {
+            expansion.on_monster_chosen(self, player_id, monster);
+        }
+        self.log(GameEvent::MonsterChosen { player_id, monster });
+        true
+    }
+
+    /// Gives `player_id` a custom monster in place of an official one, registering its
+    /// effects as `add_effect` would. Refuses designs with issues, players who already have
+    /// a monster, and names another player's design has. Like effects, designs aren't
+    /// saved, so add them again after loading a game.
+    pub fn add_monster(&mut self, player_id: u32, design: MonsterDesign) -> Result<(), ConfigError> {
+        let mut issues = design.issues();
+        let name = design.name.trim().to_string();
+        match self.get_player(player_id) {
+            None => issues.push(ConfigIssue::new("player", format!("there is no player {}", player_id), "use a seated player's id".to_string())),
+            Some(player) if player.monster.is_some() || self.designed_monster(player_id).is_some() => {
+                issues.push(ConfigIssue::new("player", format!("{} already has a monster", player.name), "give each player one monster".to_string()));
+            }
+            Some(_) => {}
+        }
+        if self.designs.iter().any(|(_, taken)| taken.eq_ignore_ascii_case(&name)) {
+            issues.push(ConfigIssue::new("name", format!("another player already plays {}", name), "give each design its own name".to_string()));
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError { source: format!("monster design \"{}\"", design.name), issues });
+        }
+        self.designs.push((player_id, name));
+        for effect in design.effects {
+            self.add_effect(player_id, effect);
+        }
+        Ok(())
+    }
+
+    /// The name of the custom monster `player_id` plays, if they were given one with `add_monster`.
+    pub fn designed_monster(&self, player_id: u32) -> Option<&str> {
+        self.designs.iter().find(|(owner, _)| *owner == player_id).map(|(_, name)| name.as_str())
+    }
+
+    /// Makes this a practice game, where whoever rolls may set the dice by hand (see
+    /// `Decision::SetDice`). Only allowed before the first turn. Practice games are unranked.
+    pub fn enable_practice(&mut self) -> bool {
+        if self.turn > 0 {
+            return false;
+        }
+        if !self.practice {
+            self.practice = true;
+            self.log(GameEvent::PracticeStarted);
+        }
+        true
+    }
+
+    /// Effect hook: `player_id` takes another turn straight after the current one.
+    pub fn grant_extra_turn(&mut self, player_id: u32) {
+        self.turn_queue.push_front(player_id);
+        self.log(GameEvent::ExtraTurnGranted { player_id });
+    }
+
+    /// Effect hook: `player_id` loses their next turn (stacks if applied more than once).
+    pub fn skip_next_turn(&mut self, player_id: u32) {
+        *self.pending_skips.entry(player_id).or_insert(0) += 1;
+        self.log(GameEvent::SkipScheduled { player_id });
+    }
+
+    /// Tokyo hold VP, doubled during overtime.
+    pub fn tokyo_hold_vp(&self) -> u8 {
+        if self.overtime {
+            self.rules.tokyo_hold_vp.saturating_mul(2)
+        } else {
+            self.rules.tokyo_hold_vp
+        }
+    }
+
+    /// Switches to sudden-death overtime once the turn limit passes with no winner.
+    fn enter_overtime(&mut self, turn: u32) {
+        if self.overtime {
+            return;
+        }
+        self.overtime = true;
+        let hold_vp = self.tokyo_hold_vp();
+        self.log(GameEvent::OvertimeStarted { turn, hold_vp });
+    }
+
+    /// Awards the hold VP (2 by default) to the active player if they start their turn in Tokyo.
+    fn apply_tokyo_control_points(&mut self, player_id: u32) {
+        let hold_vp = self.tokyo_hold_vp();
+
+        if let Some(zone) = self.tokyo.zone_of(player_id) {
+            if let Some(player) = self.get_player_mut(player_id) {
+                player.gain_vp(hold_vp, VpSource::HoldTokyo);
+                let total = player.victory_points;
+                self.log(GameEvent::TokyoHeld { player_id, zone, vp: hold_vp, total });
+            }
+        }
+    }
+
+    /// Deals `damage` from `attacker_id` to `target_id`, logging it in the damage ledger.
+    /// `card` names the card doing it, for card damage.
+    fn deal_damage(&mut self, attacker_id: u32, target_id: u32, damage: u8, source: DamageSource, card: Option<&str>) {
+        let card = card.map(str::to_string);
+        let mut hit = Hit { attacker_id, target_id, damage, source };
+        for effect in self.effects_of(target_id) {
+            hit.damage = effect.modify_damage(self, target_id, &hit);
+            if hit.damage == 0 && damage > 0 {
+                let blocked_by = effect.name().to_string();
+                self.log(GameEvent::DamageBlocked { player_id: target_id, blocked_by, attacker_id, damage, source, card });
+                return;
+            }
+        }
+        let damage = hit.damage;
+        if let Some(target) = self.get_player_mut(target_id) {
+            let hp_before = target.hp;
+            target.hp = target.hp.saturating_sub(damage);
+            let hp_after = target.hp;
+            self.log(GameEvent::DamageDealt { attacker_id, target_id, damage, hp: hp_after, source, card });
+            self.damage_log.push(DamageRecord {
+                attacker_id,
+                target_id,
+                amount: hp_before - hp_after,
+                source,
+                knockout: hp_before > 0 && hp_after == 0,
+            });
+        }
+    }
+
+    /// Moves `player_id` into `zone` and awards the entry VP (1 by default).
+    fn enter_tokyo(&mut self, player_id: u32, zone: TokyoZone) {
+        let entry_vp = self.rules.tokyo_entry_vp;
+        self.tokyo.occupy(zone, player_id);
+        if let Some(player) = self.get_player_mut(player_id) {
+            player.gain_vp(entry_vp, VpSource::EnterTokyo);
+            let total = player.victory_points;
+            self.log(GameEvent::TokyoEntered { player_id, zone, vp: entry_vp, total });
+        }
+        self.run_effects(player_id, |effect, game| effect.on_enter_tokyo(game, player_id, zone));
+    }
+
+    /// Rolls all six dice to open the roll phase. The player then gets `rules.rerolls`
+    /// rerolls (plus one for Giant Brain), each one asked as a `Decision::KeepDice`.
+    fn start_roll(&mut self, player_id: u32) -> Step {
+        self.begin_phase(player_id, TurnPhase::Roll);
+        let extra: u8 = self.effects_of(player_id).iter().map(|effect| effect.extra_rerolls(self, player_id)).sum();
+        let rerolls = self.rules.rerolls.saturating_add(extra);
+        let practice = self.practice;
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        if practice {
+            return Step::SetDice { player_id, dice: [DieResult::One; 6], keep: [false; 6], roll: 1, rerolls };
+        }
+        let dice = roll_dice(&mut player.rng);
+        self.rolled(player_id, dice, [false; 6], 1, rerolls)
+    }
+
+    /// Moves on from roll number `roll`: to the next keep decision, or to resolving the
+    /// dice once the last reroll is used.
+    fn rolled(&mut self, player_id: u32, mut dice: [DieResult; 6], kept: [bool; 6], roll: u8, rerolls: u8) -> Step {
+        self.change_dice(player_id, &mut dice, &kept, roll, rerolls + 1);
+        if roll > rerolls {
+            return self.resolve(player_id, dice, kept);
+        }
+        self.log(GameEvent::DiceRolled { player_id, roll, rolls: rerolls + 1, dice, kept });
+        Step::KeepDice { player_id, dice, reroll: roll, rerolls }
+    }
+
+    /// Asks every living monster's effects, the roller's first, whether to change the dice
+    /// `player_id` just rolled, and applies the changes to the dice not in `kept`.
+    fn change_dice(&mut self, player_id: u32, dice: &mut [DieResult; 6], kept: &[bool; 6], roll: u8, rolls: u8) {
+        let others = self.players.iter().filter(|p| p.hp > 0 && p.id != player_id).map(|p| p.id);
+        let owners: Vec<u32> = std::iter::once(player_id).chain(others).collect();
+        for owner in owners {
+            let mut i = 0;
+            while let Some(effect) = self.effects_of(owner).get(i).copied() {
+                let name = effect.name().to_string();
+                let changes = effect.on_dice_rolled(self, owner, &Roll { roller: player_id, dice: *dice, kept: *kept, roll, rolls });
+                for change in changes {
+                    let (die, rerolled) = match change {
+                        DiceChange::Reroll(die) | DiceChange::Set(die, _) if die >= 6 || kept[die] => continue,
+                        DiceChange::Reroll(die) => {
+                            let player = self.get_player_mut(player_id).expect("Player must exist");
+                            dice[die] = roll_die(&mut player.rng);
+                            (die, true)
+                        }
+                        DiceChange::Set(die, face) if dice[die] == face => continue,
+                        DiceChange::Set(die, face) => {
+                            dice[die] = face;
+                            (die, false)
+                        }
+                    };
+                    self.log(GameEvent::DiceChanged { player_id, by: owner, effect: name.clone(), die, face: dice[die], rerolled });
+                }
+                i += 1;
+            }
+        }
+    }
+
+    /// Sets the dice not in `keep` to `forced` (or rolls them if None) in a practice game.
+    fn set_dice(&mut self, player_id: u32, mut dice: [DieResult; 6], keep: [bool; 6], forced: Option<[DieResult; 6]>, roll: u8, rerolls: u8) -> Step {
+        match forced {
+            Some(forced) => {
+                for ((die, &kept), face) in dice.iter_mut().zip(&keep).zip(forced) {
+                    if !kept {
+                        *die = face;
+                    }
+                }
+            }
+            None => {
+                let player = self.get_player_mut(player_id).expect("Player must exist");
+                reroll_dice(&mut player.rng, &mut dice, &keep);
+            }
+        }
+        self.rolled(player_id, dice, keep, roll, rerolls)
+    }
+
+    /// Rerolls the dice not in `keep`. Rolling stops once every die is kept or the last
+    /// reroll is used, and the dice are resolved.
+    fn keep_dice(&mut self, player_id: u32, mut dice: [DieResult; 6], keep: [bool; 6], reroll: u8, rerolls: u8) -> Step {
+        if keep == [true; 6] {
+            return self.resolve(player_id, dice, keep);
+        }
+        if self.practice {
+            return Step::SetDice { player_id, dice, keep, roll: reroll + 1, rerolls };
+        }
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        reroll_dice(&mut player.rng, &mut dice, &keep);
+        self.rolled(player_id, dice, keep, reroll + 1, rerolls)
+    }
+
+    /// Scores the final dice, heals and attacks. Stops at the first Tokyo decision the
+    /// attack leads to, or goes straight on to the buy phase if there is none.
+    fn resolve(&mut self, player_id: u32, results: [DieResult; 6], kept: [bool; 6]) -> Step {
+        self.begin_phase(player_id, TurnPhase::Resolve);
+        let mut matched_numbers = 0;
+        let player_is_in_tokyo = self.tokyo.contains(player_id);
+
+        self.log(GameEvent::DiceResolved { player_id, dice: results, kept });
+
+        // Tally results
+        let counts = DieResult::tally(&results);
+
+        // --- 1. Scoring: Matched Numbers (3 of a kind) ---
+        if counts[DieResult::One as usize] >= 3 { matched_numbers += 1; }
+        if counts[DieResult::Two as usize] >= 3 { matched_numbers += 2; }
+        if counts[DieResult::Three as usize] >= 3 { matched_numbers += 3; }
+
+        if matched_numbers > 0 {
+            if let Some(player) = self.get_player_mut(player_id) {
+                player.gain_vp(matched_numbers as u8, VpSource::Triples);
+                let total = player.victory_points;
+                self.log(GameEvent::TriplesScored { player_id, vp: matched_numbers as u8, total });
+            }
+            if let Some(step) = self.immediate_victory() {
+                return step;
+            }
+        }
+
+        // --- 2. Energy, Hearts, and Claws ---
+        let energy_count = counts[DieResult::Energy as usize];
+        let economy = self.rules.economy;
+        if energy_count > 0 && self.rules.energy {
+            let amount = self.effects_of(player_id).iter()
+                .fold(energy_count.saturating_mul(economy.energy_per_die), |amount, effect| effect.modify_energy(self, player_id, &results, amount));
+            if let Some(player) = self.get_player_mut(player_id) {
+                player.energy = player.energy.saturating_add(amount);
+                let total = player.energy;
+                self.log(GameEvent::EnergyGained { player_id, amount, total });
+            }
+        }
+
+        for expansion in self.expansions() {
+            expansion.on_dice_counted(self, player_id, &counts);
+        }
+        let heart_count = counts[DieResult::Heart as usize];
+        if heart_count > 0 {
+            if self.overtime {
+                self.log(GameEvent::HeartsIgnored { player_id, overtime: true });
+            } else if !player_is_in_tokyo || self.rules.heal_in_tokyo {
+                let amount = self.effects_of(player_id).iter()
+                    .fold(heart_count, |amount, effect| effect.modify_healing(self, player_id, &results, amount));
+                if let Some(player) = self.get_player_mut(player_id) {
+                    player.heal(amount);
+                    let hp = player.hp;
+                    self.log(GameEvent::Healed { player_id, amount, hp });
+                }
+            } else {
+                self.log(GameEvent::HeartsIgnored { player_id, overtime: false });
+            }
+        }
+        
+        self.run_effects(player_id, |effect, game| effect.on_roll_resolved(game, player_id, &results));
+        if let Some(step) = self.immediate_victory() {
+            return step;
+        }
+        let claw_count = counts[DieResult::Claw as usize];
+
+        // --- 3. Attack and Tokyo Control ---
+        if claw_count == 0 {
+            return self.after_resolve(player_id);
+        }
+        let damage = self.effects_of(player_id).iter()
+            .fold(claw_count, |damage, effect| effect.modify_attack(self, player_id, damage));
+
+        if let Some(own_zone) = self.tokyo.zone_of(player_id) {
+            // ATTACK FROM TOKYO: Damage to everyone outside both zones
+            let targets: Vec<u32> = self.living_players()
+                .filter(|p| !self.tokyo.contains(p.id))
+                .map(|p| p.id)
+                .collect();
+            self.log(GameEvent::AttackLaunched { player_id, damage, from_tokyo: true, targets: targets.clone() });
+            for target_id in targets {
+                self.deal_damage(player_id, target_id, damage, DamageSource::ClawsFromTokyo, None);
+            }
+            if self.rules.immediate_victory {
+                self.process_eliminations();
+                if let Some(step) = self.immediate_victory() {
+                    return step;
+                }
+            }
+
+            // DECISION: Concede Tokyo after attacking
+            return Step::ConcedeTokyo { player_id, zone: own_zone };
+        }
+
+        // ATTACK INTO TOKYO: Every occupant takes the damage, then each one it hurt may yield
+        let mut occupants = self.tokyo.occupants();
+        if !occupants.is_empty() {
+            let targets = occupants.iter().map(|&(_, occupant_id)| occupant_id).collect();
+            self.log(GameEvent::AttackLaunched { player_id, damage, from_tokyo: false, targets });
+            let mark = self.damage_log.len();
+            for &(_, occupant_id) in &occupants {
+                self.deal_damage(player_id, occupant_id, damage, DamageSource::ClawsIntoTokyo, None);
+            }
+            if !self.rules.yield_unhurt {
+                let hits = &self.damage_log[mark..];
+                occupants.retain(|&(_, occupant_id)| hits.iter().any(|hit| hit.target_id == occupant_id && hit.amount > 0));
+            }
+        }
+        self.offer_yields(player_id, occupants)
+    }
+
+    /// Asks the first of `occupants` still in Tokyo whether they yield to `attacker_id`.
+    /// Once nobody is left to ask, the attacker enters any vacant zone (or, under the
+    /// `optional_entry` house rule, is asked whether to).
+    fn offer_yields(&mut self, attacker_id: u32, occupants: Vec<(TokyoZone, u32)>) -> Step {
+        // A knocked-out occupant has no decision to make: they're out, so their spot is open
+        self.process_eliminations();
+        if let Some(step) = self.immediate_victory() {
+            return step;
+        }
+        let mut remaining = occupants.into_iter().filter(|&(_, occupant_id)| self.tokyo.contains(occupant_id));
+        if let Some((zone, occupant_id)) = remaining.next() {
+            return Step::YieldTokyo { attacker_id, zone, occupant_id, next: remaining.next() };
+        }
+
+        // ENTER TOKYO: City fills before Bay
+        match self.tokyo.first_open_zone() {
+            Some(zone) if self.rules.optional_entry => Step::EnterTokyo { player_id: attacker_id, zone },
+            Some(zone) => {
+                self.enter_tokyo(attacker_id, zone);
+                self.after_resolve(attacker_id)
+            }
+            None => self.after_resolve(attacker_id),
+        }
+    }
+
+    /// Settles eliminations from the attack and opens the buy phase.
+    fn after_resolve(&mut self, player_id: u32) -> Step {
+        self.process_eliminations();
+        if let Some(step) = self.immediate_victory() {
+            return step;
+        }
+        self.begin_phase(player_id, TurnPhase::Buy);
+        self.open_shop(player_id)
+    }
+
+    /// Energy it costs to sweep the shop under this game's economy.
+    pub fn sweep_cost(&self) -> u8 {
+        self.rules.economy.sweep_cost
+    }
+
+    /// The Energy actions on offer this game, from the house rules that are on: Energy
+    /// Exchange, then Energy Heal.
+    pub fn pseudo_cards(&self) -> Vec<PseudoCard> {
+        let exchange = self.rules.energy_per_vp.map(PseudoCard::energy_exchange);
+        let heal = self.rules.energy_per_heal.map(PseudoCard::energy_heal);
+        exchange.into_iter().chain(heal).collect()
+    }
+
+    /// Whether `player_id` may use `card` right now: Energy Heal is only for monsters
+    /// outside Tokyo, and nothing heals in overtime.
+    pub fn can_use_pseudo_card(&self, player_id: u32, card: &PseudoCard) -> bool {
+        let heals = matches!(card.effect, CardEffect::Heal(_));
+        let in_tokyo = self.tokyo.contains(player_id);
+        !(card.outside_tokyo && in_tokyo || heals && self.overtime)
+    }
+
+    /// Asks the active player for their next shop move, if they can pay for anything or
+    /// have an evolution to play. Power cards come first, then the pseudo-cards.
+    fn open_shop(&mut self, player_id: u32) -> Step {
+        if let Some(step) = self.immediate_victory() {
+            return step;
+        }
+        let (energy, can_evolve) = match self.get_player(player_id) {
+            Some(player) if player.hp > 0 && (self.rules.energy || !player.evolutions.is_empty()) => {
+                (player.energy, !player.evolutions.is_empty())
+            }
+            _ => return self.end_turn(player_id),
+        };
+        let can_buy = self.rules.energy && self.shop.face_up().iter().any(|card| self.card_cost(player_id, card).affordable(energy));
+        let can_sweep = self.rules.energy && energy >= self.sweep_cost();
+        if !can_buy && !can_evolve && !can_sweep {
+            return self.offer_pseudo_cards(player_id, 0);
+        }
+        Step::Shop { player_id }
+    }
+
+    /// Carries out a shop move. Anything that can't be paid for ends shopping.
+    fn shop_action(&mut self, player_id: u32, action: ShopAction) -> Step {
+        let energy = self.get_player(player_id).map_or(0, |p| p.energy);
+        match action {
+            ShopAction::Buy(slot) if self.rules.energy && self.shop.face_up().get(slot).is_some_and(|card| self.card_cost(player_id, card).affordable(energy)) => {
+                self.buy_card(player_id, slot);
+                self.open_shop(player_id)
+            }
+            ShopAction::Sweep if self.rules.energy && energy >= self.sweep_cost() => {
+                let cost = self.sweep_cost();
+                let player = self.get_player_mut(player_id).expect("Player must exist");
+                player.energy -= cost;
+                let energy = player.energy;
+                self.log(GameEvent::ShopSwept { player_id, energy });
+                for card in self.shop.sweep() {
+                    self.log(GameEvent::CardRevealed { card: card.name.to_string() });
+                }
+                self.open_shop(player_id)
+            }
+            ShopAction::Evolve(index) if self.get_player(player_id).is_some_and(|p| index < p.evolutions.len()) => {
+                self.play_evolution(player_id, index);
+                self.open_shop(player_id)
+            }
+            _ => self.offer_pseudo_cards(player_id, 0),
+        }
+    }
+
+    /// Plays evolution `index` from the player's hand. Permanent ones stay in play; the
+    /// rest are discarded once their effects are applied.
+    fn play_evolution(&mut self, player_id: u32, index: usize) {
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        let evolution = player.evolutions.remove(index);
+        let permanent = evolution.kind == EvolutionKind::Permanent;
+        if permanent {
+            player.evolved.push(evolution);
+        }
+        self.log(GameEvent::EvolutionPlayed { player_id, evolution: evolution.name.to_string(), permanent });
+        for &effect in evolution.effects {
+            self.apply_card_effect(player_id, evolution.name, effect);
+        }
+    }
+
+    /// Offers the pseudo-cards from index `from` on, skipping any the player can't afford
+    /// or use.
+    fn offer_pseudo_cards(&mut self, player_id: u32, from: usize) -> Step {
+        if let Some(step) = self.immediate_victory() {
+            return step;
+        }
+        for (index, card) in self.pseudo_cards().into_iter().enumerate().skip(from) {
+            let energy = match self.get_player(player_id) {
+                Some(player) if player.hp > 0 => player.energy,
+                _ => break,
+            };
+            if !self.can_use_pseudo_card(player_id, &card) {
+                continue;
+            }
+            let affordable = energy / card.cost;
+            if affordable > 0 {
+                return Step::Purchase { player_id, card: index, affordable };
+            }
+        }
+        self.end_turn(player_id)
+    }
+
+    /// Buys pseudo-card `index` up to `times` times, then applies its effect that many
+    /// times over.
+    fn buy_pseudo_card(&mut self, player_id: u32, index: usize, times: u8) {
+        let Some(card) = self.pseudo_cards().get(index).copied() else {
+            return;
+        };
+        if !self.can_use_pseudo_card(player_id, &card) {
+            return;
+        }
+        let Some(player) = self.get_player_mut(player_id) else {
+            return;
+        };
+        let times = times.min(player.energy / card.cost);
+        if times == 0 {
+            return;
+        }
+        let spent = times * card.cost;
+        player.energy -= spent;
+        let energy = player.energy;
+        self.log(GameEvent::PseudoCardBought { player_id, card: card.name.to_string(), times, spent, energy });
+        self.apply_card_effect(player_id, card.name, card.effect.times(times));
+    }
+
+    /// Pays for the card in shop `slot`, deals its replacement and applies its effects.
+    /// Keep cards stay with the buyer; the rest go to the discard pile.
+    fn buy_card(&mut self, player_id: u32, slot: usize) {
+        let Some((card, revealed)) = self.shop.take(slot) else {
+            return;
+        };
+        let price = self.card_cost(player_id, &card);
+        let cost = price.energy();
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        player.energy -= cost;
+        let energy = player.energy;
+        self.log(GameEvent::CardPurchased { player_id, card: card.name.to_string(), cost, energy, price: price.clone() });
+        for new_card in revealed {
+            self.log(GameEvent::CardRevealed { card: new_card.name.to_string() });
+        }
+        if price.refund() > 0 {
+            self.apply_card_effect(player_id, card.name, CardEffect::GainEnergy(price.refund()));
+        }
+
+        for &effect in card.effects {
+            self.apply_card_effect(player_id, card.name, effect);
+        }
+        match card.kind {
+            CardKind::Keep => self.get_player_mut(player_id).expect("Player must exist").cards.push(card),
+            CardKind::Discard => self.shop.discard(card),
+        }
+        self.process_eliminations();
+    }
+
+    fn apply_card_effect(&mut self, player_id: u32, card: &str, effect: CardEffect) {
+        match effect {
+            CardEffect::GainVp(amount) => self.gain_card_vp(player_id, amount),
+            CardEffect::GainEnergy(amount) => {
+                let player = self.get_player_mut(player_id).expect("Player must exist");
+                player.energy = player.energy.saturating_add(amount);
+                let total = player.energy;
+                self.log(GameEvent::EnergyGained { player_id, amount, total });
+            }
+            CardEffect::Heal(amount) => self.heal_from_card(player_id, amount),
+            CardEffect::DamageOthers(damage) => {
+                let targets: Vec<u32> = self.living_players()
+                    .filter(|p| p.id != player_id)
+                    .map(|p| p.id)
+                    .collect();
+                for target_id in targets {
+                    self.deal_damage(player_id, target_id, damage, DamageSource::Cards, Some(card));
+                }
+                // Evolutions and ability hooks deal this outside an attack, so settle it here
+                // rather than leave a knocked-out monster holding Tokyo until the next attack
+                self.process_eliminations();
+            }
+            CardEffect::RaiseMaxHp(amount) => {
+                self.adjust_max_hp(player_id, amount as i16);
+                self.heal_from_card(player_id, amount);
+            }
+            CardEffect::ExtraTurn => self.grant_extra_turn(player_id),
+        }
+    }
+
+    fn gain_card_vp(&mut self, player_id: u32, amount: u8) {
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        let amount = player.gain_vp(amount, VpSource::Cards);
+        let total = player.victory_points;
+        self.log(GameEvent::VpGained { player_id, amount, source: VpSource::Cards, total });
+    }
+
+    /// Card healing works in Tokyo, but overtime's no-healing rule still applies.
+    fn heal_from_card(&mut self, player_id: u32, amount: u8) {
+        if self.overtime {
+            return;
+        }
+        let player = self.get_player_mut(player_id).expect("Player must exist");
+        let amount = player.heal(amount);
+        let hp = player.hp;
+        self.log(GameEvent::Healed { player_id, amount, hp });
+    }
+
+    /// Abilities that trigger once the buy phase is over. `damage_mark` is the length of
+    /// the damage ledger when the turn began.
+    fn end_of_turn(&mut self, player_id: u32, damage_mark: usize) {
+        let dealt_damage = self.damage_log[damage_mark..].iter().any(|r| r.attacker_id == player_id && r.amount > 0);
+        if self.get_player(player_id).is_none_or(|player| player.hp == 0) {
+            return;
+        }
+        self.run_effects(player_id, |effect, game| effect.on_turn_end(game, player_id, dealt_damage));
+    }
+
+    /// Orders two monsters by `rules.tie_break`: Less when `a` ranks first.
+    pub fn tie_break(&self, a: &Player, b: &Player) -> std::cmp::Ordering {
+        TieBreak::compare(&self.rules.tie_break, &a.tie_keys(), &b.tie_keys())
+    }
+
+    /// Finishing placements for every player as (player ID, placement), best first.
+    /// Eliminated players keep the placement they were given when they fell. Survivors
+    /// take the places above them: anyone who reached their VP target first, then as
+    /// `rules.tie_break` orders them. Survivors it can't separate share a placement.
+    pub fn final_placements(&self) -> Vec<(u32, u8)> {
+        let mut survivors: Vec<&Player> = self.living_players().collect();
+        let rank = |a: &Player, b: &Player| (b.victory_points >= b.max_vp).cmp(&(a.victory_points >= a.max_vp)).then_with(|| self.tie_break(a, b));
+        survivors.sort_by(|a, b| rank(a, b));
+
+        let mut placements: Vec<(u32, u8)> = Vec::new();
+        for (i, player) in survivors.iter().enumerate() {
+            let placement = match i.checked_sub(1).map(|prev| survivors[prev]) {
+                Some(prev) if rank(prev, player).is_eq() => placements[i - 1].1,
+                _ => i as u8 + 1,
+            };
+            placements.push((player.id, placement));
+        }
+
+        let mut eliminated = self.eliminated.clone();
+        eliminated.sort_by_key(|&(_, placement)| placement);
+        placements.extend(eliminated);
+        placements
+    }
+
+    /// Checks if the game has ended based on VP or HP conditions.
+    pub fn check_victory_condition(&self) -> Option<Victory> {
+        let active_players: Vec<&Player> = self.living_players().collect();
+
+        // VP WIN: monsters reaching their target together are split by the tie-break policy
+        let reached = active_players.iter().filter(|p| p.victory_points >= p.max_vp);
+        if let Some(winner) = reached.min_by(|a, b| self.tie_break(a, b)) {
+            return Some(Victory::Points { player_id: winner.id, max_vp: winner.max_vp });
+        }
+
+        // HP WIN (Last Kaiju Standing)
+        if active_players.len() <= 1 {
+            return Some(match active_players.first() {
+                Some(winner) => Victory::LastStanding { player_id: winner.id },
+                // All players eliminated simultaneously
+                None => Victory::NoSurvivors,
+            });
+        }
+
+        None
+    }
+
+    /// The result of the game, once it has one.
+    pub fn victory(&self) -> Option<Victory> {
+        self.history.iter().rev().find_map(|e| match e.event {
+            GameEvent::GameOver { victory } => Some(victory),
+            _ => None,
+        })
+    }
+
+    /// The decision the game is waiting on, if any. Players just eliminated are asked
+    /// whether to spectate before anything else.
+    pub fn pending(&self) -> Option<Decision> {
+        if self.step != Step::GameOver {
+            if let Some(&player_id) = self.spectate_queue.front() {
+                return Some(Decision::Spectate { player_id });
+            }
+        }
+        match self.step {
+            Step::SetDice { player_id, dice, keep, roll, .. } => Some(Decision::SetDice { player_id, dice, keep, roll }),
+            Step::KeepDice { player_id, dice, reroll, .. } => Some(Decision::KeepDice { player_id, dice, reroll }),
+            Step::ConcedeTokyo { player_id, zone } => Some(Decision::ConcedeTokyo { player_id, zone }),
+            Step::YieldTokyo { attacker_id, zone, occupant_id, .. } => {
+                Some(Decision::YieldTokyo { player_id: occupant_id, zone, attacker_id })
+            }
+            Step::EnterTokyo { player_id, zone } => Some(Decision::EnterTokyo { player_id, zone }),
+            Step::Shop { player_id } => Some(Decision::Shop { player_id }),
+            Step::Purchase { player_id, card, affordable } => Some(Decision::Purchase { player_id, card, affordable }),
+            Step::BeginTurn | Step::GameOver => None,
+        }
+    }
+
+    /// Steps the game forward. Pass the answer to the pending decision, or `None` between
+    /// turns to start the next one. The game then runs on by itself until it needs another
+    /// decision, the turn ends or the game is over, and reports which.
+    pub fn advance(&mut self, action: Option<Action>) -> Result<Progress, AdvanceError> {
+        match (self.pending(), action) {
+            (Some(decision), None) => return Err(AdvanceError::Missing(decision)),
+            (None, Some(action)) => return Err(AdvanceError::Unexpected(action)),
+            (Some(decision), Some(action)) => {
+                let before = (self.rules.undo_limit > 0).then(|| serde_json::to_string(self).expect("Games are always serializable"));
+                self.apply(decision, action)?;
+                if let Some(actions) = self.actions.as_mut() {
+                    actions.push(action);
+                }
+                if let Some(before) = before {
+                    if self.undo_stack.len() >= self.rules.undo_limit as usize {
+                        self.undo_stack.remove(0);
+                    }
+                    self.undo_stack.push((decision, action, before));
+                }
+                self.redo_stack.clear();
+            }
+            (None, None) => {
+                if self.step == Step::BeginTurn {
+                    self.step = self.begin_turn();
+                }
+            }
+        }
+        self.debug_assert_invariants();
+        Ok(match (self.pending(), self.victory()) {
+            (Some(decision), _) => Progress::Decide(decision),
+            (None, Some(victory)) => Progress::GameOver(victory),
+            (None, None) => Progress::TurnEnded,
+        })
+    }
+
+    /// Takes back the answer to the most recent decision, restoring the game to just before
+    /// it, and returns that decision, which is pending again. Only the last
+    /// `rules.undo_limit` decisions can be taken back; with the house rule off there are
+    /// none. Phase hooks and subscribers stay attached but aren't told about the rewind.
+    pub fn undo(&mut self) -> Result<Decision, GameError> {
+        let (decision, action, before) = self.undo_stack.pop().ok_or(GameError::NothingToUndo)?;
+        let mut restored: Game = serde_json::from_str(&before).expect("Undo snapshots are always readable");
+        restored.clock = std::mem::replace(&mut self.clock, Box::new(StoppedClock));
+        restored.clock_mark = self.clock_mark;
+        restored.elapsed_before = self.elapsed_before;
+        restored.phase_hooks = std::mem::take(&mut self.phase_hooks);
+        restored.subscribers = std::mem::take(&mut self.subscribers);
+        restored.effects = std::mem::take(&mut self.effects);
+        restored.designs = std::mem::take(&mut self.designs);
+        restored.health = std::mem::take(&mut self.health);
+        restored.current_phase = self.current_phase.take();
+        restored.undo_stack = std::mem::take(&mut self.undo_stack);
+        restored.redo_stack = std::mem::take(&mut self.redo_stack);
+        restored.redo_stack.push(action);
+        *self = restored;
+        Ok(decision)
+    }
+
+    /// Answers the pending decision again the way it was answered before the last `undo`.
+    /// Further undone answers stay available until a different answer is given.
+    pub fn redo(&mut self) -> Result<Progress, GameError> {
+        let action = self.redo_stack.pop().ok_or(GameError::NothingToRedo)?;
+        let rest = std::mem::take(&mut self.redo_stack);
+        let progress = self.advance(Some(action));
+        self.redo_stack = rest;
+        if progress.is_err() {
+            self.redo_stack.push(action);
+        }
+        Ok(progress?)
+    }
+
+    /// Answers `decision` (the pending one) with `action`.
+    fn apply(&mut self, decision: Decision, action: Action) -> Result<(), AdvanceError> {
+        let mismatch = AdvanceError::Mismatch { expected: decision, action };
+        if let Decision::Spectate { player_id } = decision {
+            let Action::Spectate(stay) = action else {
+                return Err(mismatch);
+            };
+            self.spectate_queue.pop_front();
+            if stay {
+                self.spectators.push(player_id);
+            }
+            return Ok(());
+        }
+
+        self.step = match (self.step, action) {
+            (Step::SetDice { player_id, dice, keep, roll, rerolls }, Action::SetDice(forced)) => {
+                self.set_dice(player_id, dice, keep, forced, roll, rerolls)
+            }
+            (Step::KeepDice { player_id, dice, reroll, rerolls }, Action::Keep(keep)) => {
+                self.keep_dice(player_id, dice, keep, reroll, rerolls)
+            }
+            (Step::ConcedeTokyo { player_id, zone }, Action::Concede(concede)) => {
+                if concede {
+                    self.tokyo.vacate(player_id);
+                    self.log(GameEvent::TokyoConceded { player_id, zone });
+                }
+                self.after_resolve(player_id)
+            }
+            (Step::YieldTokyo { attacker_id, zone, occupant_id, next }, Action::Yield(yield_zone)) => {
+                if yield_zone {
+                    self.tokyo.vacate(occupant_id);
+                    self.log(GameEvent::TokyoYielded { player_id: occupant_id, zone, attacker_id });
+                } else {
+                    self.log(GameEvent::TokyoDefended { player_id: occupant_id, zone, attacker_id });
+                }
+                self.offer_yields(attacker_id, next.into_iter().collect())
+            }
+            (Step::EnterTokyo { player_id, zone }, Action::Enter(enter)) => {
+                if enter {
+                    self.enter_tokyo(player_id, zone);
+                } else {
+                    self.log(GameEvent::TokyoEntryDeclined { player_id });
+                }
+                self.after_resolve(player_id)
+            }
+            (Step::Shop { player_id }, Action::Shop(shop_action)) => self.shop_action(player_id, shop_action),
+            (Step::Purchase { player_id, card, affordable }, Action::Purchase(times)) => {
+                self.buy_pseudo_card(player_id, card, times.min(affordable));
+                self.offer_pseudo_cards(player_id, card + 1)
+            }
+            _ => return Err(mismatch),
+        };
+        Ok(())
+    }
+
+    /// Starts the next scheduled turn: upkeep, then the first roll.
+    fn begin_turn(&mut self) -> Step {
+        if self.victory().is_some() {
+            return Step::GameOver;
+        }
+        let Some(player_id) = self.next_turn() else {
+            return self.finish();
+        };
+        self.turn_damage_mark = self.damage_log.len();
+        let player = self.get_player(player_id).expect("Scheduled player must exist");
+        let (hp, victory_points) = (player.hp, player.victory_points);
+        self.log(GameEvent::TurnStarted { turn: self.turn, player_id, hp, victory_points });
+
+        // 1. Check for passive Tokyo VP
+        self.begin_phase(player_id, TurnPhase::StartTurn);
+        self.apply_tokyo_control_points(player_id);
+        self.run_effects(player_id, |effect, game| effect.on_turn_start(game, player_id));
+
+        // 2. Check for victory after Tokyo VP
+        if let Some(step) = self.game_over() {
+            return step;
+        }
+
+        // 3. Roll Dice, then resolve them and shop as the decisions come in
+        self.start_roll(player_id)
+    }
+
+    /// Wraps up the turn once the buy phase is over.
+    fn end_turn(&mut self, player_id: u32) -> Step {
+        self.end_phase();
+        self.end_of_turn(player_id, self.turn_damage_mark);
+
+        // Check for victory after roll effects and purchases
+        if let Some(step) = self.game_over() {
+            return step;
+        }
+        self.record_timeline();
+        Step::BeginTurn
+    }
+
+    /// Ends the game if anyone has won, returning the step it stops at.
+    fn game_over(&mut self) -> Option<Step> {
+        self.check_victory_condition()?;
+        self.record_timeline();
+        Some(self.finish())
+    }
+
+    /// Under `rules.immediate_victory`, ends the game if the VP just scored or the monsters
+    /// just knocked out have decided it. Resolution stops there: nothing after the winning
+    /// moment happens. Called after every step that can score VP or eliminate a monster.
+    fn immediate_victory(&mut self) -> Option<Step> {
+        if !self.rules.immediate_victory {
+            return None;
+        }
+        self.game_over()
+    }
+
+    /// Gives the rest of the game new luck from `seed`: fresh dice streams for every seat,
+    /// and the unseen power cards and evolution decks reshuffled. Everything that has
+    /// happened stays as it was, and so does the game's recorded seed, so a reseeded game no
+    /// longer replays from its history.
+    pub fn reseed(&mut self, seed: u64) {
+        self.shop.reshuffle(derive_stream_seed(seed, 0));
+        for player in &mut self.players {
+            player.rng = ChaCha12Rng::seed_from_u64(derive_stream_seed(seed, player.id as u64));
+            let stream = Game::EVOLUTION_STREAMS + player.id as u64;
+            player.evolution_deck.shuffle(&mut ChaCha12Rng::seed_from_u64(derive_stream_seed(seed, stream)));
+        }
+    }
+
+    /// A copy for AI search to play ahead in: the same position with fresh luck from `seed`
+    /// (see `reseed`), so a search can't see the real game's dice or deck. The copy keeps no
+    /// names, event log, timeline, action record or undo history, reads no clock and plays
+    /// only the built-in abilities (effects from `add_effect` can't be copied), so cloning it
+    /// again and playing it out allocates next to nothing. Only its final `GameOver` event
+    /// is logged, for `victory`.
+    pub fn clone_for_search(&self, seed: u64) -> Game {
+        let mut players = self.players.clone();
+        for player in &mut players {
+            player.name.clear();
+        }
+        let mut game = Game {
+            seed: self.seed,
+            players,
+            tokyo: self.tokyo.clone(),
+            shop: self.shop.clone(),
+            start_hp: self.start_hp,
+            max_hp: self.max_hp,
+            max_vp: self.max_vp,
+            preset: String::new(),
+            rules: RuleSet { undo_limit: 0, ..self.rules.clone() },
+            privacy: self.privacy,
+            overtime: self.overtime,
+            turn: self.turn,
+            practice: self.practice,
+            turn_queue: self.turn_queue.clone(),
+            pending_skips: self.pending_skips.clone(),
+            eliminated: self.eliminated.clone(),
+            spectators: self.spectators.clone(),
+            damage_log: self.damage_log.clone(),
+            history: self.history.iter().filter(|timed| matches!(timed.event, GameEvent::GameOver { .. })).cloned().collect(),
+            timeline: Vec::new(),
+            step: self.step,
+            spectate_queue: self.spectate_queue.clone(),
+            turn_damage_mark: self.turn_damage_mark,
+            actions: None,
+            clock: Box::new(StoppedClock),
+            clock_mark: Duration::ZERO,
+            elapsed_before: Duration::ZERO,
+            phase_hooks: Vec::new(),
+            subscribers: Vec::new(),
+            effects: Vec::new(),
+            designs: Vec::new(),
+            health: HealthRegistry::new(),
+            current_phase: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            search: true,
+        };
+        game.reseed(seed);
+        game
+    }
+
+    /// Answers the pending decision with `action` and starts turns until someone has to
+    /// decide again, so the game is left waiting on its next decision or over (never
+    /// `TurnEnded`). The fast path for search and bots: once a turn has started (see
+    /// `advance`), `while let Some(decision) = game.pending() { game.apply_action(...)?; }`
+    /// plays the game out.
+    pub fn apply_action(&mut self, action: Action) -> Result<Progress, AdvanceError> {
+        let mut progress = self.advance(Some(action))?;
+        while progress == Progress::TurnEnded {
+            progress = self.advance(None)?;
+        }
+        Ok(progress)
+    }
+
+    /// Plays the next scheduled turn from upkeep to the buy phase (or finishes the one in
+    /// progress), asking `controller` for every decision along the way. Returns the result
+    /// once the game is over; calling it again after that does nothing.
+    pub fn play_turn(&mut self, controller: &mut dyn PlayerController) -> Option<Victory> {
+        loop {
+            let action = self.pending().map(|decision| decision.ask(controller.agent_for(decision.player_id()), self));
+            match self.advance(action).expect("The pending decision was just answered") {
+                Progress::Decide(_) => continue,
+                Progress::TurnEnded => return None,
+                Progress::GameOver(victory) => return Some(victory),
+            }
+        }
+    }
+
+    /// Closes the open phase and records how the game ended.
+    fn finish(&mut self) -> Step {
+        self.end_phase();
+        match self.check_victory_condition() {
+            Some(victory) => {
+                self.log(GameEvent::GameOver { victory });
+                Step::GameOver
+            }
+            None => Step::BeginTurn,
+        }
+    }
+
+    /// Stops the game without a result (e.g. the players walked away), closing the open phase
+    /// so its time is still counted.
+    pub fn abandon(&mut self) {
+        self.end_phase();
+    }
+}
@@ -0,0 +1,63 @@
+//! The monsters players pick at setup, each with an ability of its own.
+
+use crate::Passive;
+use serde::{Deserialize, Serialize};
+
+/// A monster a player can play as. Each one carries a lasting ability that works just
+/// like a Keep card's.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Monster {
+    Alienoid,
+    CyberBunny,
+    Gigazaur,
+    Kraken,
+    MekaDragon,
+    TheKing,
+}
+
+impl Monster {
+    pub const ALL: [Monster; 6] = [
+        Monster::Alienoid,
+        Monster::CyberBunny,
+        Monster::Gigazaur,
+        Monster::Kraken,
+        Monster::MekaDragon,
+        Monster::TheKing,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Monster::Alienoid => "Alienoid",
+            Monster::CyberBunny => "Cyber Bunny",
+            Monster::Gigazaur => "Gigazaur",
+            Monster::Kraken => "Kraken",
+            Monster::MekaDragon => "Meka Dragon",
+            Monster::TheKing => "The King",
+        }
+    }
+
+    /// The ability this monster has from the start of the game.
+    pub fn passive(&self) -> Passive {
+        match self {
+            Monster::Alienoid => Passive::EnergyHoarder,
+            Monster::CyberBunny => Passive::BonusEnergy,
+            Monster::Gigazaur => Passive::Armor,
+            Monster::Kraken => Passive::BonusHeal,
+            Monster::MekaDragon => Passive::SolarPowered,
+            Monster::TheKing => Passive::Herbivore,
+        }
+    }
+
+    /// One-line rules text for the ability, for setup menus.
+    pub fn ability(&self) -> &'static str {
+        match self {
+            Monster::Alienoid => "End of turn: +1 VP per 6 Energy you hold",
+            Monster::CyberBunny => "+1 Energy whenever you roll Energy",
+            Monster::Gigazaur => "Ignore any hit of exactly 1 damage",
+            Monster::Kraken => "+1 HP whenever your Hearts heal you",
+            Monster::MekaDragon => "End of turn: +1 Energy if you have none",
+            Monster::TheKing => "End of turn: +1 VP if you dealt no damage",
+        }
+    }
+}
@@ -0,0 +1,879 @@
+//! Plain-sentence narration of game events, for screen readers and text-to-speech. Unlike
+//! a frontend's decorated output, every line is a short sentence with no symbols or emoji.
+//!
+//! Events are never turned into prose directly. `message` picks a `MessageKey` and pulls
+//! the values out of the event as `Arg`s, and a `Locale` fills its template for that key.
+//! Each locale matches every key without a wildcard, so a new sentence can't ship without
+//! a template in every language. English and Spanish ship.
+//!
+//! The catalog also holds the console's prompts, menus and report lines, so a frontend
+//! asks and reports in the player's language too. Those keys are never picked by `message`,
+//! and are worded for a screen: a frontend adds its own decoration around them.
+
+use crate::{DamageSource, DieResult, Game, GameEvent, TokyoZone, Victory};
+use std::time::Duration;
+
+/// A shipped language, for narration and for how reports write numbers and durations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Spanish,
+}
+
+/// Which sentence a narration line or console prompt is, whatever the language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKey {
+    GameStarted,
+    PracticeStarted,
+    MonsterChosen,
+    TurnStarted,
+    DiceResolved,
+    DiceRerolled,
+    DiceSet,
+    TriplesScored,
+    EnergyGained,
+    Healed,
+    HeartsIgnoredInOvertime,
+    HeartsIgnoredInTokyo,
+    AttackFromTokyo,
+    AttackIntoTokyo,
+    CardDamage,
+    ClawDamage,
+    TokyoHeld,
+    TokyoEntered,
+    TokyoEntryDeclined,
+    TokyoConceded,
+    TokyoBayClosedOn,
+    TokyoBayClosed,
+    TokyoVacated,
+    TokyoYielded,
+    TokyoDefended,
+    CardRevealed,
+    EvolutionDrawn,
+    EvolutionPlayed,
+    CardPurchased,
+    ShopSwept,
+    VpGained,
+    DamageBlocked,
+    PseudoCardBought,
+    OvertimeStarted,
+    TurnSkipped,
+    SkipScheduled,
+    PlayerEliminated,
+    ExtraTurnGranted,
+    MaxHpChanged,
+    MaxVpChanged,
+    WonOnPoints,
+    LastStanding,
+    NoSurvivors,
+    // The console's prompts, menus and report lines
+    PromptNextTurn,
+    PromptSetDice,
+    PromptKeepDice,
+    PromptKeepDiceHinted,
+    PromptConcedeTokyo,
+    PromptYieldTokyo,
+    PromptEnterTokyo,
+    PromptAdvice,
+    AnswerYes,
+    AnswerNo,
+    PromptShop,
+    PromptShopEvolve,
+    PromptPurchases,
+    PromptSpectate,
+    PromptInterrupted,
+    PromptInterruptedOnline,
+    PromptPlayerCount,
+    PromptPlayerName,
+    PromptSeat,
+    PromptMonster,
+    PromptPreset,
+    PromptEnergyPerVp,
+    PromptEnergyPerHeal,
+    PromptOptionalEntry,
+    PromptPlayAgain,
+    PromptYourName,
+    Interrupted,
+    InputClosed,
+    HandOver,
+    RetryFaces,
+    RetryKeep,
+    RetryEvolution,
+    RetrySweep,
+    RetryCost,
+    RetryCard,
+    RetryTimes,
+    RetryMonster,
+    MonsterTaken,
+    UnknownPreset,
+    SeatRandomBot,
+    SeatHeuristicBot,
+    BotIdentity,
+    MonstersHeading,
+    ShopHeading,
+    EvolutionsHeading,
+    Saved,
+    SavedToResume,
+    SaveFailed,
+    Loaded,
+    LoadFailed,
+    UndoOff,
+    Stopping,
+    Leaving,
+    BugReportWritten,
+    BugReportFailed,
+    HelpIntro,
+    HelpStatus,
+    HelpBoard,
+    HelpReview,
+    HelpRule,
+    HelpSave,
+    HelpLoad,
+    HelpUndo,
+    HelpQuit,
+    HelpHelp,
+    StatusHeading,
+    StatusLine,
+    StatusEliminated,
+    BoardHeading,
+    TokyoEmpty,
+    ZoneHeld,
+    OutsideTokyo,
+    SpectatorBoard,
+    SpectatorInTokyo,
+    SpectatorOutside,
+    ReviewHeading,
+    ReviewEnd,
+    RulesThisTurn,
+    RuleIds,
+    NoSuchRule,
+    StandingsHeading,
+    PracticeUnranked,
+    StandingEliminated,
+    StandingSurvived,
+    TiesBroken,
+    ScoresHeading,
+    ScoreLine,
+    VpSources,
+    CardsKept,
+    StatisticsHeading,
+    StatisticsColumns,
+    DiceLuckHeading,
+    DiceLuckNote,
+    DiceLuckLine,
+    Lucky,
+    Robbed,
+    AsExpected,
+    DamageHeading,
+    NoDamage,
+    DamageTotals,
+    DamageTo,
+    KnockedOut,
+    KnockedOutAssisted,
+    TimingsHeading,
+    TimingLine,
+    ClockHeading,
+    StartedAt,
+    GameLength,
+    ThinkingTime,
+    SeriesHeading,
+    BySeat,
+    SeatLine,
+    ByPlayer,
+    PlayerSeriesLine,
+    GameHeading,
+    NetworkGameHeading,
+    ResumeHeading,
+    ReplayHeading,
+    Seating,
+    CommandsHint,
+    UndoHint,
+}
+
+impl MessageKey {
+    /// Every sentence, in the order they are declared. A new key goes here too, so the tests
+    /// render it in every locale.
+    pub const ALL: [MessageKey; 164] = [
+        MessageKey::GameStarted, MessageKey::PracticeStarted, MessageKey::MonsterChosen,
+        MessageKey::TurnStarted, MessageKey::DiceResolved, MessageKey::DiceRerolled, MessageKey::DiceSet,
+        MessageKey::TriplesScored, MessageKey::EnergyGained, MessageKey::Healed,
+        MessageKey::HeartsIgnoredInOvertime, MessageKey::HeartsIgnoredInTokyo, MessageKey::AttackFromTokyo,
+        MessageKey::AttackIntoTokyo, MessageKey::CardDamage, MessageKey::ClawDamage, MessageKey::TokyoHeld,
+        MessageKey::TokyoEntered, MessageKey::TokyoEntryDeclined, MessageKey::TokyoConceded,
+        MessageKey::TokyoBayClosedOn, MessageKey::TokyoBayClosed, MessageKey::TokyoVacated,
+        MessageKey::TokyoYielded, MessageKey::TokyoDefended, MessageKey::CardRevealed,
+        MessageKey::EvolutionDrawn, MessageKey::EvolutionPlayed, MessageKey::CardPurchased,
+        MessageKey::ShopSwept, MessageKey::VpGained, MessageKey::DamageBlocked, MessageKey::PseudoCardBought,
+        MessageKey::OvertimeStarted, MessageKey::TurnSkipped, MessageKey::SkipScheduled,
+        MessageKey::PlayerEliminated, MessageKey::ExtraTurnGranted, MessageKey::MaxHpChanged,
+        MessageKey::MaxVpChanged, MessageKey::WonOnPoints, MessageKey::LastStanding, MessageKey::NoSurvivors,
+        MessageKey::PromptNextTurn, MessageKey::PromptSetDice, MessageKey::PromptKeepDice,
+        MessageKey::PromptKeepDiceHinted, MessageKey::PromptConcedeTokyo, MessageKey::PromptYieldTokyo,
+        MessageKey::PromptEnterTokyo, MessageKey::PromptAdvice, MessageKey::AnswerYes, MessageKey::AnswerNo,
+        MessageKey::PromptShop, MessageKey::PromptShopEvolve, MessageKey::PromptPurchases,
+        MessageKey::PromptSpectate, MessageKey::PromptInterrupted, MessageKey::PromptInterruptedOnline,
+        MessageKey::PromptPlayerCount, MessageKey::PromptPlayerName, MessageKey::PromptSeat,
+        MessageKey::PromptMonster, MessageKey::PromptPreset, MessageKey::PromptEnergyPerVp,
+        MessageKey::PromptEnergyPerHeal, MessageKey::PromptOptionalEntry, MessageKey::PromptPlayAgain,
+        MessageKey::PromptYourName, MessageKey::Interrupted, MessageKey::InputClosed, MessageKey::HandOver,
+        MessageKey::RetryFaces, MessageKey::RetryKeep, MessageKey::RetryEvolution, MessageKey::RetrySweep,
+        MessageKey::RetryCost, MessageKey::RetryCard, MessageKey::RetryTimes, MessageKey::RetryMonster,
+        MessageKey::MonsterTaken, MessageKey::UnknownPreset, MessageKey::SeatRandomBot,
+        MessageKey::SeatHeuristicBot, MessageKey::BotIdentity, MessageKey::MonstersHeading,
+        MessageKey::ShopHeading, MessageKey::EvolutionsHeading, MessageKey::Saved, MessageKey::SavedToResume,
+        MessageKey::SaveFailed, MessageKey::Loaded, MessageKey::LoadFailed, MessageKey::UndoOff,
+        MessageKey::Stopping, MessageKey::Leaving, MessageKey::BugReportWritten, MessageKey::BugReportFailed,
+        MessageKey::HelpIntro, MessageKey::HelpStatus, MessageKey::HelpBoard, MessageKey::HelpReview,
+        MessageKey::HelpRule, MessageKey::HelpSave, MessageKey::HelpLoad, MessageKey::HelpUndo,
+        MessageKey::HelpQuit, MessageKey::HelpHelp, MessageKey::StatusHeading, MessageKey::StatusLine,
+        MessageKey::StatusEliminated, MessageKey::BoardHeading, MessageKey::TokyoEmpty, MessageKey::ZoneHeld,
+        MessageKey::OutsideTokyo, MessageKey::SpectatorBoard, MessageKey::SpectatorInTokyo,
+        MessageKey::SpectatorOutside, MessageKey::ReviewHeading, MessageKey::ReviewEnd,
+        MessageKey::RulesThisTurn, MessageKey::RuleIds, MessageKey::NoSuchRule, MessageKey::StandingsHeading,
+        MessageKey::PracticeUnranked, MessageKey::StandingEliminated, MessageKey::StandingSurvived,
+        MessageKey::TiesBroken, MessageKey::ScoresHeading, MessageKey::ScoreLine, MessageKey::VpSources,
+        MessageKey::CardsKept, MessageKey::StatisticsHeading, MessageKey::StatisticsColumns,
+        MessageKey::DiceLuckHeading, MessageKey::DiceLuckNote, MessageKey::DiceLuckLine, MessageKey::Lucky,
+        MessageKey::Robbed, MessageKey::AsExpected, MessageKey::DamageHeading, MessageKey::NoDamage,
+        MessageKey::DamageTotals, MessageKey::DamageTo, MessageKey::KnockedOut, MessageKey::KnockedOutAssisted,
+        MessageKey::TimingsHeading, MessageKey::TimingLine, MessageKey::ClockHeading, MessageKey::StartedAt,
+        MessageKey::GameLength, MessageKey::ThinkingTime, MessageKey::SeriesHeading, MessageKey::BySeat,
+        MessageKey::SeatLine, MessageKey::ByPlayer, MessageKey::PlayerSeriesLine, MessageKey::GameHeading,
+        MessageKey::NetworkGameHeading, MessageKey::ResumeHeading, MessageKey::ReplayHeading, MessageKey::Seating,
+        MessageKey::CommandsHint, MessageKey::UndoHint,
+    ];
+}
+
+/// A value for a template placeholder. Only names are passed through as text; everything
+/// the locale has to word (counts of points, dice, places) stays as data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Name(String), // A player, monster, card or preset, shown as is
+    Number(u32),
+    Points(u8), // Victory points, worded with the locale's plural
+    Dice([DieResult; 6]),
+    Face(DieResult), // A single die
+    Zone(TokyoZone),
+}
+
+/// One narration line before it is put into words: the sentence and its placeholder values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub key: MessageKey,
+    pub args: Vec<(&'static str, Arg)>, // Placeholder name (without braces) and its value
+}
+
+impl Locale {
+    /// Every language narration can be rendered in.
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Spanish];
+
+    /// The language tag profiles use, e.g. "en".
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Locale::ALL.into_iter().find(|locale| locale.tag().eq_ignore_ascii_case(tag))
+    }
+
+    /// The sentence for `key`, with `{placeholders}` for its arguments.
+    pub fn template(self, key: MessageKey) -> &'static str {
+        match self {
+            Locale::English => match key {
+                MessageKey::GameStarted => "A new game begins with {preset} rules.",
+                MessageKey::PracticeStarted => "This is a practice game, so it is not ranked.",
+                MessageKey::MonsterChosen => "{player} plays as {monster}.",
+                MessageKey::TurnStarted => "{player}'s turn, with {hp} health and {points}.",
+                MessageKey::DiceResolved => "{player} rolled {dice}.",
+                MessageKey::DiceRerolled => "{by}'s {effect} makes {player} roll a die again, and it comes up {face}.",
+                MessageKey::DiceSet => "{by}'s {effect} turns one of {player}'s dice to {face}.",
+                MessageKey::TriplesScored => "{player} scores {vp} for matching numbers, now {total}.",
+                MessageKey::EnergyGained => "{player} gains {amount} energy, now {total}.",
+                MessageKey::Healed => "{player} heals {amount}, now {hp} health.",
+                MessageKey::HeartsIgnoredInOvertime => "{player} cannot heal in overtime.",
+                MessageKey::HeartsIgnoredInTokyo => "{player} cannot heal in Tokyo.",
+                MessageKey::AttackFromTokyo => "{player} attacks everyone outside Tokyo for {damage} damage.",
+                MessageKey::AttackIntoTokyo => "{player} attacks Tokyo for {damage} damage.",
+                MessageKey::CardDamage => "{card} hits {target} for {damage}, leaving {hp} health.",
+                MessageKey::ClawDamage => "{target} takes {damage}, leaving {hp} health.",
+                MessageKey::TokyoHeld => "{player} holds {zone} and scores {vp}, now {total}.",
+                MessageKey::TokyoEntered => "{player} enters {zone} and scores {vp}, now {total}.",
+                MessageKey::TokyoEntryDeclined => "{player} stays out of Tokyo.",
+                MessageKey::TokyoConceded => "{player} leaves {zone}.",
+                MessageKey::TokyoBayClosedOn => "Tokyo Bay closes, and {player} has to leave it.",
+                MessageKey::TokyoBayClosed => "Tokyo Bay closes.",
+                MessageKey::TokyoVacated => "{zone} is empty now.",
+                MessageKey::TokyoYielded => "{player} yields {zone}.",
+                MessageKey::TokyoDefended => "{player} stays in {zone}.",
+                MessageKey::CardRevealed => "{card} goes on sale.",
+                MessageKey::EvolutionDrawn => "{player} draws an evolution card.",
+                MessageKey::EvolutionPlayed => "{player} evolves with {evolution}.",
+                MessageKey::CardPurchased => "{player} buys {card} for {cost} energy.",
+                MessageKey::ShopSwept => "{player} clears the shop.",
+                MessageKey::VpGained => "{player} gains {amount}, now {total}.",
+                MessageKey::DamageBlocked => "{player}'s {effect} blocks the hit.",
+                MessageKey::PseudoCardBought => "{player} spends {spent} Energy on {card} ({times} times).",
+                MessageKey::OvertimeStarted => "Overtime begins. Nobody can heal any more.",
+                MessageKey::TurnSkipped => "{player} loses this turn.",
+                MessageKey::SkipScheduled => "{player} will lose their next turn.",
+                MessageKey::PlayerEliminated => "{player} is out of the game.",
+                MessageKey::ExtraTurnGranted => "{player} gets another turn.",
+                MessageKey::MaxHpChanged => "{player} now has {max_hp} maximum health.",
+                MessageKey::MaxVpChanged => "{player} now needs {max_vp} to win.",
+                MessageKey::WonOnPoints => "Game over. {player} wins on points.",
+                MessageKey::LastStanding => "Game over. {player} is the last monster standing.",
+                MessageKey::NoSurvivors => "Game over. No monster survived.",
+                MessageKey::PromptNextTurn => "(Press Enter for the next turn)",
+                MessageKey::PromptSetDice => "Set {count} dice (e.g. 1 1 1 c c h), Enter to roll them: ",
+                MessageKey::PromptKeepDice => "Keep which dice? ('{key}' to stop): ",
+                MessageKey::PromptKeepDiceHinted => "Keep which dice? (e.g. 1 3 5, '{key}' to stop, Enter to reroll everything): ",
+                MessageKey::PromptConcedeTokyo => "{player} has finished attacking. CONCEDE Tokyo?",
+                MessageKey::PromptYieldTokyo => "{player} was hit by {by}. Should {player} YIELD {zone}?",
+                MessageKey::PromptEnterTokyo => "{zone} is vacant. {player} rolled {count} Claw(s). Do you want to ENTER {zone}?",
+                MessageKey::PromptAdvice => "{answer}, since {reason}",
+                MessageKey::AnswerYes => "yes",
+                MessageKey::AnswerNo => "no",
+                MessageKey::PromptShop => "{player} has {energy} Energy. Buy a card (1-{count}), '{key}' to sweep the shop ({cost} Energy), {evolve}Enter to finish: ",
+                MessageKey::PromptShopEvolve => "'{key}1' to play an evolution, ",
+                MessageKey::PromptPurchases => "{player} has {energy} Energy. Use {card} ({cost} Energy -> {effect}) how many times? (0-{max}) [0]: ",
+                MessageKey::PromptSpectate => "{player}, keep watching as a spectator?",
+                MessageKey::PromptInterrupted => "Interrupted. s = save and stop, b = write a bug report, q = stop, Enter = carry on: ",
+                MessageKey::PromptInterruptedOnline => "Interrupted. b = write a bug report, q = leave the game, Enter = carry on: ",
+                MessageKey::PromptPlayerCount => "How many players ({min}-{max})? [2]: ",
+                MessageKey::PromptPlayerName => "Enter name for Player {number}: ",
+                MessageKey::PromptSeat => "Who plays {player}? (h = human, r = random bot, b = heuristic bot) [h]: ",
+                MessageKey::PromptMonster => "Which monster is {player}? (1-{max}, Enter = none): ",
+                MessageKey::PromptPreset => "Rules preset ({list}) [official]: ",
+                MessageKey::PromptEnergyPerVp => "House rule: allow trading {energy} Energy for 1 VP in the buy phase? ({choices}): ",
+                MessageKey::PromptEnergyPerHeal => "House rule: allow paying {energy} Energy to heal 1 HP outside Tokyo in the buy phase? ({choices}): ",
+                MessageKey::PromptOptionalEntry => "House rule: let attackers choose whether to enter an empty Tokyo? ({choices}): ",
+                MessageKey::PromptPlayAgain => "Play another game? (r = rotate seats, s = shuffle seats, N = stop): ",
+                MessageKey::PromptYourName => "Your name: ",
+                MessageKey::Interrupted => "Interrupted.",
+                MessageKey::InputClosed => "Input closed. Finishing this turn with default answers, then ending the game.",
+                MessageKey::HandOver => "{player}'s decision: pass the keyboard to {player}.",
+                MessageKey::RetryFaces => "Please give {count} faces, each one of 1, 2, 3, e (Energy), c (Claw) or h (Heart).",
+                MessageKey::RetryKeep => "Please list dice positions from 1 to 6.",
+                MessageKey::RetryEvolution => "Please pick an evolution from {key}1 to {key}{max}.",
+                MessageKey::RetrySweep => "Not enough Energy to sweep the shop.",
+                MessageKey::RetryCost => "{card} costs {cost} Energy.",
+                MessageKey::RetryCard => "Please pick a card from 1 to {max}.",
+                MessageKey::RetryTimes => "Please enter a number from 0 to {max}.",
+                MessageKey::RetryMonster => "Please enter a number from 1 to {max}.",
+                MessageKey::MonsterTaken => "{monster} is already taken.",
+                MessageKey::UnknownPreset => "Unknown preset '{preset}'.",
+                MessageKey::SeatRandomBot => "{player} will be played by the random bot.",
+                MessageKey::SeatHeuristicBot => "{player} will be played by the heuristic bot.",
+                MessageKey::BotIdentity => "{player}'s bot goes by {by}.",
+                MessageKey::MonstersHeading => "Monsters:",
+                MessageKey::ShopHeading => "Shop ({count} cards left in the deck):",
+                MessageKey::EvolutionsHeading => "Evolutions in hand (free to play):",
+                MessageKey::Saved => "Saved to {path}. Loading it resumes from the start of this turn.",
+                MessageKey::SavedToResume => "Saved to {path}. Start with --load to pick it up from the start of this turn.",
+                MessageKey::SaveFailed => "Couldn't save to {path}: {error}",
+                MessageKey::Loaded => "Loaded {path} (turn {number}).",
+                MessageKey::LoadFailed => "Couldn't load {path}: {error}",
+                MessageKey::UndoOff => "Undo is off in this game. Start one with --set rules.undo_limit=<decisions> to use it.",
+                MessageKey::Stopping => "Stopping the game. This turn is set aside; the tally follows.",
+                MessageKey::Leaving => "Leaving the game.",
+                MessageKey::BugReportWritten => "Wrote a bug report to {path}. Attach it to the issue.",
+                MessageKey::BugReportFailed => "Couldn't write a bug report to {path}: {error}",
+                MessageKey::HelpIntro => "Answer the question below using the choices it shows, or type one of these:",
+                MessageKey::HelpStatus => "everyone's HP, VP and Energy",
+                MessageKey::HelpBoard => "who is in Tokyo",
+                MessageKey::HelpReview => "replay this turn so far, with the rule behind each event",
+                MessageKey::HelpRule => "look a rule up, or list the rules behind this turn",
+                MessageKey::HelpSave => "save the game as it stood at the start of this turn",
+                MessageKey::HelpLoad => "switch to a saved game",
+                MessageKey::HelpUndo => "take back or put back your last decision",
+                MessageKey::HelpQuit => "stop the game and show the final tally",
+                MessageKey::HelpHelp => "this list",
+                MessageKey::StatusHeading => "Status (turn {number}):",
+                MessageKey::StatusLine => "{player}: {hp}/{max_hp} HP, {score}/{goal} VP, {energy} Energy",
+                MessageKey::StatusEliminated => "{player}: eliminated, {score} VP",
+                MessageKey::BoardHeading => "Board:",
+                MessageKey::TokyoEmpty => "Tokyo is empty.",
+                MessageKey::ZoneHeld => "{zone}: {player}",
+                MessageKey::OutsideTokyo => "Outside Tokyo: {list}",
+                MessageKey::SpectatorBoard => "Spectator board (watching: {list})",
+                MessageKey::SpectatorInTokyo => "{player}: {hp} HP, {score} VP, {energy} Energy, {zone}",
+                MessageKey::SpectatorOutside => "{player}: {hp} HP, {score} VP, {energy} Energy, outside Tokyo",
+                MessageKey::ReviewHeading => "Review of turn {number}:",
+                MessageKey::ReviewEnd => "End of review.",
+                MessageKey::RulesThisTurn => "Rules behind this turn so far:",
+                MessageKey::RuleIds => "Type `rule <id>` for any of: {list}.",
+                MessageKey::NoSuchRule => "There's no rule \"{key}\". Try one of: {list}.",
+                MessageKey::StandingsHeading => "Final Standings",
+                MessageKey::PracticeUnranked => "(Practice game: unranked, dice were set by hand)",
+                MessageKey::StandingEliminated => "{player} (eliminated, {score} VP)",
+                MessageKey::StandingSurvived => "{player} (survived, {score} VP)",
+                MessageKey::TiesBroken => "Ties broken by {policy}.",
+                MessageKey::ScoresHeading => "Final Scores",
+                MessageKey::ScoreLine => "{player}: {score} VP, {hp} HP, {energy} Energy",
+                MessageKey::VpSources => "VP sources: {list}",
+                MessageKey::CardsKept => "Cards kept: {list}",
+                MessageKey::StatisticsHeading => "Game Statistics",
+                MessageKey::StatisticsColumns => "Player, Dealt, Taken, KOs, Energy, Spent, Turns, Tokyo, VP from",
+                MessageKey::DiceLuckHeading => "Dice Luck",
+                MessageKey::DiceLuckNote => "(Rerolled dice landing on a face you were keeping, against the odds)",
+                MessageKey::DiceLuckLine => "{player}: {luck} dice ({verdict})",
+                MessageKey::Lucky => "lucky",
+                MessageKey::Robbed => "robbed",
+                MessageKey::AsExpected => "about as expected",
+                MessageKey::DamageHeading => "Damage Report",
+                MessageKey::NoDamage => "No damage was dealt this game.",
+                MessageKey::DamageTotals => "{player}: dealt {dealt}, taken {taken}",
+                MessageKey::DamageTo => "{damage} to {target} ({source})",
+                MessageKey::KnockedOut => "{by} knocked out {target}.",
+                MessageKey::KnockedOutAssisted => "{by} knocked out {target} (assists: {list}).",
+                MessageKey::TimingsHeading => "Turn Phase Timings",
+                MessageKey::TimingLine => "{phase}: {duration} total, {average} average over {count} turns",
+                MessageKey::ClockHeading => "Game Clock",
+                MessageKey::StartedAt => "Started at {time} (Unix time)",
+                MessageKey::GameLength => "Game length: {duration}",
+                MessageKey::ThinkingTime => "{player}: {duration} thinking",
+                MessageKey::SeriesHeading => "Series Summary ({count} games)",
+                MessageKey::BySeat => "By seat:",
+                MessageKey::SeatLine => "Seat {number}: {count} wins ({share}), average placement {average}",
+                MessageKey::ByPlayer => "By player:",
+                MessageKey::PlayerSeriesLine => "{player}: {count} wins (seats played: {list})",
+                MessageKey::GameHeading => "Game {number} Start with {count} Players (seed {seed}, {preset} rules)",
+                MessageKey::NetworkGameHeading => "Network Game Start with {count} Players (seed {seed}, {preset} rules)",
+                MessageKey::ResumeHeading => "Resuming {path} at turn {number} (seed {seed}, {preset} rules)",
+                MessageKey::ReplayHeading => "Replaying {path} ({count} players, seed {seed}, {preset} rules)",
+                MessageKey::Seating => "Seating: {list}",
+                MessageKey::CommandsHint => "(Type 'save [file]' or 'load [file]' at any prompt to save or switch games, 'status' or 'board' to look around, 'help' for more.)",
+                MessageKey::UndoHint => "(Type 'undo' or 'redo' to take back or put back up to {count} decisions.)",
+            },
+            Locale::Spanish => match key {
+                MessageKey::GameStarted => "Empieza una partida nueva con las reglas {preset}.",
+                MessageKey::PracticeStarted => "Es una partida de práctica, así que no cuenta para la clasificación.",
+                MessageKey::MonsterChosen => "{player} juega con {monster}.",
+                MessageKey::TurnStarted => "Turno de {player}, con {hp} de vida y {points}.",
+                MessageKey::DiceResolved => "{player} ha sacado {dice}.",
+                MessageKey::DiceRerolled => "{effect} de {by} obliga a {player} a volver a tirar un dado, y sale {face}.",
+                MessageKey::DiceSet => "{effect} de {by} pone un dado de {player} en {face}.",
+                MessageKey::TriplesScored => "{player} gana {vp} por números iguales, y tiene {total}.",
+                MessageKey::EnergyGained => "{player} gana {amount} de energía, y tiene {total}.",
+                MessageKey::Healed => "{player} se cura {amount}, y tiene {hp} de vida.",
+                MessageKey::HeartsIgnoredInOvertime => "{player} no puede curarse en la prórroga.",
+                MessageKey::HeartsIgnoredInTokyo => "{player} no puede curarse en Tokio.",
+                MessageKey::AttackFromTokyo => "{player} ataca a todos los que están fuera de Tokio con {damage} de daño.",
+                MessageKey::AttackIntoTokyo => "{player} ataca Tokio con {damage} de daño.",
+                MessageKey::CardDamage => "{card} golpea a {target} con {damage} de daño, y le deja con {hp} de vida.",
+                MessageKey::ClawDamage => "{target} recibe {damage} de daño, y se queda con {hp} de vida.",
+                MessageKey::TokyoHeld => "{player} mantiene {zone} y gana {vp}, y tiene {total}.",
+                MessageKey::TokyoEntered => "{player} entra en {zone} y gana {vp}, y tiene {total}.",
+                MessageKey::TokyoEntryDeclined => "{player} se queda fuera de Tokio.",
+                MessageKey::TokyoConceded => "{player} sale de {zone}.",
+                MessageKey::TokyoBayClosedOn => "La Bahía de Tokio se cierra, y {player} tiene que salir.",
+                MessageKey::TokyoBayClosed => "La Bahía de Tokio se cierra.",
+                MessageKey::TokyoVacated => "{zone} se queda vacía.",
+                MessageKey::TokyoYielded => "{player} cede {zone}.",
+                MessageKey::TokyoDefended => "{player} se queda en {zone}.",
+                MessageKey::CardRevealed => "{card} sale a la venta.",
+                MessageKey::EvolutionDrawn => "{player} roba una carta de evolución.",
+                MessageKey::EvolutionPlayed => "{player} evoluciona con {evolution}.",
+                MessageKey::CardPurchased => "{player} compra {card} por {cost} de energía.",
+                MessageKey::ShopSwept => "{player} vacía la tienda.",
+                MessageKey::VpGained => "{player} gana {amount}, y tiene {total}.",
+                MessageKey::DamageBlocked => "{effect} de {player} bloquea el golpe.",
+                MessageKey::PseudoCardBought => "{player} gasta {spent} de energía en {card} ({times} veces).",
+                MessageKey::OvertimeStarted => "Empieza la prórroga. Ya nadie puede curarse.",
+                MessageKey::TurnSkipped => "{player} pierde este turno.",
+                MessageKey::SkipScheduled => "{player} perderá su próximo turno.",
+                MessageKey::PlayerEliminated => "{player} queda fuera de la partida.",
+                MessageKey::ExtraTurnGranted => "{player} juega otro turno.",
+                MessageKey::MaxHpChanged => "{player} tiene ahora {max_hp} de vida máxima.",
+                MessageKey::MaxVpChanged => "{player} necesita ahora {max_vp} para ganar.",
+                MessageKey::WonOnPoints => "Fin de la partida. {player} gana por puntos.",
+                MessageKey::LastStanding => "Fin de la partida. {player} es el último monstruo en pie.",
+                MessageKey::NoSurvivors => "Fin de la partida. No ha sobrevivido ningún monstruo.",
+                MessageKey::PromptNextTurn => "(Pulsa Intro para el siguiente turno)",
+                MessageKey::PromptSetDice => "Pon {count} dados (p. ej. 1 1 1 c c h), Intro para tirarlos: ",
+                MessageKey::PromptKeepDice => "¿Qué dados guardas? ('{key}' para plantarte): ",
+                MessageKey::PromptKeepDiceHinted => "¿Qué dados guardas? (p. ej. 1 3 5, '{key}' para plantarte, Intro para volver a tirarlos todos): ",
+                MessageKey::PromptConcedeTokyo => "{player} ha terminado de atacar. ¿CEDE Tokio?",
+                MessageKey::PromptYieldTokyo => "{by} ha golpeado a {player}. ¿Debe {player} CEDER {zone}?",
+                MessageKey::PromptEnterTokyo => "{zone} está libre. {player} ha sacado {count} garra(s). ¿Quieres ENTRAR en {zone}?",
+                MessageKey::PromptAdvice => "{answer}, porque {reason}",
+                MessageKey::AnswerYes => "sí",
+                MessageKey::AnswerNo => "no",
+                MessageKey::PromptShop => "{player} tiene {energy} de energía. Compra una carta (1-{count}), '{key}' para vaciar la tienda ({cost} de energía), {evolve}Intro para terminar: ",
+                MessageKey::PromptShopEvolve => "'{key}1' para jugar una evolución, ",
+                MessageKey::PromptPurchases => "{player} tiene {energy} de energía. ¿Cuántas veces usas {card} ({cost} de energía -> {effect})? (0-{max}) [0]: ",
+                MessageKey::PromptSpectate => "{player}, ¿sigues mirando como espectador?",
+                MessageKey::PromptInterrupted => "Interrumpido. s = guardar y parar, b = escribir un informe de error, q = parar, Intro = seguir: ",
+                MessageKey::PromptInterruptedOnline => "Interrumpido. b = escribir un informe de error, q = dejar la partida, Intro = seguir: ",
+                MessageKey::PromptPlayerCount => "¿Cuántos jugadores ({min}-{max})? [2]: ",
+                MessageKey::PromptPlayerName => "Nombre del jugador {number}: ",
+                MessageKey::PromptSeat => "¿Quién juega con {player}? (h = humano, r = bot aleatorio, b = bot heurístico) [h]: ",
+                MessageKey::PromptMonster => "¿Qué monstruo es {player}? (1-{max}, Intro = ninguno): ",
+                MessageKey::PromptPreset => "Reglas ({list}) [official]: ",
+                MessageKey::PromptEnergyPerVp => "Regla de la casa: ¿se pueden cambiar {energy} de energía por 1 PV en la fase de compra? ({choices}): ",
+                MessageKey::PromptEnergyPerHeal => "Regla de la casa: ¿se pueden pagar {energy} de energía por curar 1 de vida fuera de Tokio en la fase de compra? ({choices}): ",
+                MessageKey::PromptOptionalEntry => "Regla de la casa: ¿pueden los atacantes elegir si entran en Tokio cuando está vacío? ({choices}): ",
+                MessageKey::PromptPlayAgain => "¿Otra partida? (r = rotar los asientos, s = barajar los asientos, N = parar): ",
+                MessageKey::PromptYourName => "Tu nombre: ",
+                MessageKey::Interrupted => "Interrumpido.",
+                MessageKey::InputClosed => "La entrada se ha cerrado. Se termina este turno con las respuestas por defecto, y luego la partida.",
+                MessageKey::HandOver => "Le toca decidir a {player}: pásale el teclado a {player}.",
+                MessageKey::RetryFaces => "Indica {count} caras, cada una 1, 2, 3, e (energía), c (garra) o h (corazón).",
+                MessageKey::RetryKeep => "Indica posiciones de dados del 1 al 6.",
+                MessageKey::RetryEvolution => "Elige una evolución de {key}1 a {key}{max}.",
+                MessageKey::RetrySweep => "No tienes energía suficiente para vaciar la tienda.",
+                MessageKey::RetryCost => "{card} cuesta {cost} de energía.",
+                MessageKey::RetryCard => "Elige una carta del 1 al {max}.",
+                MessageKey::RetryTimes => "Escribe un número del 0 al {max}.",
+                MessageKey::RetryMonster => "Escribe un número del 1 al {max}.",
+                MessageKey::MonsterTaken => "{monster} ya tiene jugador.",
+                MessageKey::UnknownPreset => "No hay reglas '{preset}'.",
+                MessageKey::SeatRandomBot => "{player} lo jugará el bot aleatorio.",
+                MessageKey::SeatHeuristicBot => "{player} lo jugará el bot heurístico.",
+                MessageKey::BotIdentity => "El bot de {player} se llama {by}.",
+                MessageKey::MonstersHeading => "Monstruos:",
+                MessageKey::ShopHeading => "Tienda (quedan {count} cartas en el mazo):",
+                MessageKey::EvolutionsHeading => "Evoluciones en la mano (se juegan gratis):",
+                MessageKey::Saved => "Guardada en {path}. Al cargarla se sigue desde el principio de este turno.",
+                MessageKey::SavedToResume => "Guardada en {path}. Empieza con --load para seguir desde el principio de este turno.",
+                MessageKey::SaveFailed => "No se ha podido guardar en {path}: {error}",
+                MessageKey::Loaded => "Cargada {path} (turno {number}).",
+                MessageKey::LoadFailed => "No se ha podido cargar {path}: {error}",
+                MessageKey::UndoOff => "Deshacer está desactivado en esta partida. Empieza una con --set rules.undo_limit=<decisiones> para usarlo.",
+                MessageKey::Stopping => "Se para la partida. Este turno se descarta; sigue el recuento.",
+                MessageKey::Leaving => "Dejas la partida.",
+                MessageKey::BugReportWritten => "Informe de error escrito en {path}. Adjúntalo a la incidencia.",
+                MessageKey::BugReportFailed => "No se ha podido escribir un informe de error en {path}: {error}",
+                MessageKey::HelpIntro => "Responde a la pregunta de abajo con las opciones que muestra, o escribe una de estas:",
+                MessageKey::HelpStatus => "la vida, los PV y la energía de todos",
+                MessageKey::HelpBoard => "quién está en Tokio",
+                MessageKey::HelpReview => "repite este turno hasta ahora, con la regla de cada suceso",
+                MessageKey::HelpRule => "busca una regla, o lista las reglas de este turno",
+                MessageKey::HelpSave => "guarda la partida como estaba al empezar este turno",
+                MessageKey::HelpLoad => "cambia a una partida guardada",
+                MessageKey::HelpUndo => "deshace o rehace tu última decisión",
+                MessageKey::HelpQuit => "para la partida y muestra el recuento final",
+                MessageKey::HelpHelp => "esta lista",
+                MessageKey::StatusHeading => "Estado (turno {number}):",
+                MessageKey::StatusLine => "{player}: {hp}/{max_hp} de vida, {score}/{goal} PV, {energy} de energía",
+                MessageKey::StatusEliminated => "{player}: eliminado, {score} PV",
+                MessageKey::BoardHeading => "Tablero:",
+                MessageKey::TokyoEmpty => "Tokio está vacío.",
+                MessageKey::ZoneHeld => "{zone}: {player}",
+                MessageKey::OutsideTokyo => "Fuera de Tokio: {list}",
+                MessageKey::SpectatorBoard => "Tablero para espectadores (miran: {list})",
+                MessageKey::SpectatorInTokyo => "{player}: {hp} de vida, {score} PV, {energy} de energía, {zone}",
+                MessageKey::SpectatorOutside => "{player}: {hp} de vida, {score} PV, {energy} de energía, fuera de Tokio",
+                MessageKey::ReviewHeading => "Repaso del turno {number}:",
+                MessageKey::ReviewEnd => "Fin del repaso.",
+                MessageKey::RulesThisTurn => "Reglas de este turno hasta ahora:",
+                MessageKey::RuleIds => "Escribe `rule <id>` para cualquiera de: {list}.",
+                MessageKey::NoSuchRule => "No hay ninguna regla \"{key}\". Prueba con una de: {list}.",
+                MessageKey::StandingsHeading => "Clasificación final",
+                MessageKey::PracticeUnranked => "(Partida de práctica: no cuenta, los dados se pusieron a mano)",
+                MessageKey::StandingEliminated => "{player} (eliminado, {score} PV)",
+                MessageKey::StandingSurvived => "{player} (sigue en pie, {score} PV)",
+                MessageKey::TiesBroken => "Los empates se deshacen por {policy}.",
+                MessageKey::ScoresHeading => "Puntuaciones finales",
+                MessageKey::ScoreLine => "{player}: {score} PV, {hp} de vida, {energy} de energía",
+                MessageKey::VpSources => "De dónde salen los PV: {list}",
+                MessageKey::CardsKept => "Cartas guardadas: {list}",
+                MessageKey::StatisticsHeading => "Estadísticas de la partida",
+                MessageKey::StatisticsColumns => "Jugador, Hecho, Recibido, KOs, Energía, Gastada, Turnos, Tokio, PV por",
+                MessageKey::DiceLuckHeading => "Suerte con los dados",
+                MessageKey::DiceLuckNote => "(Dados que, al volver a tirarlos, caen en una cara que guardabas, frente a lo esperado)",
+                MessageKey::DiceLuckLine => "{player}: {luck} dados ({verdict})",
+                MessageKey::Lucky => "con suerte",
+                MessageKey::Robbed => "sin suerte",
+                MessageKey::AsExpected => "lo esperado",
+                MessageKey::DamageHeading => "Informe de daño",
+                MessageKey::NoDamage => "Nadie ha hecho daño en esta partida.",
+                MessageKey::DamageTotals => "{player}: ha hecho {dealt}, ha recibido {taken}",
+                MessageKey::DamageTo => "{damage} a {target} ({source})",
+                MessageKey::KnockedOut => "{by} ha eliminado a {target}.",
+                MessageKey::KnockedOutAssisted => "{by} ha eliminado a {target} (asistencias: {list}).",
+                MessageKey::TimingsHeading => "Tiempos de cada fase del turno",
+                MessageKey::TimingLine => "{phase}: {duration} en total, {average} de media en {count} turnos",
+                MessageKey::ClockHeading => "Reloj de la partida",
+                MessageKey::StartedAt => "Empezó en {time} (hora Unix)",
+                MessageKey::GameLength => "Duración de la partida: {duration}",
+                MessageKey::ThinkingTime => "{player}: {duration} pensando",
+                MessageKey::SeriesHeading => "Resumen de la serie ({count} partidas)",
+                MessageKey::BySeat => "Por asiento:",
+                MessageKey::SeatLine => "Asiento {number}: {count} victorias ({share}), puesto medio {average}",
+                MessageKey::ByPlayer => "Por jugador:",
+                MessageKey::PlayerSeriesLine => "{player}: {count} victorias (asientos jugados: {list})",
+                MessageKey::GameHeading => "Partida {number}: empieza con {count} jugadores (semilla {seed}, reglas {preset})",
+                MessageKey::NetworkGameHeading => "Partida en red: empieza con {count} jugadores (semilla {seed}, reglas {preset})",
+                MessageKey::ResumeHeading => "Se sigue {path} en el turno {number} (semilla {seed}, reglas {preset})",
+                MessageKey::ReplayHeading => "Repetición de {path} ({count} jugadores, semilla {seed}, reglas {preset})",
+                MessageKey::Seating => "Asientos: {list}",
+                MessageKey::CommandsHint => "(Escribe 'save [file]' o 'load [file]' en cualquier pregunta para guardar o cambiar de partida, 'status' o 'board' para echar un vistazo, 'help' para más.)",
+                MessageKey::UndoHint => "(Escribe 'undo' o 'redo' para deshacer o rehacer hasta {count} decisiones.)",
+            },
+        }
+    }
+
+    /// Thousands separator and decimal mark for numbers in this language.
+    fn separators(self) -> (&'static str, &'static str) {
+        match self {
+            Locale::English => (",", "."), // 12,345.6
+            Locale::Spanish => (".", ","), // 12.345,6
+        }
+    }
+
+    /// A whole number with thousands separators.
+    pub fn number(self, n: u64) -> String {
+        let (thousands, _) = self.separators();
+        let digits = n.to_string();
+        let mut out = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push_str(thousands);
+            }
+            out.push(digit);
+        }
+        out
+    }
+
+    /// A decimal with `places` digits after the language's decimal mark.
+    pub fn decimal(self, value: f64, places: usize) -> String {
+        let (_, decimal_mark) = self.separators();
+        let fixed = format!("{:.*}", places, value.abs());
+        let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let sign = if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') { "-" } else { "" };
+        let whole = self.number(whole.parse().unwrap_or(0));
+        if fraction.is_empty() {
+            format!("{}{}", sign, whole)
+        } else {
+            format!("{}{}{}{}", sign, whole, decimal_mark, fraction)
+        }
+    }
+
+    /// A ratio in 0.0..=1.0 as a percentage, e.g. "41.7%" or "41,7 %".
+    pub fn percent(self, ratio: f64) -> String {
+        match self {
+            Locale::English => format!("{}%", self.decimal(ratio * 100.0, 1)),
+            Locale::Spanish => format!("{} %", self.decimal(ratio * 100.0, 1)),
+        }
+    }
+
+    /// A placement as "1st", "2nd", "3rd"... or "1.º", "2.º"...
+    pub fn ordinal(self, n: u8) -> String {
+        match self {
+            Locale::English => {
+                let suffix = match (n % 10, n % 100) {
+                    (1, 11) | (2, 12) | (3, 13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", n, suffix)
+            }
+            Locale::Spanish => format!("{}.º", n),
+        }
+    }
+
+    /// A duration as "1h 02m 03s", "2m 03s" or "3.4s" (with the language's decimal mark).
+    pub fn duration(self, duration: Duration) -> String {
+        let secs = duration.as_secs();
+        if secs >= 3600 {
+            format!("{}h {:02}m {:02}s", self.number(secs / 3600), secs % 3600 / 60, secs % 60)
+        } else if secs >= 60 {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        } else {
+            format!("{}s", self.decimal(duration.as_secs_f64(), 1))
+        }
+    }
+
+    /// An argument in this language.
+    fn word(self, arg: &Arg) -> String {
+        match (self, arg) {
+            (_, Arg::Name(name)) => name.clone(),
+            (_, &Arg::Number(n)) => self.number(n as u64),
+            (Locale::English, Arg::Points(1)) => "1 point".to_string(),
+            (Locale::English, Arg::Points(n)) => format!("{} points", n),
+            (Locale::English, Arg::Dice(dice)) => describe_dice(dice, &FACES, "and"),
+            (Locale::English, Arg::Face(DieResult::Energy)) => "an energy".to_string(),
+            (Locale::English, &Arg::Face(face)) => format!("a {}", face_name(&FACES, face)),
+            (Locale::English, Arg::Zone(TokyoZone::City)) => "Tokyo City".to_string(),
+            (Locale::English, Arg::Zone(TokyoZone::Bay)) => "Tokyo Bay".to_string(),
+            (Locale::Spanish, Arg::Points(1)) => "1 punto".to_string(),
+            (Locale::Spanish, Arg::Points(n)) => format!("{} puntos", n),
+            (Locale::Spanish, Arg::Dice(dice)) => describe_dice(dice, &FACES_ES, "y"),
+            (Locale::Spanish, &Arg::Face(face @ (DieResult::Energy | DieResult::Claw))) => format!("una {}", face_name(&FACES_ES, face)),
+            (Locale::Spanish, &Arg::Face(face)) => format!("un {}", face_name(&FACES_ES, face)),
+            (Locale::Spanish, Arg::Zone(TokyoZone::City)) => "Tokio Ciudad".to_string(),
+            (Locale::Spanish, Arg::Zone(TokyoZone::Bay)) => "Bahía de Tokio".to_string(),
+        }
+    }
+
+    /// Fills the template for `message` with its arguments. Placeholders are filled in one
+    /// pass, so a name that happens to contain braces is left alone.
+    pub fn render(self, message: &Message) -> String {
+        let mut line = String::new();
+        let mut rest = self.template(message.key);
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                break;
+            };
+            line.push_str(&rest[..open]);
+            let placeholder = &rest[open + 1..close];
+            match message.args.iter().find(|(name, _)| *name == placeholder) {
+                Some((_, arg)) => line.push_str(&self.word(arg)),
+                None => line.push_str(&rest[open..=close]),
+            }
+            rest = &rest[close + 1..];
+        }
+        line.push_str(rest);
+        line
+    }
+}
+
+/// Faces in the order narration lists them, with their singular and plural names.
+const FACES: [(DieResult, &str, &str); 6] = [
+    (DieResult::One, "one", "ones"),
+    (DieResult::Two, "two", "twos"),
+    (DieResult::Three, "three", "threes"),
+    (DieResult::Energy, "energy", "energy"),
+    (DieResult::Claw, "claw", "claws"),
+    (DieResult::Heart, "heart", "hearts"),
+];
+
+/// `FACES` in Spanish.
+const FACES_ES: [(DieResult, &str, &str); 6] = [
+    (DieResult::One, "uno", "unos"),
+    (DieResult::Two, "dos", "doses"),
+    (DieResult::Three, "tres", "treses"),
+    (DieResult::Energy, "energía", "energías"),
+    (DieResult::Claw, "garra", "garras"),
+    (DieResult::Heart, "corazón", "corazones"),
+];
+
+/// The singular name of `face` in `faces`.
+fn face_name(faces: &[(DieResult, &'static str, &'static str); 6], face: DieResult) -> &'static str {
+    faces.iter().find(|&&(f, _, _)| f == face).map_or("", |&(_, one, _)| one)
+}
+
+/// "2 claws, 3 ones and 1 heart", with the face names in `faces` and `and` before the last.
+fn describe_dice(dice: &[DieResult; 6], faces: &[(DieResult, &str, &str); 6], and: &str) -> String {
+    let parts: Vec<String> = faces.iter()
+        .filter_map(|&(face, one, many)| {
+            let count = dice.iter().filter(|&&die| die == face).count();
+            match count {
+                0 => None,
+                1 => Some(format!("1 {}", one)),
+                _ => Some(format!("{} {}", count, many)),
+            }
+        })
+        .collect();
+    match parts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} {} {}", rest.join(", "), and, last),
+        _ => parts.concat(),
+    }
+}
+
+/// The sentence `event` calls for, or None for bookkeeping events nobody needs to hear
+/// (phase timings, intermediate rolls, new rounds). Players are named as the game's
+/// `privacy` settings say, since narration usually ends up in a file.
+pub fn message(game: &Game, event: &GameEvent) -> Option<Message> {
+    let name = |id: u32| Arg::Name(game.logged_name(id));
+    let number = |n: u8| Arg::Number(n as u32);
+    let (key, args) = match *event {
+        GameEvent::GameStarted { ref preset, .. } => (MessageKey::GameStarted, vec![("preset", Arg::Name(preset.clone()))]),
+        GameEvent::PracticeStarted => (MessageKey::PracticeStarted, vec![]),
+        GameEvent::MonsterChosen { player_id, monster } => {
+            (MessageKey::MonsterChosen, vec![("player", name(player_id)), ("monster", Arg::Name(monster.name().to_string()))])
+        }
+        GameEvent::TurnStarted { player_id, hp, victory_points, .. } => {
+            (MessageKey::TurnStarted, vec![("player", name(player_id)), ("hp", number(hp)), ("points", Arg::Points(victory_points))])
+        }
+        GameEvent::DiceResolved { player_id, dice, .. } => (MessageKey::DiceResolved, vec![("player", name(player_id)), ("dice", Arg::Dice(dice))]),
+        GameEvent::DiceChanged { player_id, by, ref effect, face, rerolled, .. } => {
+            let key = if rerolled { MessageKey::DiceRerolled } else { MessageKey::DiceSet };
+            (key, vec![("by", name(by)), ("effect", Arg::Name(effect.clone())), ("player", name(player_id)), ("face", Arg::Face(face))])
+        }
+        GameEvent::TriplesScored { player_id, vp, total } => {
+            (MessageKey::TriplesScored, vec![("player", name(player_id)), ("vp", Arg::Points(vp)), ("total", Arg::Points(total))])
+        }
+        GameEvent::EnergyGained { player_id, amount, total } => {
+            (MessageKey::EnergyGained, vec![("player", name(player_id)), ("amount", number(amount)), ("total", number(total))])
+        }
+        GameEvent::Healed { player_id, amount, hp } => {
+            (MessageKey::Healed, vec![("player", name(player_id)), ("amount", number(amount)), ("hp", number(hp))])
+        }
+        GameEvent::HeartsIgnored { player_id, overtime: true } => (MessageKey::HeartsIgnoredInOvertime, vec![("player", name(player_id))]),
+        GameEvent::HeartsIgnored { player_id, overtime: false } => (MessageKey::HeartsIgnoredInTokyo, vec![("player", name(player_id))]),
+        GameEvent::AttackLaunched { player_id, damage, from_tokyo, .. } => {
+            let key = if from_tokyo { MessageKey::AttackFromTokyo } else { MessageKey::AttackIntoTokyo };
+            (key, vec![("player", name(player_id)), ("damage", number(damage))])
+        }
+        GameEvent::DamageDealt { target_id, damage, hp, source: DamageSource::Cards, card: Some(ref card), .. } => {
+            (MessageKey::CardDamage,
+             vec![("card", Arg::Name(card.clone())), ("target", name(target_id)), ("damage", number(damage)), ("hp", number(hp))])
+        }
+        GameEvent::DamageDealt { target_id, damage, hp, .. } => {
+            (MessageKey::ClawDamage, vec![("target", name(target_id)), ("damage", number(damage)), ("hp", number(hp))])
+        }
+        GameEvent::TokyoHeld { player_id, zone, vp, total } => {
+            (MessageKey::TokyoHeld,
+             vec![("player", name(player_id)), ("zone", Arg::Zone(zone)), ("vp", Arg::Points(vp)), ("total", Arg::Points(total))])
+        }
+        GameEvent::TokyoEntered { player_id, zone, vp, total } => {
+            (MessageKey::TokyoEntered,
+             vec![("player", name(player_id)), ("zone", Arg::Zone(zone)), ("vp", Arg::Points(vp)), ("total", Arg::Points(total))])
+        }
+        GameEvent::TokyoEntryDeclined { player_id } => (MessageKey::TokyoEntryDeclined, vec![("player", name(player_id))]),
+        GameEvent::TokyoConceded { player_id, zone } => (MessageKey::TokyoConceded, vec![("player", name(player_id)), ("zone", Arg::Zone(zone))]),
+        GameEvent::TokyoBayClosed { player_id: Some(player_id) } => (MessageKey::TokyoBayClosedOn, vec![("player", name(player_id))]),
+        GameEvent::TokyoBayClosed { player_id: None } => (MessageKey::TokyoBayClosed, vec![]),
+        GameEvent::TokyoVacated { zone, .. } => (MessageKey::TokyoVacated, vec![("zone", Arg::Zone(zone))]),
+        GameEvent::TokyoYielded { player_id, zone, .. } => (MessageKey::TokyoYielded, vec![("player", name(player_id)), ("zone", Arg::Zone(zone))]),
+        GameEvent::TokyoDefended { player_id, zone, .. } => (MessageKey::TokyoDefended, vec![("player", name(player_id)), ("zone", Arg::Zone(zone))]),
+        GameEvent::CardRevealed { ref card } => (MessageKey::CardRevealed, vec![("card", Arg::Name(card.clone()))]),
+        GameEvent::EvolutionDrawn { player_id, .. } => (MessageKey::EvolutionDrawn, vec![("player", name(player_id))]),
+        GameEvent::EvolutionPlayed { player_id, ref evolution, .. } => {
+            (MessageKey::EvolutionPlayed, vec![("player", name(player_id)), ("evolution", Arg::Name(evolution.clone()))])
+        }
+        GameEvent::CardPurchased { player_id, ref card, cost, .. } => {
+            (MessageKey::CardPurchased, vec![("player", name(player_id)), ("card", Arg::Name(card.clone())), ("cost", number(cost))])
+        }
+        GameEvent::ShopSwept { player_id, .. } => (MessageKey::ShopSwept, vec![("player", name(player_id))]),
+        GameEvent::VpGained { player_id, amount, total, .. } => {
+            (MessageKey::VpGained, vec![("player", name(player_id)), ("amount", Arg::Points(amount)), ("total", Arg::Points(total))])
+        }
+        GameEvent::DamageBlocked { player_id, ref blocked_by, .. } => {
+            (MessageKey::DamageBlocked, vec![("player", name(player_id)), ("effect", Arg::Name(blocked_by.clone()))])
+        }
+        GameEvent::PseudoCardBought { player_id, ref card, times, spent, .. } => {
+            (MessageKey::PseudoCardBought,
+             vec![("player", name(player_id)), ("card", Arg::Name(card.clone())), ("times", number(times)), ("spent", number(spent))])
+        }
+        GameEvent::OvertimeStarted { .. } => (MessageKey::OvertimeStarted, vec![]),
+        GameEvent::TurnSkipped { player_id } => (MessageKey::TurnSkipped, vec![("player", name(player_id))]),
+        GameEvent::SkipScheduled { player_id } => (MessageKey::SkipScheduled, vec![("player", name(player_id))]),
+        GameEvent::PlayerEliminated { player_id, .. } => (MessageKey::PlayerEliminated, vec![("player", name(player_id))]),
+        GameEvent::ExtraTurnGranted { player_id } => (MessageKey::ExtraTurnGranted, vec![("player", name(player_id))]),
+        GameEvent::MaxHpChanged { player_id, max_hp, .. } => (MessageKey::MaxHpChanged, vec![("player", name(player_id)), ("max_hp", number(max_hp))]),
+        GameEvent::MaxVpChanged { player_id, max_vp } => (MessageKey::MaxVpChanged, vec![("player", name(player_id)), ("max_vp", Arg::Points(max_vp))]),
+        GameEvent::GameOver { victory: Victory::Points { player_id, .. } } => (MessageKey::WonOnPoints, vec![("player", name(player_id))]),
+        GameEvent::GameOver { victory: Victory::LastStanding { player_id } } => (MessageKey::LastStanding, vec![("player", name(player_id))]),
+        GameEvent::GameOver { victory: Victory::NoSurvivors } => (MessageKey::NoSurvivors, vec![]),
+        GameEvent::RoundStarted | GameEvent::PhaseEnded { .. } | GameEvent::DiceRolled { .. } => return None,
+    };
+    Some(Message { key, args })
+}
+
+/// One spoken sentence for `event` in `locale`, or None for bookkeeping events.
+pub fn narrate_in(locale: Locale, game: &Game, event: &GameEvent) -> Option<String> {
+    message(game, event).map(|message| locale.render(&message))
+}
+
+/// One spoken sentence for `event` in English, or None for bookkeeping events.
+pub fn narrate(game: &Game, event: &GameEvent) -> Option<String> {
+    narrate_in(Locale::English, game, event)
+}
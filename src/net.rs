@@ -0,0 +1,83 @@
+//! The protocol for network play: one JSON message per line over TCP. The host owns the
+//! authoritative `Game`; each client sends `Join` once, then answers every `Decide` it is
+//! sent with an `Act`. Clients can't change the game any other way, and only ever see it
+//! through views (see `Game::view_json`), so they can't work out the dice to come either.
+
+use crate::{Action, Decision, Victory};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+
+/// Version of the protocol this build speaks, sent in every `Welcome`. Bump it whenever a
+/// message changes so that older clients or hosts can't read it.
+///
+/// - 1: `State` carries a view rather than the whole save, and `Welcome` names the version.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Messages from the host to a client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    /// The game has started and this client plays `player_id`, in version
+    /// `protocol_version` of the protocol (0 from hosts older than `PROTOCOL_VERSION` 1).
+    Welcome {
+        player_id: u32,
+        #[serde(default)]
+        protocol_version: u32,
+    },
+    /// The game as it stands, as this client may see it (see `Game::view_json`). Sent
+    /// before every decision, so clients can show what happened since the last one.
+    State { game: String },
+    /// The game is waiting on `player_id`'s answer to `decision`. Only that player's client
+    /// is asked, so a Tokyo occupant decides whether to yield even on someone else's turn.
+    Decide { player_id: u32, decision: Decision },
+    /// The last message was refused; a `Decide` that was refused is sent again.
+    Rejected { reason: String },
+    /// The game is over; the `State` before this one is final.
+    GameOver { victory: Option<Victory> },
+}
+
+/// Messages from a client to the host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Asks for a seat under `name`. Only valid as the first message.
+    Join { name: String },
+    /// Answers the last `Decide`.
+    Act { action: Action },
+}
+
+/// A TCP connection carrying one JSON message per line.
+#[derive(Debug)]
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        Ok(Connection { reader: BufReader::new(stream.try_clone()?), writer: stream })
+    }
+
+    /// The address of the other end.
+    pub fn peer(&self) -> io::Result<std::net::SocketAddr> {
+        self.writer.peer_addr()
+    }
+
+    pub fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// Waits for the next message. A closed connection is `UnexpectedEof`.
+    pub fn receive<T: DeserializeOwned>(&mut self) -> io::Result<T> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the connection was closed"));
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+}
@@ -0,0 +1,68 @@
+//! Where frontends keep their files: saves, profiles, bot packs and series standings all go
+//! in one data directory, in the place each platform expects (XDG on Linux, AppData on
+//! Windows, Application Support on macOS) rather than wherever the game was started.
+
+use crate::FileStorage;
+use std::path::{Path, PathBuf};
+
+/// The directory a frontend keeps its files in, found with `DataDir::locate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    /// Name of the game's own folder inside the platform's data directory.
+    pub const APP_FOLDER: &'static str = "kingoftokyo";
+
+    /// Environment variable that overrides the platform's directory.
+    pub const ENV_VAR: &'static str = "KINGOFTOKYO_DATA_DIR";
+
+    /// A data directory at `root`, used as given.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DataDir { root: root.into() }
+    }
+
+    /// The directory to use: `overridden` if given (e.g. from a command-line flag), else
+    /// `KINGOFTOKYO_DATA_DIR`, else the platform's own, else the working directory.
+    pub fn locate(overridden: Option<&Path>) -> Self {
+        if let Some(root) = overridden {
+            return DataDir::new(root);
+        }
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        if let Some(root) = var(DataDir::ENV_VAR) {
+            return DataDir::new(root);
+        }
+        DataDir::for_platform(std::env::consts::OS, var).unwrap_or_else(|| DataDir::new("."))
+    }
+
+    /// The game's folder in the data directory that `os` (as in `std::env::consts::OS`)
+    /// expects, reading environment variables through `var`. None if the variables it
+    /// needs aren't set.
+    pub fn for_platform(os: &str, var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let base = match os {
+            "windows" => PathBuf::from(var("APPDATA")?),
+            "macos" => PathBuf::from(var("HOME")?).join("Library").join("Application Support"),
+            _ => match var("XDG_DATA_HOME") {
+                Some(data_home) => PathBuf::from(data_home),
+                None => PathBuf::from(var("HOME")?).join(".local").join("share"),
+            },
+        };
+        Some(DataDir::new(base.join(DataDir::APP_FOLDER)))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// A `FileStorage` rooted here, for profiles, bot packs, standings and saves by key.
+    pub fn storage(&self) -> FileStorage {
+        FileStorage::new(&self.root)
+    }
+
+    /// Where a save file called `name` goes: in the `saves` folder, next to the saves kept
+    /// through `storage`.
+    pub fn save_file(&self, name: &str) -> PathBuf {
+        self.root.join(crate::Game::SAVES).join(name)
+    }
+}
@@ -0,0 +1,141 @@
+//! Bot identities: a name, a few catchphrases and an avatar for each bot at the table, so
+//! games full of bots don't feel like playing spreadsheets. Identities come in packs, one
+//! language each. Two packs are built in, and more can be kept as JSON in a `Storage`.
+
+#[cfg(feature = "storage")]
+use crate::Storage;
+use crate::{ConfigError, ConfigIssue};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "storage")]
+use std::io;
+
+/// Who a bot plays as. Purely flavor: the bot's strategy comes from its `AgentKind`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotIdentity {
+    pub name: String,
+    #[serde(default)]
+    pub catchphrases: Vec<String>, // Said as emotes at big moments
+    #[serde(default)]
+    pub avatar: String,            // An emoji, or an image name for graphical frontends
+}
+
+impl BotIdentity {
+    /// Catchphrase number `n`, wrapping around, if the identity has any.
+    pub fn catchphrase(&self, n: usize) -> Option<&str> {
+        self.catchphrases.get(n % self.catchphrases.len().max(1)).map(|line| line.as_str())
+    }
+}
+
+/// A set of bot identities in one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotPack {
+    pub name: String,
+    pub language: String, // Language tag, e.g. "en"
+    pub identities: Vec<BotIdentity>,
+}
+
+fn identity(name: &str, avatar: &str, catchphrases: &[&str]) -> BotIdentity {
+    BotIdentity { name: name.to_string(), catchphrases: catchphrases.iter().map(|line| line.to_string()).collect(), avatar: avatar.to_string() }
+}
+
+impl BotPack {
+    /// Collection that `save_to` and `load_from` use.
+    pub const PACKS: &'static str = "bot_packs";
+
+    /// Names of the packs every build has.
+    pub const BUILTIN: [&'static str; 2] = ["classic", "clasico"];
+
+    /// A built-in pack by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (language, identities) = match name {
+            "classic" => ("en", vec![
+                identity("Gigaclaw", "🦖", &["Tokyo looks tasty today.", "Is that all you've got?", "Stomp first, ask later."]),
+                identity("Professor Fizz", "🧪", &["Fascinating. Again!", "For science!", "My calculations were correct."]),
+                identity("Mecha Molly", "🤖", &["Target acquired.", "Rebooting rage module.", "Beep boop, you're toast."]),
+                identity("Old Barnacle", "🐙", &["Back in my day, Tokyo was smaller.", "Tentacles up!", "I've eaten worse."]),
+                identity("Sparkwing", "🦋", &["Shiny!", "Catch me if you can!", "Ooh, a skyscraper."]),
+                identity("Grumblerock", "🪨", &["Hmph.", "Rocks don't yield.", "Slow and steady smashes the city."]),
+            ]),
+            "clasico" => ("es", vec![
+                identity("Garragigante", "🦖", &["Tokio se ve delicioso hoy.", "¿Eso es todo?", "Primero piso, luego pregunto."]),
+                identity("Profesora Chispa", "🧪", &["¡Fascinante! ¡Otra vez!", "¡Por la ciencia!", "Mis cálculos eran correctos."]),
+                identity("Robo Rita", "🤖", &["Objetivo fijado.", "Reiniciando módulo de furia.", "Bip bup, estás frito."]),
+                identity("Viejo Percebe", "🐙", &["En mis tiempos, Tokio era más pequeño.", "¡Tentáculos arriba!", "He comido cosas peores."]),
+                identity("Alachispa", "🦋", &["¡Qué brillante!", "¡Atrápame si puedes!", "Mmm, un rascacielos."]),
+                identity("Gruñipiedra", "🪨", &["Hmph.", "Las rocas no se rinden.", "Despacio y con buena letra se aplasta la ciudad."]),
+            ]),
+            _ => return None,
+        };
+        Some(BotPack { name: name.to_string(), language: language.to_string(), identities })
+    }
+
+    /// The built-in pack for `language`, falling back to English.
+    pub fn for_language(language: &str) -> Self {
+        BotPack::BUILTIN.iter()
+            .filter_map(|name| BotPack::builtin(name))
+            .find(|pack| pack.language.eq_ignore_ascii_case(language))
+            .unwrap_or_else(|| BotPack::builtin("classic").expect("The classic pack is built in"))
+    }
+
+    /// Refuses packs whose bots couldn't be told apart, listing every problem at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+        if self.identities.is_empty() {
+            issues.push(ConfigIssue::new("identities", "the pack has no identities".to_string(), "add at least one".to_string()));
+        }
+        for (i, identity) in self.identities.iter().enumerate() {
+            let field = format!("identities[{}].name", i + 1);
+            if identity.name.trim().is_empty() {
+                issues.push(ConfigIssue::new(&field, "the name is blank".to_string(), "give every identity a name".to_string()));
+            } else if let Some(first) = self.identities[..i].iter().position(|other| other.name.eq_ignore_ascii_case(&identity.name)) {
+                issues.push(ConfigIssue::new(&field, format!("\"{}\" is already identity {}'s name", identity.name, first + 1),
+                                             "give every identity a different name".to_string()));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { source: format!("bot pack \"{}\"", self.name), issues })
+        }
+    }
+
+    /// Picks `count` different identities in a shuffled order, skipping any whose name is
+    /// already `taken` by someone at the table. Fewer come back if the pack runs out.
+    pub fn assign(&self, count: usize, taken: &[&str], seed: u64) -> Vec<BotIdentity> {
+        let mut free: Vec<&BotIdentity> = self.identities.iter()
+            .filter(|identity| !taken.iter().any(|name| name.eq_ignore_ascii_case(&identity.name)))
+            .collect();
+        free.shuffle(&mut ChaCha12Rng::seed_from_u64(seed));
+        free.into_iter().take(count).cloned().collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Bot packs are always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the pack in `storage` under its name.
+    #[cfg(feature = "storage")]
+    pub fn save_to(&self, storage: &mut dyn Storage) -> io::Result<()> {
+        storage.put(BotPack::PACKS, &self.name, &self.to_json())
+    }
+
+    /// The pack called `name`: a built-in one, or else one kept in `storage`.
+    #[cfg(feature = "storage")]
+    pub fn load_from(storage: &dyn Storage, name: &str) -> io::Result<Option<Self>> {
+        if let Some(pack) = BotPack::builtin(name) {
+            return Ok(Some(pack));
+        }
+        match storage.get(BotPack::PACKS, name)? {
+            Some(json) => Ok(Some(BotPack::from_json(&json)?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Pre-game matchup prediction: each seat's chance of winning before the first roll, from
+//! quick bot simulations of the actual table (seating, monsters and rules) blended with how
+//! the players and monsters have done before. Every prediction is kept next to the result
+//! it turned out to have, and that record decides how far the blend trusts past results.
+
+#[cfg(feature = "storage")]
+use crate::Storage;
+use crate::{simulate_from, AgentKind, ConfigError, ConfigIssue, Game, Monster, Victory};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "storage")]
+use std::io;
+
+/// Simulated games behind a prediction unless a frontend asks for another number.
+pub const PREDICTION_GAMES: usize = 200;
+
+/// Recorded games needed before the history weight is fitted to them rather than taken
+/// from `DEFAULT_HISTORY_WEIGHT`.
+pub const MIN_CALIBRATION_GAMES: usize = 20;
+
+/// How far predictions trust past results over simulations until there are enough
+/// recorded games to fit it.
+pub const DEFAULT_HISTORY_WEIGHT: f64 = 0.25;
+
+/// Most recorded predictions kept; older ones are dropped first.
+pub const MAX_OUTCOMES: usize = 1000;
+
+/// Games' worth of an average record every player and monster starts with, so a couple of
+/// lucky wins don't make a favourite.
+const PRIOR_GAMES: f64 = 2.0;
+
+/// How one player (by name) or monster has done in recorded games.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Track {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub fair_share: f64, // Wins expected from the table sizes alone: 1/players per game
+}
+
+impl Track {
+    /// Wins against the fair share, pulled towards 1 (average) while there are few games.
+    pub fn strength(&self) -> f64 {
+        (self.wins as f64 + PRIOR_GAMES) / (self.fair_share + PRIOR_GAMES)
+    }
+}
+
+/// One seat's prediction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatPrediction {
+    pub name: String,
+    pub monster: Option<Monster>,
+    pub simulated: f64, // Share of the simulated games this seat won
+    pub history: f64,   // Chance from the player's and monster's past results
+    pub chance: f64,    // The two blended by the history weight
+}
+
+/// Every seat's chance of winning a game about to start, seat 1 first. Chances add up to
+/// less than 1 by the share of games expected to end with no survivors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Prediction {
+    pub games: usize,        // Simulated games behind `simulated`
+    pub history_weight: f64, // From 0 (simulations only) to 1 (past results only)
+    pub seats: Vec<SeatPrediction>,
+}
+
+impl fmt::Display for Prediction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Predicted chances ({} simulated games, past results weighted {:.0}%):", self.games, self.history_weight * 100.0)?;
+        for (seat, prediction) in self.seats.iter().enumerate() {
+            let monster = prediction.monster.map_or(String::new(), |monster| format!(" ({})", monster.name()));
+            writeln!(f, "  Seat {} {}{}: {:>5.1}% (simulated {:.1}%, past results {:.1}%)", seat + 1, prediction.name, monster,
+                     prediction.chance * 100.0, prediction.simulated * 100.0, prediction.history * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// A recorded prediction and how the game went.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Outcome {
+    pub simulated: Vec<f64>,   // Per seat, as predicted
+    pub history: Vec<f64>,     // Per seat, as predicted
+    pub chances: Vec<f64>,     // Per seat, the blend that was shown
+    pub winner: Option<usize>, // Seat from 0; None if no monster survived
+}
+
+impl Outcome {
+    /// Brier score of `chances` for this game: the squared misses summed over the seats.
+    /// 0 is a sure and right prediction; lower is better.
+    fn brier(&self, chances: &[f64]) -> f64 {
+        chances.iter().enumerate().map(|(seat, chance)| (chance - (self.winner == Some(seat)) as u8 as f64).powi(2)).sum()
+    }
+
+    fn blend(&self, weight: f64) -> Vec<f64> {
+        self.simulated.iter().zip(&self.history).map(|(simulated, history)| (1.0 - weight) * simulated + weight * history).collect()
+    }
+}
+
+/// How well recorded predictions did.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Calibration {
+    pub games: usize,
+    pub brier: f64,          // Average Brier score of the chances shown
+    pub uniform_brier: f64,  // The same for an even chance per seat, to compare against
+    pub history_weight: f64, // What the next prediction will use
+}
+
+impl fmt::Display for Calibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} predicted games: Brier score {:.3} (even chances would score {:.3}); past results now weighted {:.0}%",
+                 self.games, self.brier, self.uniform_brier, self.history_weight * 100.0)
+    }
+}
+
+/// Past results per player and per monster, and every prediction recorded with its outcome.
+/// Kept as JSON in a `Storage` so predictions improve from one game night to the next.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PredictionLog {
+    pub players: Vec<Track>,
+    pub monsters: Vec<Track>,
+    pub outcomes: Vec<Outcome>, // Oldest first, at most `MAX_OUTCOMES`
+}
+
+impl PredictionLog {
+    /// Collection that `save_to` and `load_from` use.
+    pub const PREDICTIONS: &'static str = "predictions";
+    /// Key of the log in `PREDICTIONS`.
+    pub const LOG: &'static str = "log";
+
+    fn track<'a>(tracks: &'a [Track], name: &str) -> Option<&'a Track> {
+        tracks.iter().find(|track| track.name == name)
+    }
+
+    fn track_mut<'a>(tracks: &'a mut Vec<Track>, name: &str) -> &'a mut Track {
+        match tracks.iter().position(|track| track.name == name) {
+            Some(i) => &mut tracks[i],
+            None => {
+                tracks.push(Track { name: name.to_string(), games: 0, wins: 0, fair_share: 0.0 });
+                tracks.last_mut().expect("Pushed above")
+            }
+        }
+    }
+
+    /// The history weight with the lowest Brier score over the recorded games, in steps of
+    /// 5%, or `DEFAULT_HISTORY_WEIGHT` until `MIN_CALIBRATION_GAMES` are recorded.
+    pub fn history_weight(&self) -> f64 {
+        if self.outcomes.len() < MIN_CALIBRATION_GAMES {
+            return DEFAULT_HISTORY_WEIGHT;
+        }
+        let score = |weight: f64| self.outcomes.iter().map(|outcome| outcome.brier(&outcome.blend(weight))).sum::<f64>();
+        (0..=20).map(|step| step as f64 / 20.0)
+            .min_by(|&a, &b| score(a).total_cmp(&score(b)))
+            .expect("The steps aren't empty")
+    }
+
+    pub fn calibration(&self) -> Calibration {
+        let games = self.outcomes.len();
+        let average = |total: f64| total / games.max(1) as f64;
+        Calibration {
+            games,
+            brier: average(self.outcomes.iter().map(|outcome| outcome.brier(&outcome.chances)).sum()),
+            uniform_brier: average(self.outcomes.iter().map(|outcome| {
+                outcome.brier(&vec![1.0 / outcome.chances.len() as f64; outcome.chances.len()])
+            }).sum()),
+            history_weight: self.history_weight(),
+        }
+    }
+
+    /// Predicts `game`, set up but not started, by playing `games` games on from it with
+    /// the bots in `seats` (one per seat; stand-ins for humans too), and weighing in each
+    /// player's and monster's past results. Simulations are seeded from the game's own seed,
+    /// so a game with a fixed seed always gets the same prediction from the same log.
+    pub fn predict(&self, game: &Game, seats: &[AgentKind], games: usize) -> Result<Prediction, ConfigError> {
+        let mut issues = Vec::new();
+        if games == 0 {
+            issues.push(ConfigIssue::new("games", "no games to simulate".to_string(), "simulate at least one game".to_string()));
+        }
+        if game.turn > 0 {
+            issues.push(ConfigIssue::new("position", format!("the game is already on turn {}", game.turn), "predict before the first turn".to_string()));
+        }
+        if game.practice {
+            issues.push(ConfigIssue::new("position", "dice are set by hand in practice games".to_string(), "predict ranked games only".to_string()));
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError { source: "the prediction".to_string(), issues });
+        }
+        let report = simulate_from(games, seats, game, game.seed)?;
+        let simulated: Vec<f64> = (0..seats.len()).map(|seat| report.win_rate(seat)).collect();
+
+        let strengths: Vec<f64> = game.players.iter().map(|player| {
+            let monster = player.monster.and_then(|monster| PredictionLog::track(&self.monsters, monster.name()));
+            PredictionLog::track(&self.players, &player.name).map_or(1.0, Track::strength) * monster.map_or(1.0, Track::strength)
+        }).collect();
+        // Past results share out the games the simulations expect someone to win
+        let decided: f64 = simulated.iter().sum();
+        let decided = if decided > 0.0 { decided } else { 1.0 };
+        let total_strength: f64 = strengths.iter().sum();
+
+        let weight = self.history_weight();
+        let seats = game.players.iter().zip(simulated).zip(strengths)
+            .map(|((player, simulated), strength)| {
+                let history = strength / total_strength * decided;
+                SeatPrediction {
+                    name: player.name.clone(),
+                    monster: player.monster,
+                    simulated,
+                    history,
+                    chance: (1.0 - weight) * simulated + weight * history,
+                }
+            })
+            .collect();
+        Ok(Prediction { games, history_weight: weight, seats })
+    }
+
+    /// Adds a finished game and what was predicted for it: to the players' and monsters'
+    /// records, and to the outcomes the history weight is fitted to. Unfinished and practice
+    /// games don't count, and neither do predictions made for another table. Returns
+    /// whether it was counted.
+    pub fn record(&mut self, prediction: &Prediction, game: &Game) -> bool {
+        let Some(victory) = game.victory() else {
+            return false;
+        };
+        let same_table = prediction.seats.len() == game.players.len()
+            && prediction.seats.iter().zip(&game.players).all(|(seat, player)| seat.name == player.name && seat.monster == player.monster);
+        if game.practice || !same_table {
+            return false;
+        }
+        let winner = match victory {
+            Victory::Points { player_id, .. } | Victory::LastStanding { player_id } => game.players.iter().position(|player| player.id == player_id),
+            Victory::NoSurvivors => None,
+        };
+
+        let fair_share = 1.0 / game.players.len() as f64;
+        for (seat, player) in game.players.iter().enumerate() {
+            let mut tracks = vec![PredictionLog::track_mut(&mut self.players, &player.name)];
+            if let Some(monster) = player.monster {
+                tracks.push(PredictionLog::track_mut(&mut self.monsters, monster.name()));
+            }
+            for track in tracks {
+                track.games += 1;
+                track.wins += (winner == Some(seat)) as u32;
+                track.fair_share += fair_share;
+            }
+        }
+
+        self.outcomes.push(Outcome {
+            simulated: prediction.seats.iter().map(|seat| seat.simulated).collect(),
+            history: prediction.seats.iter().map(|seat| seat.history).collect(),
+            chances: prediction.seats.iter().map(|seat| seat.chance).collect(),
+            winner,
+        });
+        if self.outcomes.len() > MAX_OUTCOMES {
+            self.outcomes.drain(..self.outcomes.len() - MAX_OUTCOMES);
+        }
+        true
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Prediction logs are always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the log in `storage`.
+    #[cfg(feature = "storage")]
+    pub fn save_to(&self, storage: &mut dyn Storage) -> io::Result<()> {
+        storage.put(PredictionLog::PREDICTIONS, PredictionLog::LOG, &self.to_json())
+    }
+
+    /// The saved log, or an empty one if nothing has been recorded yet.
+    #[cfg(feature = "storage")]
+    pub fn load_from(storage: &dyn Storage) -> io::Result<Self> {
+        match storage.get(PredictionLog::PREDICTIONS, PredictionLog::LOG)? {
+            Some(json) => Ok(PredictionLog::from_json(&json)?),
+            None => Ok(PredictionLog::default()),
+        }
+    }
+}
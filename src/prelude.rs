@@ -0,0 +1,17 @@
+//! The types nearly every bot and frontend needs, for one glob import:
+//! `use kingoftokyo::prelude::*;`. Everything here is the stable surface of the crate: the
+//! game and its setup, the decisions it asks for and the actions that answer them, the
+//! events it logs, the agent traits, and `GameView`, the read-only look at a game in
+//! progress (its players, Tokyo, shop, history and pending decision). `Game` itself keeps
+//! public fields for setting games up and tooling, so hand code that should only read a
+//! game a `GameView`. The enums among them that grow as rules, monsters or bots are added
+//! (events, decisions, errors, monsters, damage and VP sources, `Progress` and
+//! `AgentKind`) are `#[non_exhaustive]`, so a new variant isn't a breaking change; matches
+//! on them need a `_` arm. The same goes for the setup structs, which are built
+//! from a preset or JSON rather than field by field.
+
+pub use crate::{
+    Action, AdvanceError, Agent, AgentKind, Capabilities, Card, ConfigError, DamageSource, Decision, DieResult, Engine, Game,
+    GameConfig, GameError, GameEvent, GameView, Monster, Player, PlayerController, Progress, PseudoCard, Replay, RuleSet,
+    ShopAction, TimedEvent, Tokyo, TokyoZone, Victory, VpSource,
+};
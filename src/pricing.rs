@@ -0,0 +1,88 @@
+//! What a power card costs a particular buyer. The price is a stack worked out in a fixed
+//! order: the printed cost, then the game's economy (`rules.economy.card_cost_percent`),
+//! then each of the buyer's effects through `GameEffect::modify_card_cost`, in the order
+//! `Game::effects_of` lists them. The result never drops below 0 unless one of the buyer's
+//! effects sets a lower floor with `GameEffect::card_cost_floor`; a price below 0 pays the
+//! buyer that much Energy.
+
+use crate::{Card, Game};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One layer of the stack that changed the price.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostStep {
+    pub source: String, // What changed it: the economy, an effect's name, or the floor
+    pub change: i16,
+    pub cost: i16, // The price after this step
+}
+
+/// A card's price for one buyer, with every step that moved it off the printed cost.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardCost {
+    pub printed: u8,
+    pub steps: Vec<CostStep>, // Only the steps that changed something, in the order applied
+    pub total: i16,
+}
+
+impl CardCost {
+    /// How the economy's step is named, e.g. "150% prices".
+    pub fn economy_source(percent: u16) -> String {
+        format!("{}% prices", percent)
+    }
+
+    /// Whether a buyer holding `energy` can pay it.
+    pub fn affordable(&self, energy: u8) -> bool {
+        self.total <= energy as i16
+    }
+
+    /// The Energy the buyer hands over: the total, or 0 when the card pays them instead.
+    pub fn energy(&self) -> u8 {
+        self.total.clamp(0, u8::MAX as i16) as u8
+    }
+
+    /// The Energy the buyer gets back for a price below 0.
+    pub fn refund(&self) -> u8 {
+        (-self.total).clamp(0, u8::MAX as i16) as u8
+    }
+
+    /// Moves the price to `cost` on account of `source`, if that changes it.
+    pub(crate) fn apply(&mut self, cost: i16, source: impl FnOnce() -> String) {
+        if cost != self.total {
+            self.steps.push(CostStep { source: source(), change: cost - self.total, cost });
+            self.total = cost;
+        }
+    }
+}
+
+impl fmt::Display for CardCost {
+    /// "6 printed, +3 (150% prices), -1 (Discount) = 8", or just "6" when nothing
+    /// changed the printed cost.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "{}", self.total);
+        }
+        write!(f, "{} printed", self.printed)?;
+        for step in &self.steps {
+            write!(f, ", {:+} ({})", step.change, step.source)?;
+        }
+        write!(f, " = {}", self.total)
+    }
+}
+
+impl Game {
+    /// What `card` costs `player_id` right now, step by step.
+    pub fn card_cost(&self, player_id: u32, card: &Card) -> CardCost {
+        let mut price = CardCost { printed: card.cost, steps: Vec::new(), total: card.cost as i16 };
+        let economy = &self.rules.economy;
+        price.apply(economy.card_cost(card) as i16, || CardCost::economy_source(economy.card_cost_percent));
+        let effects = self.effects_of(player_id);
+        for effect in &effects {
+            let cost = effect.modify_card_cost(self, player_id, card, price.total);
+            price.apply(cost, || effect.name().to_string());
+        }
+        let floor = effects.iter().filter_map(|effect| effect.card_cost_floor(self, player_id, card)).min().unwrap_or(0);
+        price.apply(price.total.max(floor), || "floor".to_string());
+        price
+    }
+}
@@ -0,0 +1,35 @@
+//! Dice odds, and how far a roll beat or fell short of them.
+
+use crate::DieResult;
+
+/// Faces on a die.
+const FACES: usize = 6;
+
+/// The chance that one die lands on any of `faces` different faces.
+pub fn face_chance(faces: usize) -> f64 {
+    faces.min(FACES) as f64 / FACES as f64
+}
+
+/// How many of `dice` rolled dice are expected to land on any of `faces` different faces.
+pub fn expected_hits(dice: usize, faces: usize) -> f64 {
+    dice as f64 * face_chance(faces)
+}
+
+/// How lucky a reroll was, given the `dice` it left and which of them were `kept` going
+/// into it. The kept faces are taken as what the player was after, so the luck is how
+/// many rerolled dice landed on one of them, less how many were expected to. None when
+/// nothing was kept or nothing was rerolled, since the roll then had no clear target.
+pub fn roll_luck(dice: &[DieResult; 6], kept: &[bool; 6]) -> Option<f64> {
+    let mut wanted: Vec<DieResult> = Vec::new();
+    for (&die, _) in dice.iter().zip(kept).filter(|(_, &kept)| kept) {
+        if !wanted.contains(&die) {
+            wanted.push(die);
+        }
+    }
+    let rerolled: Vec<DieResult> = dice.iter().zip(kept).filter(|(_, &kept)| !kept).map(|(&die, _)| die).collect();
+    if wanted.is_empty() || rerolled.is_empty() {
+        return None;
+    }
+    let hits = rerolled.iter().filter(|die| wanted.contains(die)).count();
+    Some(hits as f64 - expected_hits(rerolled.len(), wanted.len()))
+}
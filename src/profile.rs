@@ -0,0 +1,122 @@
+//! Per-player preferences for frontends: keys, look, hints and language. A profile is a
+//! small JSON document kept in a `Storage`, so copying the file (or serving it from a
+//! shared store) brings a player's setup along to another machine.
+
+#[cfg(feature = "storage")]
+use crate::Storage;
+use crate::{ConfigError, ConfigIssue};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "storage")]
+use std::io;
+
+/// How a frontend decorates its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Emoji, // Icons in front of events and prompts
+    Ascii, // Plain text only, for terminals and fonts without emoji
+}
+
+/// The answers a player types at prompts. Matched ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub yes: String,
+    pub no: String,
+    pub keep_all: String, // Keep every die and stop rolling
+    pub sweep: String,    // Sweep the shop
+    pub evolve: String,   // Prefix for playing an evolution from hand, e.g. "e1"
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings { yes: "y".to_string(), no: "n".to_string(), keep_all: "all".to_string(), sweep: "s".to_string(), evolve: "e".to_string() }
+    }
+}
+
+impl KeyBindings {
+    /// Every binding with the name of its field, for checking and listing.
+    pub fn all(&self) -> [(&'static str, &str); 5] {
+        [("yes", &self.yes), ("no", &self.no), ("keep_all", &self.keep_all), ("sweep", &self.sweep), ("evolve", &self.evolve)]
+    }
+}
+
+/// One player's preferences. Fields missing from a stored profile take their defaults,
+/// so profiles written by older versions (or by hand) still load.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub keys: KeyBindings,
+    pub theme: Theme,
+    pub hints: bool,      // Show examples and reminders in prompts, and why a default was suggested
+    pub advisor: bool,    // Default yes/no answers suggested by `advise` rather than fixed ones
+    pub language: String, // Language tag, e.g. "en"
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile { name: String::new(), keys: KeyBindings::default(), theme: Theme::default(), hints: true, advisor: true, language: "en".to_string() }
+    }
+}
+
+impl Profile {
+    /// Collection that `save_to` and `load_from` use.
+    pub const PROFILES: &'static str = "profiles";
+
+    /// Languages frontends can render. Other tags are kept but shown in English.
+    pub const LANGUAGES: [&'static str; 2] = ["en", "es"];
+
+    /// A profile with every preference at its default.
+    pub fn new(name: &str) -> Self {
+        Profile { name: name.to_string(), ..Profile::default() }
+    }
+
+    /// Refuses key bindings that can't be told apart, listing every problem at once.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+        let keys = self.keys.all();
+        for (i, (field, key)) in keys.iter().enumerate() {
+            let field = format!("keys.{}", field);
+            if key.trim().is_empty() {
+                issues.push(ConfigIssue::new(&field, "the key is blank".to_string(), "bind it to a letter or word".to_string()));
+            } else if key.chars().all(|c| c.is_ascii_digit()) {
+                issues.push(ConfigIssue::new(&field, format!("\"{}\" is a number, which prompts read as a die or card", key),
+                                             "bind it to a letter or word".to_string()));
+            } else if let Some((other, _)) = keys[..i].iter().find(|(_, other)| other.eq_ignore_ascii_case(key)) {
+                issues.push(ConfigIssue::new(&field, format!("\"{}\" is already bound to keys.{}", key, other),
+                                             "give every action a different key".to_string()));
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { source: format!("profile \"{}\"", self.name), issues })
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Profiles are always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the profile in `storage` under its name.
+    #[cfg(feature = "storage")]
+    pub fn save_to(&self, storage: &mut dyn Storage) -> io::Result<()> {
+        storage.put(Profile::PROFILES, &self.name, &self.to_json())
+    }
+
+    /// Loads the profile called `name`, if there is one.
+    #[cfg(feature = "storage")]
+    pub fn load_from(storage: &dyn Storage, name: &str) -> io::Result<Option<Self>> {
+        match storage.get(Profile::PROFILES, name)? {
+            Some(json) => Ok(Some(Profile::from_json(&json)?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Replays: everything needed to play a game again exactly as it went. Every die comes
+//! from the seeded RNG streams, so a replay only has to record the setup and the players'
+//! answers to each decision, in order.
+
+use crate::{save_checksum, Action, AdvanceError, ConfigError, Decision, Engine, Game, GameConfig, Monster, Progress, ShopAction};
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A recorded game: its setup and every action taken, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub config: GameConfig,
+    pub players: Vec<String>,           // In seat order
+    pub monsters: Vec<Option<Monster>>, // Per seat
+    pub actions: Vec<Action>,
+    #[serde(default)]
+    pub practice: bool,                 // Dice were set by hand; see `Game::enable_practice`
+}
+
+impl Replay {
+    /// Version of the replay file format written by `to_json`.
+    pub const FORMAT_VERSION: u32 = 2;
+
+    /// The replay of `game` so far, with players named as its `privacy` settings say. None
+    /// for games resumed from saves older than action recording, whose early moves were
+    /// never kept.
+    pub fn from_game(game: &Game) -> Option<Self> {
+        Some(Replay {
+            seed: game.seed,
+            config: game.config(),
+            players: game.players.iter().map(|p| game.logged_name(p.id)).collect(),
+            monsters: game.players.iter().map(|p| p.monster).collect(),
+            actions: game.actions.clone()?,
+            practice: game.practice,
+        })
+    }
+
+    /// The replay as JSON, tagged with its format and engine version and checksummed like
+    /// a save, so an edited or truncated replay is refused on load.
+    pub fn to_json(&self) -> String {
+        let mut replay = serde_json::to_value(self).expect("Replays are always serializable");
+        replay["format_version"] = Replay::FORMAT_VERSION.into();
+        replay["engine_version"] = Engine::VERSION.into();
+        replay["checksum"] = format!("{:016x}", save_checksum(&replay)).into();
+        replay.to_string()
+    }
+
+    /// Reads a replay written by `to_json`.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Replay::from_value(serde_json::from_str(json)?)
+    }
+
+    /// Reads a replay written by `to_json` straight from `reader`, without holding the
+    /// text in memory first.
+    pub fn from_reader(reader: impl io::Read) -> serde_json::Result<Self> {
+        Replay::from_value(serde_json::from_reader(reader)?)
+    }
+
+    fn from_value(mut replay: serde_json::Value) -> serde_json::Result<Self> {
+        use serde::de::Error;
+        let Some(fields) = replay.as_object_mut() else {
+            return Err(serde_json::Error::custom("a replay must be a JSON object"));
+        };
+        let checksum = fields.remove("checksum");
+        let expected = format!("{:016x}", save_checksum(&replay));
+        if checksum.as_ref().and_then(|c| c.as_str()) != Some(expected.as_str()) {
+            return Err(serde_json::Error::custom("replay checksum doesn't match: the file was damaged or edited"));
+        }
+        let version = replay.get("format_version").and_then(|v| v.as_u64()).unwrap_or(0);
+        if version > Replay::FORMAT_VERSION as u64 {
+            return Err(serde_json::Error::custom(format!(
+                "replay format version {} is newer than this build supports ({})",
+                version,
+                Replay::FORMAT_VERSION
+            )));
+        }
+        serde_json::from_value(replay)
+    }
+
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(Replay::from_json(&json)?)
+    }
+
+    /// Sets the game up again as it stood before the first turn, ready to play back.
+    pub fn playback(&self) -> Result<Playback<'_>, ConfigError> {
+        let names: Vec<&str> = self.players.iter().map(|name| name.as_str()).collect();
+        let mut game = Game::new(&names, self.seed, self.config.clone())?;
+        if self.practice {
+            game.enable_practice();
+        }
+        for (player_id, monster) in (1..).zip(&self.monsters) {
+            if let Some(monster) = *monster {
+                game.choose_monster(player_id, monster);
+            }
+        }
+        Ok(Playback { replay: self, game, next_action: 0 })
+    }
+}
+
+/// The first recorded action that breaks the rules, found by `Replay::verify`.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub index: usize,           // Position in `Replay::actions`, from 0
+    pub turn: u32,              // Turn the game was on
+    pub action: Option<Action>, // None if the game couldn't even be set up
+    pub reason: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.action {
+            Some(action) => write!(f, "action {} (turn {}) {:?}: {}", self.index + 1, self.turn, action, self.reason),
+            None => write!(f, "setup: {}", self.reason),
+        }
+    }
+}
+
+/// Why `action` could not have been sent by a client playing fair, when the engine would
+/// otherwise quietly make the best of it (an unaffordable buy just ends shopping).
+fn illegal_reason(game: &Game, decision: Decision, action: Action) -> Option<String> {
+    let energy = game.get_player(decision.player_id()).map_or(0, |p| p.energy);
+    match (decision, action) {
+        (Decision::Shop { .. }, Action::Shop(ShopAction::Buy(slot))) => match game.shop.face_up().get(slot) {
+            None => Some(format!("there is no card in shop slot {}", slot + 1)),
+            Some(card) if !game.card_cost(decision.player_id(), card).affordable(energy) => {
+                Some(format!("{} costs {} Energy, but only {} is held", card.name, game.card_cost(decision.player_id(), card), energy))
+            }
+            Some(_) => None,
+        },
+        (Decision::Shop { .. }, Action::Shop(ShopAction::Sweep)) if energy < game.sweep_cost() => {
+            Some(format!("sweeping costs {} Energy, but only {} is held", game.sweep_cost(), energy))
+        }
+        (Decision::Shop { player_id }, Action::Shop(ShopAction::Evolve(index))) => {
+            let hand = game.get_player(player_id).map_or(0, |p| p.evolutions.len());
+            (index >= hand).then(|| format!("there is no evolution {} in hand, only {}", index + 1, hand))
+        }
+        (Decision::Purchase { affordable, .. }, Action::Purchase(times)) if times > affordable => {
+            Some(format!("{} purchases asked for, but only {} can be paid for", times, affordable))
+        }
+        _ => None,
+    }
+}
+
+impl Replay {
+    /// Checks every recorded action against the rules, stopping at the first one that
+    /// breaks them. Stricter than `playback`: actions that don't answer the decision the
+    /// game is waiting on, or that ask for more than the player can pay, are violations.
+    /// Returns the game as it stands after the last action.
+    pub fn verify(&self) -> Result<Game, Violation> {
+        let mut playback = self.playback().map_err(|error| Violation {
+            index: 0,
+            turn: 0,
+            action: None,
+            reason: error.to_string().trim().to_string(),
+        })?;
+        let game = &mut playback.game;
+        for (index, &action) in self.actions.iter().enumerate() {
+            let violation = |game: &Game, reason: String| Violation { index, turn: game.turn, action: Some(action), reason };
+            // Start turns until the game asks for something
+            let decision = loop {
+                if let Some(decision) = game.pending() {
+                    break decision;
+                }
+                if game.victory().is_some() {
+                    return Err(violation(game, "the game was already over".to_string()));
+                }
+                game.advance(None).expect("Advancing with nothing pending is always allowed");
+            };
+            if let Some(reason) = illegal_reason(game, decision, action) {
+                return Err(violation(game, reason));
+            }
+            if let Err(error) = game.advance(Some(action)) {
+                return Err(violation(game, error.to_string()));
+            }
+        }
+        // Run on through anything that needs no decision, such as a win at the next upkeep
+        while game.pending().is_none() && game.victory().is_none() {
+            game.advance(None).expect("Advancing with nothing pending is always allowed");
+        }
+        Ok(playback.game)
+    }
+}
+
+/// A replay being played back, one turn at a time.
+pub struct Playback<'a> {
+    replay: &'a Replay,
+    game: Game,
+    next_action: usize, // Index of the next recorded action to apply
+}
+
+impl Playback<'_> {
+    /// The game as played back so far.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Plays the next turn from the recorded actions. Returns `Progress::Decide` if the
+    /// recording stops partway through the turn (the game was left unfinished). An error
+    /// means the replay doesn't fit this engine: the game asked for something else.
+    pub fn next_turn(&mut self) -> Result<Progress, AdvanceError> {
+        loop {
+            let action = match self.game.pending() {
+                Some(decision) => match self.replay.actions.get(self.next_action) {
+                    Some(&action) => {
+                        self.next_action += 1;
+                        Some(action)
+                    }
+                    None => return Ok(Progress::Decide(decision)),
+                },
+                None => None,
+            };
+            match self.game.advance(action)? {
+                Progress::Decide(_) => continue,
+                progress => return Ok(progress),
+            }
+        }
+    }
+
+    /// Whether every recorded action has been played back.
+    pub fn finished(&self) -> bool {
+        self.next_action == self.replay.actions.len()
+    }
+}
@@ -0,0 +1,294 @@
+//! Tournament reports: one document summing up a finished series of games (standings, the
+//! result of every round, the games worth a second look and how the power cards fared),
+//! written as Markdown for a club's wiki and as HTML for a noticeboard or browser.
+
+use crate::{Game, GameEvent, TieBreak, TieKeys, Victory};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// How one round of the tournament went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundResult {
+    pub round: usize, // From 1, in the order the games were played
+    pub seed: u64,
+    pub preset: String,
+    pub turns: u32,
+    pub victory: Option<Victory>,    // None if the game was left unfinished
+    pub finish: Vec<(String, u8)>,   // (player, placement), best first
+    pub vp_gap: Option<u8>,          // VP between the winner and the runner-up
+    pub damage: u32,                 // Damage dealt by everyone over the whole game
+    pub tie_break: Vec<TieBreak>,    // The policy that ordered `finish`
+}
+
+/// One player's record across the tournament.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub average_placement: f64,
+    pub total_vp: u32,
+    pub total_hp: u32,     // Final HP, summed over every game
+    pub total_energy: u32, // Final Energy, summed over every game
+}
+
+/// How often a power card was bought, and how often by the player who went on to win.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CardStat {
+    pub card: String,
+    pub bought: usize,
+    pub bought_by_winners: usize,
+}
+
+/// A round worth a second look, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotableGame {
+    pub round: usize,
+    pub reason: String,
+}
+
+/// Everything a tournament report says, worked out once and rendered in either format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentReport {
+    pub title: String,
+    pub rounds: Vec<RoundResult>,
+    pub standings: Vec<Standing>,  // Most wins first, then best average placement, then `tie_break` over the totals
+    pub tie_break: Vec<TieBreak>,  // The policy of the last round
+    pub cards: Vec<CardStat>,      // Most bought first
+    pub notable: Vec<NotableGame>,
+}
+
+impl TournamentReport {
+    /// Sums up `games`, one per round in the order they were played. Practice games are
+    /// unranked and left out.
+    pub fn from_games(title: &str, games: &[Game]) -> Self {
+        let ranked: Vec<&Game> = games.iter().filter(|game| !game.practice).collect();
+        let rounds: Vec<RoundResult> = ranked.iter().enumerate().map(|(i, game)| round_result(i + 1, game)).collect();
+
+        // Name, placements, and the totals the tie-break compares; a player's seat is the
+        // order they first appear in
+        let mut records: Vec<(String, Vec<u8>, TieKeys)> = Vec::new();
+        for game in &ranked {
+            for (player_id, placement) in game.final_placements() {
+                let name = game.logged_name(player_id);
+                let keys = game.get_player(player_id).map(|p| p.tie_keys());
+                let (vp, hp, energy) = keys.map_or((0, 0, 0), |keys| (keys.vp, keys.hp, keys.energy));
+                match records.iter_mut().find(|(known, _, _)| *known == name) {
+                    Some((_, placements, totals)) => {
+                        placements.push(placement);
+                        totals.vp += vp;
+                        totals.hp += hp;
+                        totals.energy += energy;
+                    }
+                    None => {
+                        let seat = records.len() as u32 + 1;
+                        records.push((name, vec![placement], TieKeys { vp, hp, energy, seat }));
+                    }
+                }
+            }
+        }
+        let tie_break = ranked.last().map_or(TieBreak::DEFAULT.to_vec(), |game| game.rules.tie_break.clone());
+        let mut standings: Vec<(Standing, TieKeys)> = records.into_iter()
+            .map(|(name, placements, totals)| (Standing {
+                name,
+                games: placements.len(),
+                wins: placements.iter().filter(|&&p| p == 1).count(),
+                average_placement: placements.iter().map(|&p| p as f64).sum::<f64>() / placements.len() as f64,
+                total_vp: totals.vp,
+                total_hp: totals.hp,
+                total_energy: totals.energy,
+            }, totals))
+            .collect();
+        standings.sort_by(|(a, a_totals), (b, b_totals)| {
+            b.wins.cmp(&a.wins)
+                .then(a.average_placement.total_cmp(&b.average_placement))
+                .then_with(|| TieBreak::compare(&tie_break, a_totals, b_totals))
+        });
+        let standings = standings.into_iter().map(|(standing, _)| standing).collect();
+
+        let mut cards: HashMap<String, CardStat> = HashMap::new();
+        for game in &ranked {
+            let winners: Vec<u32> = game.final_placements().into_iter().filter(|&(_, p)| p == 1).map(|(id, _)| id).collect();
+            for timed in &game.history {
+                if let GameEvent::CardPurchased { player_id, ref card, .. } = timed.event {
+                    let stat = cards.entry(card.clone())
+                        .or_insert_with(|| CardStat { card: card.clone(), bought: 0, bought_by_winners: 0 });
+                    stat.bought += 1;
+                    stat.bought_by_winners += winners.contains(&player_id) as usize;
+                }
+            }
+        }
+        let mut cards: Vec<CardStat> = cards.into_values().collect();
+        cards.sort_by(|a, b| b.bought.cmp(&a.bought).then_with(|| a.card.cmp(&b.card)));
+
+        let notable = notable_games(&rounds);
+        TournamentReport { title: title.to_string(), rounds, standings, tie_break, cards, notable }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}\n", self.title);
+        let _ = writeln!(out, "{}\n", rounds_played(self.rounds.len()));
+
+        let _ = writeln!(out, "## Standings\n");
+        let _ = writeln!(out, "{}\n", tie_break_note(&self.tie_break));
+        let _ = writeln!(out, "| Rank | Player | Games | Wins | Average placement | Total VP |");
+        let _ = writeln!(out, "|---:|---|---:|---:|---:|---:|");
+        for (rank, standing) in self.standings.iter().enumerate() {
+            let _ = writeln!(out, "| {} | {} | {} | {} | {:.2} | {} |", rank + 1, markdown_cell(&standing.name), standing.games,
+                             standing.wins, standing.average_placement, standing.total_vp);
+        }
+
+        let _ = writeln!(out, "\n## Rounds\n");
+        let _ = writeln!(out, "| Round | Rules | Seed | Turns | Result | Finishing order |");
+        let _ = writeln!(out, "|---:|---|---:|---:|---|---|");
+        for round in &self.rounds {
+            let _ = writeln!(out, "| {} | {} | {} | {} | {} | {} |", round.round, markdown_cell(&round.preset), round.seed, round.turns,
+                             markdown_cell(&outcome(round)), markdown_cell(&finishing_order(round)));
+        }
+
+        if !self.notable.is_empty() {
+            let _ = writeln!(out, "\n## Notable games\n");
+            for notable in &self.notable {
+                let _ = writeln!(out, "- Round {}: {}", notable.round, notable.reason);
+            }
+        }
+
+        let _ = writeln!(out, "\n## Power cards\n");
+        if self.cards.is_empty() {
+            let _ = writeln!(out, "No cards were bought.");
+        } else {
+            let _ = writeln!(out, "| Card | Bought | Bought by the winner |");
+            let _ = writeln!(out, "|---|---:|---:|");
+            for stat in &self.cards {
+                let _ = writeln!(out, "| {} | {} | {} |", markdown_cell(&stat.card), stat.bought, stat.bought_by_winners);
+            }
+        }
+        out
+    }
+
+    /// A complete HTML page with no outside stylesheets or scripts, so it can be opened
+    /// straight from disk.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>", html(&self.title));
+        let _ = writeln!(out, "<style>body {{ font-family: sans-serif; margin: 2em; }} table {{ border-collapse: collapse; }} \
+                               th, td {{ border: 1px solid #ccc; padding: 0.3em 0.6em; }} td.n {{ text-align: right; }}</style>");
+        let _ = writeln!(out, "</head>\n<body>\n<h1>{}</h1>\n<p>{}</p>", html(&self.title), rounds_played(self.rounds.len()));
+
+        let _ = writeln!(out, "<h2>Standings</h2>\n<p>{}</p>\n<table>\n<tr><th>Rank</th><th>Player</th><th>Games</th><th>Wins</th><th>Average placement</th><th>Total VP</th></tr>", html(&tie_break_note(&self.tie_break)));
+        for (rank, standing) in self.standings.iter().enumerate() {
+            let _ = writeln!(out, "<tr><td class=\"n\">{}</td><td>{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td class=\"n\">{:.2}</td><td class=\"n\">{}</td></tr>",
+                             rank + 1, html(&standing.name), standing.games, standing.wins, standing.average_placement, standing.total_vp);
+        }
+        let _ = writeln!(out, "</table>");
+
+        let _ = writeln!(out, "<h2>Rounds</h2>\n<table>\n<tr><th>Round</th><th>Rules</th><th>Seed</th><th>Turns</th><th>Result</th><th>Finishing order</th></tr>");
+        for round in &self.rounds {
+            let _ = writeln!(out, "<tr><td class=\"n\">{}</td><td>{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td><td>{}</td><td>{}</td></tr>",
+                             round.round, html(&round.preset), round.seed, round.turns, html(&outcome(round)), html(&finishing_order(round)));
+        }
+        let _ = writeln!(out, "</table>");
+
+        if !self.notable.is_empty() {
+            let _ = writeln!(out, "<h2>Notable games</h2>\n<ul>");
+            for notable in &self.notable {
+                let _ = writeln!(out, "<li>Round {}: {}</li>", notable.round, html(&notable.reason));
+            }
+            let _ = writeln!(out, "</ul>");
+        }
+
+        let _ = writeln!(out, "<h2>Power cards</h2>");
+        if self.cards.is_empty() {
+            let _ = writeln!(out, "<p>No cards were bought.</p>");
+        } else {
+            let _ = writeln!(out, "<table>\n<tr><th>Card</th><th>Bought</th><th>Bought by the winner</th></tr>");
+            for stat in &self.cards {
+                let _ = writeln!(out, "<tr><td>{}</td><td class=\"n\">{}</td><td class=\"n\">{}</td></tr>",
+                                 html(&stat.card), stat.bought, stat.bought_by_winners);
+            }
+            let _ = writeln!(out, "</table>");
+        }
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+}
+
+fn round_result(round: usize, game: &Game) -> RoundResult {
+    let placements = game.final_placements();
+    let vp = |player_id: u32| game.get_player(player_id).map_or(0, |p| p.victory_points);
+    let vp_gap = match placements.as_slice() {
+        [(winner, _), (runner_up, _), ..] => Some(vp(*winner).saturating_sub(vp(*runner_up))),
+        _ => None,
+    };
+    RoundResult {
+        round,
+        seed: game.seed,
+        preset: game.preset.clone(),
+        turns: game.turn,
+        victory: game.victory(),
+        finish: placements.iter().map(|&(player_id, placement)| (game.logged_name(player_id), placement)).collect(),
+        vp_gap,
+        damage: game.damage_log.iter().map(|record| record.amount as u32).sum(),
+        tie_break: game.rules.tie_break.clone(),
+    }
+}
+
+/// The shortest and longest games (if they differ), the closest finish on points and the
+/// bloodiest game. Only picked out when there is more than one round to compare.
+fn notable_games(rounds: &[RoundResult]) -> Vec<NotableGame> {
+    let mut notable = Vec::new();
+    if rounds.len() < 2 {
+        return notable;
+    }
+    let shortest = rounds.iter().min_by_key(|r| r.turns).expect("There are rounds");
+    let longest = rounds.iter().rev().max_by_key(|r| r.turns).expect("There are rounds");
+    if longest.turns > shortest.turns {
+        notable.push(NotableGame { round: shortest.round, reason: format!("the shortest game, over in {} turns", shortest.turns) });
+        notable.push(NotableGame { round: longest.round, reason: format!("the longest game, at {} turns", longest.turns) });
+    }
+    let closest = rounds.iter()
+        .filter(|r| matches!(r.victory, Some(Victory::Points { .. })))
+        .filter_map(|r| r.vp_gap.map(|gap| (gap, r)))
+        .min_by_key(|&(gap, _)| gap);
+    if let Some((gap, round)) = closest {
+        notable.push(NotableGame { round: round.round, reason: format!("the closest finish, won by {} VP", gap) });
+    }
+    if let Some(round) = rounds.iter().rev().max_by_key(|r| r.damage) {
+        notable.push(NotableGame { round: round.round, reason: format!("the most damage, {} in all", round.damage) });
+    }
+    notable
+}
+
+fn tie_break_note(tie_break: &[TieBreak]) -> String {
+    format!("Players level on wins and average placement are ranked by their totals: {}.", TieBreak::describe(tie_break))
+}
+
+fn rounds_played(rounds: usize) -> String {
+    format!("{} round{} played.", rounds, if rounds == 1 { "" } else { "s" })
+}
+
+fn outcome(round: &RoundResult) -> String {
+    let winner = round.finish.first().map_or("nobody", |(name, _)| name.as_str());
+    match round.victory {
+        Some(Victory::Points { .. }) => format!("{} on points", winner),
+        Some(Victory::LastStanding { .. }) => format!("{}, last standing", winner),
+        Some(Victory::NoSurvivors) => "no survivors".to_string(),
+        None => "unfinished".to_string(),
+    }
+}
+
+/// "1. Alice, 2. Bob, 3. Cho".
+fn finishing_order(round: &RoundResult) -> String {
+    round.finish.iter().map(|(name, placement)| format!("{}. {}", placement, name)).collect::<Vec<_>>().join(", ")
+}
+
+/// Keeps a player's name from breaking a Markdown table.
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
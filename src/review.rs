@@ -0,0 +1,93 @@
+//! Table disputes: the events of the turn in progress, each with the rule that produced
+//! it, quoted with the values this game is played with. For groups around a table who
+//! want to know what the engine just did and why before anyone argues about it.
+
+use crate::{DamageSource, Game, GameEvent, PseudoCard, TieBreak, TimedEvent, Victory};
+
+/// The events logged since the current turn began, oldest first. Empty before the first turn.
+pub fn turn_events(game: &Game) -> &[TimedEvent] {
+    let start = game.history.iter().rposition(|timed| matches!(timed.event, GameEvent::TurnStarted { .. }));
+    start.map_or(&[], |start| &game.history[start..])
+}
+
+/// The rule behind `event` under `game`'s rules, as one sentence naming the option that
+/// sets it, followed by where the rule comes from (see `GameEvent::rule`). None for events
+/// that only report (dice rolled, cards dealt) rather than apply a rule.
+pub fn cite(game: &Game, event: &GameEvent) -> Option<String> {
+    let rules = &game.rules;
+    let citation = match *event {
+        GameEvent::DiceResolved { .. } => {
+            format!("Dice count once they are all kept or the rerolls run out (rules.rerolls = {}, plus any from cards).", rules.rerolls)
+        }
+        GameEvent::DiceChanged { ref effect, rerolled: true, .. } => format!("{} may make a monster roll a die again before the dice count.", effect),
+        GameEvent::DiceChanged { ref effect, .. } => format!("{} may turn a die that isn't kept to another face before the dice count.", effect),
+        GameEvent::TriplesScored { .. } => "Three or more of a number score that number in VP.".to_string(),
+        GameEvent::EnergyGained { .. } => {
+            format!("Each Energy die pays {} Energy (rules.economy.energy_per_die); cards can add more.", rules.economy.energy_per_die)
+        }
+        GameEvent::Healed { .. } => format!("Each Heart heals 1 HP, up to the maximum of {} (max_hp).", game.max_hp),
+        GameEvent::HeartsIgnored { overtime: true, .. } => "Nobody heals in overtime.".to_string(),
+        GameEvent::HeartsIgnored { overtime: false, .. } => "Hearts don't heal a monster in Tokyo (rules.heal_in_tokyo = false).".to_string(),
+        GameEvent::AttackLaunched { from_tokyo: true, .. } => "A monster in Tokyo hits every monster outside it, 1 damage per Claw.".to_string(),
+        GameEvent::AttackLaunched { from_tokyo: false, .. } => "A monster outside Tokyo hits every monster in it, 1 damage per Claw.".to_string(),
+        GameEvent::DamageDealt { source: DamageSource::Cards, ref card, .. } => {
+            format!("{} deals this damage itself; it isn't an attack, so nobody may yield.", card.as_deref().unwrap_or("The card"))
+        }
+        GameEvent::DamageBlocked { ref blocked_by, damage, .. } => format!("{} brought the {} damage down to 0.", blocked_by, damage),
+        GameEvent::TokyoHeld { vp, .. } if game.overtime => format!("Starting a turn in Tokyo scores {} VP: rules.tokyo_hold_vp, doubled in overtime.", vp),
+        GameEvent::TokyoHeld { vp, .. } => format!("Starting a turn in Tokyo scores {} VP (rules.tokyo_hold_vp).", vp),
+        GameEvent::TokyoEntered { .. } if rules.optional_entry => {
+            format!("Entering Tokyo scores {} VP (rules.tokyo_entry_vp); under rules.optional_entry the attacker could have stayed out.", rules.tokyo_entry_vp)
+        }
+        GameEvent::TokyoEntered { .. } => {
+            format!("An attacker has to move into an empty Tokyo, and scores {} VP for it (rules.tokyo_entry_vp).", rules.tokyo_entry_vp)
+        }
+        GameEvent::TokyoEntryDeclined { .. } => "Under rules.optional_entry an attacker may stay out of an empty Tokyo.".to_string(),
+        GameEvent::TokyoConceded { .. } => "A monster in Tokyo may leave once it has attacked.".to_string(),
+        GameEvent::TokyoYielded { .. } | GameEvent::TokyoDefended { .. } if rules.yield_unhurt => {
+            "Every occupant an attack reached may yield their spot to the attacker or stay (rules.yield_unhurt).".to_string()
+        }
+        GameEvent::TokyoYielded { .. } | GameEvent::TokyoDefended { .. } => {
+            "An occupant who lost HP to an attack may yield their spot to the attacker or stay.".to_string()
+        }
+        GameEvent::TokyoBayClosed { .. } => "Tokyo Bay is only in play while 5 or more monsters are left.".to_string(),
+        GameEvent::TokyoVacated { .. } => "An eliminated monster's spot in Tokyo is left empty.".to_string(),
+        GameEvent::EvolutionDrawn { .. } => "With Power Up! (rules.power_up), three or more Hearts draw an evolution card.".to_string(),
+        GameEvent::CardPurchased { ref price, .. } => {
+            let effects = "then the buyer's effects adjust it, never below 0 unless one of them allows it";
+            format!("Cards cost their printed price at {}% (rules.economy.card_cost_percent), {}; this one came to {}.",
+                    rules.economy.card_cost_percent, effects, price)
+        }
+        GameEvent::ShopSwept { .. } => format!("Sweeping the shop costs {} Energy (rules.economy.sweep_cost).", rules.economy.sweep_cost),
+        GameEvent::PseudoCardBought { ref card, .. } if card == PseudoCard::energy_heal(0).name => {
+            format!("Energy Heal trades {} Energy for 1 HP outside Tokyo (rules.energy_per_heal).", rules.energy_per_heal.unwrap_or(0))
+        }
+        GameEvent::PseudoCardBought { .. } => {
+            format!("Energy Exchange trades {} Energy for 1 VP (rules.energy_per_vp).", rules.energy_per_vp.unwrap_or(0))
+        }
+        GameEvent::OvertimeStarted { hold_vp, .. } => {
+            format!("After {} turns (rules.turn_limit) overtime starts: no more healing, and holding Tokyo scores {} VP.", rules.turn_limit, hold_vp)
+        }
+        GameEvent::PlayerEliminated { .. } => "A monster at 0 HP is out of the game.".to_string(),
+        GameEvent::GameOver { victory: Victory::Points { max_vp, .. } } => {
+            let when = if rules.immediate_victory { "at once (rules.immediate_victory)" } else { "once the turn is over" };
+            format!("Reaching {} VP (max_vp) wins {}; monsters reaching it together are split by {} (rules.tie_break).",
+                    max_vp, when, TieBreak::describe(&rules.tie_break))
+        }
+        GameEvent::GameOver { victory: Victory::LastStanding { .. } } if rules.immediate_victory => {
+            "The last monster standing wins the moment the others fall (rules.immediate_victory).".to_string()
+        }
+        GameEvent::GameOver { victory: Victory::LastStanding { .. } } => "The last monster standing wins.".to_string(),
+        GameEvent::GameOver { victory: Victory::NoSurvivors } => "When the last monsters fall together, nobody wins.".to_string(),
+        _ => return None,
+    };
+    match event.rule() {
+        Some(rule) => Some(format!("{} [{}: {}]", citation, rule.reference(), rule.title())),
+        None => Some(citation),
+    }
+}
+
+/// The current turn so far: each event with the rule behind it, if any.
+pub fn review_turn(game: &Game) -> Vec<(&TimedEvent, Option<String>)> {
+    turn_events(game).iter().map(|timed| (timed, cite(game, &timed.event))).collect()
+}
@@ -0,0 +1,183 @@
+//! The rules the engine plays by, as a table frontends can point at: each rule has a stable
+//! id, a title, where it comes from (the rulebook section, or the house rule or engine
+//! option behind it) and a one-line summary quoting the game's own values. `GameEvent::rule`
+//! names the rule behind an event, for the `rule` command, the turn review and tooltips in
+//! graphical frontends. Page numbers differ between printings of the rulebook, so
+//! references name the section instead.
+
+use crate::{DamageSource, Game, GameEvent};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One entry of the rules table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleId {
+    Rolling,
+    Numbers,
+    Energy,
+    Hearts,
+    Smash,
+    StartInTokyo,
+    EnterTokyo,
+    Yield,
+    TokyoBay,
+    BuyCards,
+    PowerCards,
+    Evolutions,
+    EnergyExchange,
+    Overtime,
+    Elimination,
+    Victory,
+}
+
+impl RuleId {
+    /// Every rule, in the order the rulebook covers them; house rules and engine options last.
+    pub const ALL: [RuleId; 16] = [
+        RuleId::Rolling, RuleId::Numbers, RuleId::Energy, RuleId::Hearts, RuleId::Smash, RuleId::StartInTokyo,
+        RuleId::EnterTokyo, RuleId::Yield, RuleId::TokyoBay, RuleId::BuyCards, RuleId::PowerCards, RuleId::Elimination,
+        RuleId::Victory, RuleId::Evolutions, RuleId::EnergyExchange, RuleId::Overtime,
+    ];
+
+    /// The stable id frontends and the `rule` command use, e.g. "yield".
+    pub fn id(self) -> &'static str {
+        match self {
+            RuleId::Rolling => "rolling",
+            RuleId::Numbers => "numbers",
+            RuleId::Energy => "energy",
+            RuleId::Hearts => "hearts",
+            RuleId::Smash => "smash",
+            RuleId::StartInTokyo => "start_in_tokyo",
+            RuleId::EnterTokyo => "enter_tokyo",
+            RuleId::Yield => "yield",
+            RuleId::TokyoBay => "tokyo_bay",
+            RuleId::BuyCards => "buy_cards",
+            RuleId::PowerCards => "power_cards",
+            RuleId::Evolutions => "evolutions",
+            RuleId::EnergyExchange => "energy_exchange",
+            RuleId::Overtime => "overtime",
+            RuleId::Elimination => "elimination",
+            RuleId::Victory => "victory",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        RuleId::ALL.into_iter().find(|rule| rule.id().eq_ignore_ascii_case(id.trim()))
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            RuleId::Rolling => "Rolling the dice",
+            RuleId::Numbers => "Scoring numbers",
+            RuleId::Energy => "Energy",
+            RuleId::Hearts => "Healing",
+            RuleId::Smash => "Attacking",
+            RuleId::StartInTokyo => "Starting a turn in Tokyo",
+            RuleId::EnterTokyo => "Entering Tokyo",
+            RuleId::Yield => "Yielding Tokyo",
+            RuleId::TokyoBay => "Tokyo Bay",
+            RuleId::BuyCards => "Buying power cards",
+            RuleId::PowerCards => "Power cards and abilities",
+            RuleId::Evolutions => "Evolutions",
+            RuleId::EnergyExchange => "Energy Exchange and Energy Heal",
+            RuleId::Overtime => "Overtime",
+            RuleId::Elimination => "Elimination",
+            RuleId::Victory => "Winning",
+        }
+    }
+
+    /// Where the rule comes from: the rulebook section, or the house rule or engine option.
+    pub fn reference(self) -> &'static str {
+        match self {
+            RuleId::Rolling => "rulebook, 1. Roll the Dice",
+            RuleId::Numbers => "rulebook, 2. Resolve the Dice: Victory Points",
+            RuleId::Energy => "rulebook, 2. Resolve the Dice: Energy",
+            RuleId::Hearts => "rulebook, 2. Resolve the Dice: Heal",
+            RuleId::Smash => "rulebook, 2. Resolve the Dice: Smash",
+            RuleId::StartInTokyo => "rulebook, Tokyo: Being in Tokyo",
+            RuleId::EnterTokyo => "rulebook, 3. Enter Tokyo",
+            RuleId::Yield => "rulebook, Tokyo: Leaving Tokyo",
+            RuleId::TokyoBay => "rulebook, Games of 5 or 6 Players",
+            RuleId::BuyCards => "rulebook, 4. Buy Power Cards",
+            RuleId::PowerCards => "rulebook, Power Cards",
+            RuleId::Elimination => "rulebook, Elimination",
+            RuleId::Victory => "rulebook, End of the Game",
+            RuleId::Evolutions => "Power Up! rulebook, Evolutions (rules.power_up)",
+            RuleId::EnergyExchange => "house rule (rules.energy_per_vp, rules.energy_per_heal)",
+            RuleId::Overtime => "engine option (rules.turn_limit)",
+        }
+    }
+
+    /// The rule in one sentence, with the values `game` is played with.
+    pub fn summary(self, game: &Game) -> String {
+        let rules = &game.rules;
+        match self {
+            RuleId::Rolling => format!("Roll the six dice, then reroll any of them up to {} more times, keeping the rest.", rules.rerolls),
+            RuleId::Numbers => "Three or more of a number score that number in VP.".to_string(),
+            RuleId::Energy => {
+                let per_die = rules.economy.energy_per_die;
+                format!("Each Energy rolled gives {} Energy cube{}.", per_die, if per_die == 1 { "" } else { "s" })
+            }
+            RuleId::Hearts if rules.heal_in_tokyo => format!("Each Heart heals 1 HP, up to the maximum of {}, in Tokyo too.", game.max_hp),
+            RuleId::Hearts => format!("Each Heart heals 1 HP, up to the maximum of {}, except in Tokyo.", game.max_hp),
+            RuleId::Smash => "Each Claw deals 1 damage: from Tokyo to everyone outside, from outside to everyone in Tokyo.".to_string(),
+            RuleId::StartInTokyo => format!("A monster that starts its turn in Tokyo scores {} VP.", rules.tokyo_hold_vp),
+            RuleId::EnterTokyo if rules.optional_entry => format!("An attacker may move into an empty Tokyo, and scores {} VP if it does.", rules.tokyo_entry_vp),
+            RuleId::EnterTokyo => format!("An attacker moves into an empty Tokyo and scores {} VP.", rules.tokyo_entry_vp),
+            RuleId::Yield if rules.yield_unhurt => "A monster in Tokyo that an attack reaches may yield its spot to the attacker.".to_string(),
+            RuleId::Yield => "A monster in Tokyo that takes damage may yield its spot to the attacker.".to_string(),
+            RuleId::TokyoBay => "With 5 or more monsters a second spot, Tokyo Bay, is used until fewer than 5 are left.".to_string(),
+            RuleId::BuyCards => {
+                format!("After resolving the dice, buy face-up cards with Energy, or pay {} Energy to sweep them.", rules.economy.sweep_cost)
+            }
+            RuleId::PowerCards => "A card's text overrides the basic rules.".to_string(),
+            RuleId::Evolutions => "Three Hearts draw an evolution card, played for free in a later buy phase.".to_string(),
+            RuleId::EnergyExchange => {
+                let trade = |cost: Option<u8>, gain: &str| cost.map_or("off".to_string(), |cost| format!("{} Energy for {}", cost, gain));
+                format!("Energy Exchange: {}. Energy Heal: {}.", trade(rules.energy_per_vp, "1 VP"), trade(rules.energy_per_heal, "1 HP outside Tokyo"))
+            }
+            RuleId::Overtime => format!("After {} turns nobody heals, and holding Tokyo scores double.", rules.turn_limit),
+            RuleId::Elimination => "A monster at 0 HP is out of the game and leaves Tokyo.".to_string(),
+            RuleId::Victory => format!("The first monster to {} VP, or the last one standing, wins.", game.max_vp),
+        }
+    }
+}
+
+impl fmt::Display for RuleId {
+    /// "yield (Yielding Tokyo, rulebook, Tokyo: Leaving Tokyo)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, {})", self.id(), self.title(), self.reference())
+    }
+}
+
+impl GameEvent {
+    /// The rule that produced this event, or None for events that only report (a turn
+    /// starting, a phase ending, a monster being chosen).
+    pub fn rule(&self) -> Option<RuleId> {
+        let rule = match *self {
+            GameEvent::DiceRolled { .. } | GameEvent::DiceResolved { .. } => RuleId::Rolling,
+            GameEvent::TriplesScored { .. } => RuleId::Numbers,
+            GameEvent::EnergyGained { .. } => RuleId::Energy,
+            GameEvent::Healed { .. } | GameEvent::HeartsIgnored { overtime: false, .. } => RuleId::Hearts,
+            GameEvent::HeartsIgnored { overtime: true, .. } | GameEvent::OvertimeStarted { .. } => RuleId::Overtime,
+            GameEvent::AttackLaunched { .. } => RuleId::Smash,
+            GameEvent::DamageDealt { source: DamageSource::Cards, .. } => RuleId::PowerCards,
+            GameEvent::DamageDealt { .. } => RuleId::Smash,
+            GameEvent::TokyoHeld { .. } => RuleId::StartInTokyo,
+            GameEvent::TokyoEntered { .. } | GameEvent::TokyoEntryDeclined { .. } => RuleId::EnterTokyo,
+            GameEvent::TokyoYielded { .. } | GameEvent::TokyoDefended { .. } | GameEvent::TokyoConceded { .. } => RuleId::Yield,
+            GameEvent::TokyoBayClosed { .. } => RuleId::TokyoBay,
+            GameEvent::CardRevealed { .. } | GameEvent::CardPurchased { .. } | GameEvent::ShopSwept { .. } => RuleId::BuyCards,
+            GameEvent::DiceChanged { .. } | GameEvent::VpGained { .. } | GameEvent::DamageBlocked { .. } | GameEvent::TurnSkipped { .. }
+            | GameEvent::SkipScheduled { .. } | GameEvent::ExtraTurnGranted { .. } | GameEvent::MaxHpChanged { .. }
+            | GameEvent::MaxVpChanged { .. } => RuleId::PowerCards,
+            GameEvent::EvolutionDrawn { .. } | GameEvent::EvolutionPlayed { .. } => RuleId::Evolutions,
+            GameEvent::PseudoCardBought { .. } => RuleId::EnergyExchange,
+            GameEvent::PlayerEliminated { .. } | GameEvent::TokyoVacated { .. } => RuleId::Elimination,
+            GameEvent::GameOver { .. } => RuleId::Victory,
+            GameEvent::GameStarted { .. } | GameEvent::PracticeStarted | GameEvent::MonsterChosen { .. } | GameEvent::TurnStarted { .. }
+            | GameEvent::RoundStarted | GameEvent::PhaseEnded { .. } => return None,
+        };
+        Some(rule)
+    }
+}
@@ -0,0 +1,189 @@
+//! Series play between the same players: a best-of-N match or a round-robin with the
+//! seating rotated every game. The standings carry over from game to game and are kept as
+//! JSON in a `Storage`, so a game night or a bot benchmark can be picked up again later.
+
+#[cfg(feature = "storage")]
+use crate::Storage;
+use crate::{ConfigError, ConfigIssue, Engine, Game, RuleSet, TieBreak, TieKeys};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "storage")]
+use std::io;
+
+/// How long a series runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesFormat {
+    BestOf(u32), // Until someone wins a majority of this many games, or all are played
+    RoundRobin,  // One game per seat, so everyone goes first once
+}
+
+impl SeriesFormat {
+    /// Reads a format as typed on the command line: "best-of-5" (or "bo5") or "round-robin"
+    /// (or "rr").
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        if text == "round-robin" || text == "rr" {
+            return Some(SeriesFormat::RoundRobin);
+        }
+        let games = text.strip_prefix("best-of-").or_else(|| text.strip_prefix("bo"))?;
+        games.parse().ok().map(SeriesFormat::BestOf)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            SeriesFormat::BestOf(games) => format!("best of {}", games),
+            SeriesFormat::RoundRobin => "round-robin".to_string(),
+        }
+    }
+}
+
+/// One player's results across a series.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesRecord {
+    pub name: String,
+    pub wins: u32,
+    pub total_vp: u32,    // Final VP, summed over every game
+    #[serde(default)]
+    pub total_hp: u32,    // Final HP, summed over every game
+    #[serde(default)]
+    pub total_energy: u32, // Final Energy, summed over every game
+    pub eliminated: u32,  // Games this player was knocked out of
+    pub knockouts: u32,   // Monsters this player finished off
+}
+
+/// A series in progress (or finished): its format, its players in their original seating
+/// order, and their standings so far.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Series {
+    pub format: SeriesFormat,
+    pub games: u32, // Finished games recorded so far
+    pub players: Vec<SeriesRecord>,
+    #[serde(default = "RuleSet::legacy_tie_break")]
+    pub tie_break: Vec<TieBreak>, // The policy of the games recorded, for players level on wins
+}
+
+impl Series {
+    /// Collection that `save_to` and `load_from` use.
+    pub const STANDINGS: &'static str = "standings";
+
+    /// A new series between `names`, seated in that order for the first game. Refuses
+    /// formats that can't be decided and tables the engine can't seat.
+    pub fn new(format: SeriesFormat, names: &[&str]) -> Result<Self, ConfigError> {
+        let mut issues = Vec::new();
+        let capabilities = Engine::capabilities();
+        if !(capabilities.min_players..=capabilities.max_players).contains(&names.len()) {
+            issues.push(ConfigIssue::new("players", format!("{} player(s) given", names.len()),
+                format!("use {} to {} players", capabilities.min_players, capabilities.max_players)));
+        }
+        if format == SeriesFormat::BestOf(0) {
+            issues.push(ConfigIssue::new("format", "a best-of-0 series has no games".to_string(), "play at least one game".to_string()));
+        }
+        if !issues.is_empty() {
+            return Err(ConfigError { source: "the series".to_string(), issues });
+        }
+        let players = names.iter()
+            .map(|name| SeriesRecord { name: name.to_string(), wins: 0, total_vp: 0, total_hp: 0, total_energy: 0, eliminated: 0, knockouts: 0 })
+            .collect();
+        Ok(Series { format, games: 0, players, tie_break: TieBreak::DEFAULT.to_vec() })
+    }
+
+    /// Names in seating order for the next game: the original order, rotated one seat
+    /// further for every game played.
+    pub fn seating(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.players.iter().map(|record| record.name.clone()).collect();
+        if !names.is_empty() {
+            let turn = self.games as usize % names.len();
+            names.rotate_left(turn);
+        }
+        names
+    }
+
+    /// Whether the series is over: every game played, or a best-of winner already clear.
+    pub fn is_decided(&self) -> bool {
+        match self.format {
+            SeriesFormat::BestOf(games) => {
+                self.games >= games || self.players.iter().any(|record| record.wins > games / 2)
+            }
+            SeriesFormat::RoundRobin => self.games as usize >= self.players.len(),
+        }
+    }
+
+    /// Adds a finished game to the standings, which from then on break ties with its
+    /// `rules.tie_break`. Unfinished and practice games don't count, and neither do players
+    /// who aren't in the series. Returns whether it was counted.
+    pub fn record(&mut self, game: &Game) -> bool {
+        if game.practice || game.victory().is_none() {
+            return false;
+        }
+        let stats = game.player_stats();
+        for (player_id, placement) in game.final_placements() {
+            let Some(player) = game.get_player(player_id) else {
+                continue;
+            };
+            let Some(record) = self.players.iter_mut().find(|record| record.name == player.name) else {
+                continue;
+            };
+            record.wins += (placement == 1) as u32;
+            record.total_vp += player.victory_points as u32;
+            record.total_hp += player.hp as u32;
+            record.total_energy += player.energy as u32;
+            record.eliminated += (player.hp == 0) as u32;
+            record.knockouts += stats.iter().find(|s| s.player_id == player_id).map_or(0, |s| s.knockouts);
+        }
+        self.games += 1;
+        self.tie_break = game.rules.tie_break.clone();
+        true
+    }
+
+    /// The players best first: most wins, then the tie-break policy over their totals
+    /// (seating order being the series' original one), then fewest eliminations.
+    pub fn standings(&self) -> Vec<&SeriesRecord> {
+        let keys = |seat: usize, record: &SeriesRecord| TieKeys {
+            vp: record.total_vp, hp: record.total_hp, energy: record.total_energy, seat: seat as u32 + 1,
+        };
+        let mut standings: Vec<(usize, &SeriesRecord)> = self.players.iter().enumerate().collect();
+        standings.sort_by(|&(a_seat, a), &(b_seat, b)| {
+            b.wins.cmp(&a.wins)
+                .then_with(|| TieBreak::compare(&self.tie_break, &keys(a_seat, a), &keys(b_seat, b)))
+                .then(a.eliminated.cmp(&b.eliminated))
+        });
+        standings.into_iter().map(|(_, record)| record).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Series standings are always serializable")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Saves the standings in `storage` under `name`.
+    #[cfg(feature = "storage")]
+    pub fn save_to(&self, storage: &mut dyn Storage, name: &str) -> io::Result<()> {
+        storage.put(Series::STANDINGS, name, &self.to_json())
+    }
+
+    /// The series saved under `name`, if there is one.
+    #[cfg(feature = "storage")]
+    pub fn load_from(storage: &dyn Storage, name: &str) -> io::Result<Option<Self>> {
+        match storage.get(Series::STANDINGS, name)? {
+            Some(json) => Ok(Some(Series::from_json(&json)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl fmt::Display for Series {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.is_decided() { "final" } else { "so far" };
+        writeln!(f, "Standings, {} ({} game{} played, {}):", self.format.label(), self.games, if self.games == 1 { "" } else { "s" }, state)?;
+        writeln!(f, "{:>4}  {:<14} {:>5} {:>8} {:>5} {:>11}", "", "Player", "Wins", "Total VP", "KOs", "Eliminated")?;
+        for (place, record) in self.standings().into_iter().enumerate() {
+            writeln!(f, "{:>4}  {:<14} {:>5} {:>8} {:>5} {:>11}", place + 1, record.name, record.wins, record.total_vp,
+                     record.knockouts, record.eliminated)?;
+        }
+        Ok(())
+    }
+}
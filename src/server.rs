@@ -0,0 +1,479 @@
+//! A small REST/JSON API over HTTP, for clients that would rather speak HTTP than embed
+//! the crate or hold a TCP connection open (chat bots, mobile apps). The server keeps any
+//! number of games; players join a seat each, then poll for the pending decision and post
+//! their answers:
+//!
+//! - `POST /games` with `{"players": 3, "config": {"preset": "speed"}}` opens a table
+//!   (`config` as for `GameConfig::from_json`, the official rules if left out; an optional
+//!   `seed` makes the game reproducible). Returns its `game_id`.
+//! - `POST /games/{id}/join` with `{"name": "Ann"}` takes the next seat, returning the
+//!   `player_id` and a `token` to answer with. The game starts once every seat is taken.
+//! - `GET /games/{id}` is the table: its status (`waiting`, `playing` or `over`), who has
+//!   joined, and once started the game as a spectator may see it (see `Game::view_json`).
+//!   With `?token=...` it is the game as that token's seat may see it, with its hand.
+//! - `GET /games/{id}/pending` is the decision the game is waiting on, and whose it is.
+//! - `GET /games/{id}/events?since=N` is the events logged from position N in the history on,
+//!   each with the `rule` behind it (id, title and reference), if any. They are hidden as in
+//!   the table's view, and `&token=...` works the same way.
+//! - `POST /games/{id}/actions` with `{"token": "...", "action": {"Keep": [...]}}` answers
+//!   the pending decision for the token's seat.
+//!
+//! - `GET /leaderboard` is the bot leaderboard, when the server is running one (see
+//!   `set_leaderboard`): every bot best first, with its rating, and every head-to-head
+//!   record. `GET /leaderboard/{bot}` is one bot's rating and its record against each other
+//!   bot (names URL-encoded, e.g. `heuristic%20bot`).
+//!
+//! Errors come back with a 4xx status and `{"error": "..."}`. Turns start by themselves,
+//! so the pending decision is only ever null while seats are open or once the game is over.
+//!
+//! `save_to` checkpoints every table (seats, tokens and games) in a `Storage`, and
+//! `load_from` picks them back up, so a server that is shut down and started again carries
+//! on where it stopped.
+
+use crate::{validate_player_names, Action, Engine, Game, GameConfig, Leaderboard, Progress, Storage};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Largest request body the server reads, in bytes.
+pub const MAX_REQUEST_BODY: usize = 1 << 20;
+
+/// What `POST /games` reads.
+#[derive(Debug, Deserialize)]
+struct CreateRequest {
+    players: usize,
+    #[serde(default)]
+    config: Option<Value>,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+/// What `POST /games/{id}/join` reads.
+#[derive(Debug, Deserialize)]
+struct JoinRequest {
+    name: String,
+}
+
+/// What `POST /games/{id}/actions` reads.
+#[derive(Debug, Deserialize)]
+struct ActionRequest {
+    token: String,
+    action: Action,
+}
+
+/// A status code and JSON body to send back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl ApiResponse {
+    fn ok(body: Value) -> Self {
+        ApiResponse { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        ApiResponse { status, body: json!({ "error": message.into() }) }
+    }
+
+    /// The reason phrase for the status line.
+    pub fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            _ => "Error",
+        }
+    }
+}
+
+/// A table on the server: the seats taken so far, then the game once they're all filled.
+struct Table {
+    seats: usize,
+    config: GameConfig,
+    seed: u64,
+    names: Vec<String>,
+    tokens: Vec<String>, // One per joined player, by seat
+    game: Option<Game>,
+}
+
+impl Table {
+    fn status(&self) -> &'static str {
+        match &self.game {
+            None => "waiting",
+            Some(game) if game.victory().is_some() => "over",
+            Some(_) => "playing",
+        }
+    }
+
+    /// Runs the game on until someone has to decide or it's over, starting turns as they come.
+    fn settle(game: &mut Game) {
+        while game.pending().is_none() {
+            if !matches!(game.advance(None), Ok(Progress::TurnEnded)) {
+                break;
+            }
+        }
+    }
+}
+
+/// A table as checkpointed, with its game as a save so it loads back through `Game::from_json`.
+#[derive(Serialize, Deserialize)]
+struct SavedTable {
+    seats: usize,
+    config: GameConfig,
+    seed: u64,
+    names: Vec<String>,
+    tokens: Vec<String>,
+    game: Option<String>,
+}
+
+/// What `ApiServer::to_json` writes: every table, and the stream seeds and tokens come from.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    tables: Vec<SavedTable>,
+    rng: ChaCha12Rng,
+}
+
+/// The games being played through the API. `handle` answers one request without doing any
+/// I/O; `serve` reads and answers one HTTP connection with it.
+pub struct ApiServer {
+    tables: Vec<Table>,               // Game IDs are positions here, from 1
+    rng: ChaCha12Rng,                 // For game seeds and tokens
+    leaderboard: Option<Leaderboard>, // The bot leaderboard as last set, if the server shows one
+}
+
+impl ApiServer {
+    /// An empty server drawing game seeds and player tokens from `seed`.
+    pub fn new(seed: u64) -> Self {
+        ApiServer { tables: Vec::new(), rng: ChaCha12Rng::seed_from_u64(seed), leaderboard: None }
+    }
+
+    /// Shows `leaderboard` at `/leaderboard` from now on, in place of any shown before.
+    pub fn set_leaderboard(&mut self, leaderboard: Leaderboard) {
+        self.leaderboard = Some(leaderboard);
+    }
+
+    /// Collection checkpoints are kept in.
+    pub const CHECKPOINTS: &'static str = "checkpoints";
+
+    /// Every table, with its seats, tokens and game, for picking the server up again. The
+    /// leaderboard isn't part of it; it is kept on its own.
+    pub fn to_json(&self) -> String {
+        let tables = self.tables.iter().map(|table| SavedTable {
+            seats: table.seats,
+            config: table.config.clone(),
+            seed: table.seed,
+            names: table.names.clone(),
+            tokens: table.tokens.clone(),
+            game: table.game.as_ref().map(Game::to_json),
+        });
+        let checkpoint = Checkpoint { tables: tables.collect(), rng: self.rng.clone() };
+        serde_json::to_string_pretty(&checkpoint).expect("Checkpoints are always serializable")
+    }
+
+    /// A server with the tables written by `to_json`. Fails if any game's save doesn't check out.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let checkpoint: Checkpoint = serde_json::from_str(json)?;
+        let mut tables = Vec::new();
+        for saved in checkpoint.tables {
+            let game = saved.game.as_deref().map(Game::from_json).transpose()?;
+            tables.push(Table { seats: saved.seats, config: saved.config, seed: saved.seed, names: saved.names, tokens: saved.tokens, game });
+        }
+        Ok(ApiServer { tables, rng: checkpoint.rng, leaderboard: None })
+    }
+
+    /// Checkpoints every table in `storage` under `name`.
+    pub fn save_to(&self, storage: &mut dyn Storage, name: &str) -> io::Result<()> {
+        storage.put(ApiServer::CHECKPOINTS, name, &self.to_json())
+    }
+
+    /// The server checkpointed under `name`, if there is one.
+    pub fn load_from(storage: &dyn Storage, name: &str) -> io::Result<Option<Self>> {
+        match storage.get(ApiServer::CHECKPOINTS, name)? {
+            Some(json) => Ok(Some(ApiServer::from_json(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Number of tables opened, waiting, playing or over.
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// The game with ID `game_id`, once it has started.
+    pub fn game(&self, game_id: usize) -> Option<&Game> {
+        self.tables.get(game_id.checked_sub(1)?)?.game.as_ref()
+    }
+
+    /// Answers `method` on `path` (which may carry a query string) with `body`.
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> ApiResponse {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["leaderboard"]) => self.leaderboard(None),
+            ("GET", ["leaderboard", bot]) => self.leaderboard(Some(&percent_decode(bot))),
+            (_, ["leaderboard", ..]) => ApiResponse::error(405, "the leaderboard is read-only; use GET"),
+            ("POST", ["games"]) => self.create(body),
+            (_, ["games"]) => ApiResponse::error(405, "use POST to open a game"),
+            (_, ["games", id, rest @ ..]) => {
+                let Some(game_id) = id.parse::<usize>().ok().filter(|&id| id >= 1 && id <= self.tables.len()) else {
+                    return ApiResponse::error(404, format!("there is no game {}", id));
+                };
+                match (method, rest) {
+                    ("GET", []) => self.table(game_id, query),
+                    ("POST", ["join"]) => self.join(game_id, body),
+                    ("GET", ["pending"]) => self.pending(game_id),
+                    ("GET", ["events"]) => self.events(game_id, query),
+                    ("POST", ["actions"]) => self.act(game_id, body),
+                    (_, [] | ["join"] | ["pending"] | ["events"] | ["actions"]) => {
+                        ApiResponse::error(405, format!("{} isn't supported on {}", method, path))
+                    }
+                    _ => ApiResponse::error(404, format!("there is nothing at {}", path)),
+                }
+            }
+            _ => ApiResponse::error(404, format!("there is nothing at {}", path)),
+        }
+    }
+
+    fn create(&mut self, body: &str) -> ApiResponse {
+        let request: CreateRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(error) => return ApiResponse::error(400, format!("the game can't be read: {}", error)),
+        };
+        let capabilities = Engine::capabilities();
+        if !(capabilities.min_players..=capabilities.max_players).contains(&request.players) {
+            return ApiResponse::error(400, format!("a game needs {} to {} players", capabilities.min_players, capabilities.max_players));
+        }
+        let config = match request.config {
+            Some(config) => match GameConfig::from_json(&config.to_string()) {
+                Ok(config) => config,
+                Err(error) => return ApiResponse::error(400, error.to_string().trim().to_string()),
+            },
+            None => GameConfig::official(),
+        };
+        let seed = request.seed.unwrap_or_else(|| self.rng.gen());
+        self.tables.push(Table { seats: request.players, config, seed, names: Vec::new(), tokens: Vec::new(), game: None });
+        ApiResponse { status: 201, body: json!({ "game_id": self.tables.len(), "players": request.players }) }
+    }
+
+    fn join(&mut self, game_id: usize, body: &str) -> ApiResponse {
+        let request: JoinRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(error) => return ApiResponse::error(400, format!("the join can't be read: {}", error)),
+        };
+        let token = format!("{:016x}", self.rng.gen::<u64>());
+        let table = &mut self.tables[game_id - 1];
+        if table.names.len() >= table.seats {
+            return ApiResponse::error(409, "every seat is taken");
+        }
+        let mut candidate: Vec<&str> = table.names.iter().map(|name| name.as_str()).collect();
+        candidate.push(&request.name);
+        if let Err(error) = validate_player_names(&candidate) {
+            return ApiResponse::error(400, error.to_string().trim().to_string());
+        }
+        table.names.push(request.name);
+        table.tokens.push(token.clone());
+        let player_id = table.names.len();
+        if table.names.len() == table.seats {
+            let names: Vec<&str> = table.names.iter().map(|name| name.as_str()).collect();
+            let mut game = match Game::new(&names, table.seed, table.config.clone()) {
+                Ok(game) => game,
+                Err(error) => return ApiResponse::error(400, error.to_string().trim().to_string()),
+            };
+            Table::settle(&mut game);
+            table.game = Some(game);
+        }
+        ApiResponse { status: 201, body: json!({ "player_id": player_id, "token": token, "status": table.status() }) }
+    }
+
+    /// The seat whose `token=` is in `query`, if there is one, as a player ID; a 403 for a
+    /// token that isn't for a seat at the table.
+    fn viewer(table: &Table, query: &str) -> Result<Option<u32>, ApiResponse> {
+        let Some(token) = query.split('&').find_map(|pair| pair.strip_prefix("token=")) else {
+            return Ok(None);
+        };
+        match table.tokens.iter().position(|seat| seat == token) {
+            Some(seat) => Ok(Some(seat as u32 + 1)),
+            None => Err(ApiResponse::error(403, "that token isn't for a seat in this game")),
+        }
+    }
+
+    /// `game` as `viewer` may see it.
+    fn view(game: &Game, viewer: Option<u32>) -> Value {
+        serde_json::from_str(&game.view_json(viewer)).expect("Views are JSON")
+    }
+
+    fn table(&self, game_id: usize, query: &str) -> ApiResponse {
+        let table = &self.tables[game_id - 1];
+        let viewer = match ApiServer::viewer(table, query) {
+            Ok(viewer) => viewer,
+            Err(response) => return response,
+        };
+        let state = table.game.as_ref().map(|game| ApiServer::view(game, viewer));
+        ApiResponse::ok(json!({
+            "game_id": game_id,
+            "status": table.status(),
+            "players": table.seats,
+            "joined": table.names,
+            "victory": table.game.as_ref().and_then(|game| game.victory()),
+            "state": state,
+        }))
+    }
+
+    fn pending(&self, game_id: usize) -> ApiResponse {
+        let table = &self.tables[game_id - 1];
+        let decision = table.game.as_ref().and_then(|game| game.pending());
+        ApiResponse::ok(json!({
+            "status": table.status(),
+            "player_id": decision.map(|decision| decision.player_id()),
+            "decision": decision,
+            "history": table.game.as_ref().map_or(0, |game| game.history.len()),
+        }))
+    }
+
+    fn events(&self, game_id: usize, query: &str) -> ApiResponse {
+        let since = query.split('&').find_map(|pair| pair.strip_prefix("since="));
+        let since = match since.map(|since| since.parse::<usize>()) {
+            None => 0,
+            Some(Ok(since)) => since,
+            Some(Err(_)) => return ApiResponse::error(400, "since needs a position in the history, e.g. ?since=40"),
+        };
+        let table = &self.tables[game_id - 1];
+        let viewer = match ApiServer::viewer(table, query) {
+            Ok(viewer) => viewer,
+            Err(response) => return response,
+        };
+        let Some(game) = table.game.as_ref() else {
+            return ApiResponse::ok(json!({ "since": since, "events": [] }));
+        };
+        let mut view = ApiServer::view(game, viewer);
+        let shown = view["history"].as_array_mut().map(std::mem::take).unwrap_or_default();
+        // Each event comes with the rule behind it, for tooltips
+        let events: Vec<Value> = shown.into_iter().zip(&game.history).skip(since)
+            .map(|(mut value, timed)| {
+                value["rule"] = json!(timed.event.rule().map(|rule| json!({ "id": rule, "title": rule.title(), "reference": rule.reference() })));
+                value
+            })
+            .collect();
+        ApiResponse::ok(json!({ "since": since, "events": events }))
+    }
+
+    fn leaderboard(&self, bot: Option<&str>) -> ApiResponse {
+        let Some(leaderboard) = self.leaderboard.as_ref() else {
+            return ApiResponse::error(404, "this server isn't running a leaderboard");
+        };
+        let Some(bot) = bot else {
+            return ApiResponse::ok(json!({
+                "games": leaderboard.games,
+                "standings": leaderboard.standings(),
+                "head_to_head": leaderboard.head_to_head,
+            }));
+        };
+        match leaderboard.bots.iter().find(|rating| rating.name.eq_ignore_ascii_case(bot)) {
+            Some(rating) => ApiResponse::ok(json!({ "bot": rating, "head_to_head": leaderboard.head_to_head_of(&rating.name) })),
+            None => ApiResponse::error(404, format!("no bot called {} has played a rated game", bot)),
+        }
+    }
+
+    fn act(&mut self, game_id: usize, body: &str) -> ApiResponse {
+        let request: ActionRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(error) => return ApiResponse::error(400, format!("the action can't be read: {}", error)),
+        };
+        let table = &mut self.tables[game_id - 1];
+        let Some(seat) = table.tokens.iter().position(|token| *token == request.token) else {
+            return ApiResponse::error(403, "that token isn't for a seat in this game");
+        };
+        let Some(game) = table.game.as_mut() else {
+            return ApiResponse::error(409, "the game hasn't started: seats are still open");
+        };
+        let Some(decision) = game.pending() else {
+            return ApiResponse::error(409, "the game is over");
+        };
+        if decision.player_id() != seat as u32 + 1 {
+            let name = &game.get_player(decision.player_id()).expect("Deciding player must exist").name;
+            return ApiResponse::error(409, format!("it's {}'s decision", name));
+        }
+        if let Err(error) = game.advance(Some(request.action)) {
+            return ApiResponse::error(400, error.to_string());
+        }
+        Table::settle(game);
+        self.pending(game_id)
+    }
+
+    /// Reads one HTTP/1.1 request from `stream`, answers it with `handle` and closes the
+    /// connection. Returns the request line and the status sent, for logging.
+    pub fn serve(&mut self, stream: TcpStream) -> io::Result<(String, u16)> {
+        // A client that stalls mid-request mustn't hold up everyone else's games
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let request_line = request_line.trim_end().to_string();
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let response = if length > MAX_REQUEST_BODY {
+            ApiResponse::error(413, format!("request bodies are limited to {} bytes", MAX_REQUEST_BODY))
+        } else {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body)?;
+            match String::from_utf8(body) {
+                Ok(body) => self.handle(method, path, &body),
+                Err(_) => ApiResponse::error(400, "the request body isn't UTF-8"),
+            }
+        };
+
+        let body = response.body.to_string();
+        let mut writer = stream;
+        write!(writer, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                        Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+               response.status, response.reason(), body.len(), body)?;
+        writer.flush()?;
+        Ok((request_line, response.status))
+    }
+}
+
+/// Undoes URL encoding in a path segment ("heuristic%20bot" is "heuristic bot"). Escapes
+/// that aren't valid are left as they are.
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%').then(|| segment.get(i + 1..i + 3)).flatten().and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
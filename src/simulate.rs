@@ -0,0 +1,169 @@
+//! Headless batch play: many bot-only games in a row, summed up into statistics.
+
+use crate::{Agent, AgentKind, ConfigError, ConfigIssue, Engine, Game, GameConfig, PlayerController, Victory};
+use std::fmt;
+
+/// Win rates, game length and scores per seat over a batch of simulated games.
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub games: usize,
+    pub seats: Vec<AgentKind>,    // Who played each seat, in turn order
+    pub wins: Vec<usize>,         // Games won per seat
+    pub no_survivors: usize,      // Games where the last monsters fell together
+    pub total_turns: u64,
+    pub total_vp: Vec<u64>,       // Final VP per seat, summed over every game
+}
+
+impl SimulationReport {
+    pub fn average_turns(&self) -> f64 {
+        self.total_turns as f64 / self.games.max(1) as f64
+    }
+
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        self.wins[seat] as f64 / self.games.max(1) as f64
+    }
+
+    pub fn average_vp(&self, seat: usize) -> f64 {
+        self.total_vp[seat] as f64 / self.games.max(1) as f64
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games, {:.1} turns on average", self.games, self.average_turns())?;
+        for (seat, kind) in self.seats.iter().enumerate() {
+            writeln!(f, "  Seat {} ({}): {:>5.1}% wins, {:.1} VP on average",
+                     seat + 1, kind.label(), self.win_rate(seat) * 100.0, self.average_vp(seat))?;
+        }
+        if self.no_survivors > 0 {
+            writeln!(f, "  No survivors: {} games", self.no_survivors)?;
+        }
+        Ok(())
+    }
+}
+
+/// How batches of simulated games change as one option takes different values. Every
+/// batch replays the same seeds, so the differences come from the option alone.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub option: String,                                    // Dotted path, e.g. "rules.economy.sweep_cost"
+    pub runs: Vec<(serde_json::Value, SimulationReport)>, // One batch per value, in the order given
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((_, baseline)) = self.runs.first() else {
+            return writeln!(f, "No values of {} to compare.", self.option);
+        };
+        writeln!(f, "{} games per value of {} (changes are against the first value)", baseline.games, self.option)?;
+        for (value, report) in &self.runs {
+            write!(f, "  {:>8}: {:>5.1} turns ({:+.1})", value.to_string(), report.average_turns(),
+                   report.average_turns() - baseline.average_turns())?;
+            for seat in 0..report.seats.len() {
+                write!(f, ", seat {} {:>5.1}% ({:+.1})", seat + 1, report.win_rate(seat) * 100.0,
+                       (report.win_rate(seat) - baseline.win_rate(seat)) * 100.0)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulates a batch of `games` for each of `values` of `option`, on top of `config` and
+/// from the same `seed`, so a variant designer can see what a knob does to win rates and
+/// game length. Every value is checked before any game is played.
+pub fn compare(games: usize, seats: &[AgentKind], config: &GameConfig, option: &str, values: &[serde_json::Value], seed: u64)
+               -> Result<Comparison, ConfigError> {
+    let configs = values.iter()
+        .map(|value| config.with_option(option, value.clone()).and_then(|config| config.validate().map(|()| config)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut runs = Vec::new();
+    for (value, config) in values.iter().zip(&configs) {
+        runs.push((value.clone(), simulate(games, seats, config, seed)?));
+    }
+    Ok(Comparison { option: option.to_string(), runs })
+}
+
+/// One bot per seat, seat 1 first.
+struct Bots(Vec<Box<dyn Agent>>);
+
+impl PlayerController for Bots {
+    fn agent_for(&mut self, player_id: u32) -> &mut dyn Agent {
+        self.0[player_id as usize - 1].as_mut()
+    }
+}
+
+/// Plays `games` complete games between the bots in `seats` under `config`, without any
+/// input or output. Game `n` (from 0) uses seed `seed + n`, so a batch can be rerun exactly.
+pub fn simulate(games: usize, seats: &[AgentKind], config: &GameConfig, seed: u64) -> Result<SimulationReport, ConfigError> {
+    let capabilities = Engine::capabilities();
+    if !(capabilities.min_players..=capabilities.max_players).contains(&seats.len()) {
+        let issue = ConfigIssue::new("seats",
+            format!("{} seat(s) given", seats.len()),
+            format!("use {} to {} seats", capabilities.min_players, capabilities.max_players));
+        return Err(ConfigError { source: "the simulation".to_string(), issues: vec![issue] });
+    }
+    let names: Vec<String> = (1..=seats.len()).map(|seat| format!("Seat {}", seat)).collect();
+    let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    play_batch(games, seats, seed, |game_seed| Game::new(&names, game_seed, config.clone()))
+}
+
+/// Plays `games` games on from `start`, a game in progress (e.g. one just loaded from a
+/// save), with the bots in `seats` taking over every seat. Each game copies `start` and
+/// reseeds it with `seed + n`, so the dice and the unseen cards differ from game to game
+/// while everything that already happened is kept. Turn counts include the turns played
+/// before `start`.
+pub fn simulate_from(games: usize, seats: &[AgentKind], start: &Game, seed: u64) -> Result<SimulationReport, ConfigError> {
+    let mut issues = Vec::new();
+    if seats.len() != start.players.len() {
+        issues.push(ConfigIssue::new("seats",
+            format!("{} seat(s) given for a game of {}", seats.len(), start.players.len()),
+            "give one bot per player in the game".to_string()));
+    }
+    if start.victory().is_some() {
+        issues.push(ConfigIssue::new("position", "the game is already over".to_string(), "start from a game still in progress".to_string()));
+    }
+    if !issues.is_empty() {
+        return Err(ConfigError { source: "the simulation".to_string(), issues });
+    }
+    let position = start.to_json();
+    play_batch(games, seats, seed, |game_seed| {
+        let mut game = Game::from_json(&position).expect("A game's own save always loads");
+        game.reseed(game_seed);
+        Ok(game)
+    })
+}
+
+/// Plays `games` games to the end, each set up by `new_game` from its seed, and sums them up.
+fn play_batch(games: usize, seats: &[AgentKind], seed: u64, new_game: impl Fn(u64) -> Result<Game, ConfigError>)
+              -> Result<SimulationReport, ConfigError> {
+    let mut report = SimulationReport {
+        games,
+        seats: seats.to_vec(),
+        wins: vec![0; seats.len()],
+        no_survivors: 0,
+        total_turns: 0,
+        total_vp: vec![0; seats.len()],
+    };
+
+    for n in 0..games {
+        let game_seed = seed.wrapping_add(n as u64);
+        let mut game = new_game(game_seed)?;
+        let mut bots = Bots(seats.iter().enumerate().map(|(i, kind)| kind.build(game_seed.wrapping_add(i as u64 + 1))).collect());
+        let victory = loop {
+            if let Some(victory) = game.play_turn(&mut bots) {
+                break victory;
+            }
+        };
+
+        match victory {
+            Victory::Points { player_id, .. } | Victory::LastStanding { player_id } => report.wins[player_id as usize - 1] += 1,
+            Victory::NoSurvivors => report.no_survivors += 1,
+        }
+        report.total_turns += game.turn as u64;
+        for player in &game.players {
+            report.total_vp[player.id as usize - 1] += player.victory_points as u64;
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,100 @@
+//! Where saved games and other records are kept. The engine only talks to the `Storage`
+//! trait, so embedders can keep their data in a database instead of the filesystem.
+
+use crate::Game;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A key-value store of JSON documents, grouped into named collections (e.g. "saves").
+pub trait Storage {
+    /// Stores `json` under `key`, replacing whatever was there.
+    fn put(&mut self, collection: &str, key: &str, json: &str) -> io::Result<()>;
+
+    /// The document stored under `key`, if any.
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<String>>;
+
+    /// Every key in `collection`, sorted.
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>>;
+
+    /// Deletes the document under `key`. Deleting a missing key is not an error.
+    fn remove(&mut self, collection: &str, key: &str) -> io::Result<()>;
+}
+
+/// Keeps each collection as a directory of `<key>.json` files under `root`.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileStorage { root: root.into() }
+    }
+
+    /// The file for `key`, refusing names that would reach outside the collection.
+    fn path(&self, collection: &str, key: &str) -> io::Result<PathBuf> {
+        let safe = |name: &str| !name.is_empty() && !name.contains(['/', '\\']) && name != "." && name != "..";
+        if !safe(collection) || !safe(key) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("invalid storage key \"{}/{}\"", collection, key)));
+        }
+        Ok(self.root.join(collection).join(format!("{}.json", key)))
+    }
+}
+
+impl Storage for FileStorage {
+    fn put(&mut self, collection: &str, key: &str, json: &str) -> io::Result<()> {
+        let path = self.path(collection, key)?;
+        fs::create_dir_all(self.root.join(collection))?;
+        fs::write(path, json)
+    }
+
+    fn get(&self, collection: &str, key: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(collection, key)?) {
+            Ok(json) => Ok(Some(json)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn keys(&self, collection: &str) -> io::Result<Vec<String>> {
+        let entries = match fs::read_dir(self.root.join(collection)) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut keys = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            if let Some(key) = name.strip_suffix(".json") {
+                keys.push(key.to_string());
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn remove(&mut self, collection: &str, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(collection, key)?) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Game {
+    /// Collection that `save_to` and `load_from` use.
+    pub const SAVES: &'static str = "saves";
+
+    /// Saves the game in `storage` under `key`.
+    pub fn save_to(&self, storage: &mut dyn Storage, key: &str) -> io::Result<()> {
+        storage.put(Game::SAVES, key, &self.to_json())
+    }
+
+    /// Loads the game saved under `key` with `save_to`.
+    pub fn load_from(storage: &dyn Storage, key: &str) -> io::Result<Self> {
+        let json = storage.get(Game::SAVES, key)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no saved game \"{}\"", key)))?;
+        Ok(Game::from_json(&json)?)
+    }
+}
@@ -0,0 +1,76 @@
+//! The tie-break policy: what separates monsters level on the result itself, whether
+//! they reached the VP target together, survived to the end of a game or finished a
+//! series on the same number of wins. One `rules.tie_break` list decides all of them, so a
+//! game's winner, its placements and the standings it feeds into never disagree.
+
+use crate::Player;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// One step of the policy, applied to whoever is still level after the steps before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    Vp,     // More VP first
+    Hp,     // More HP first
+    Energy, // More Energy first
+    Seat,   // Earlier in the seating order first; settles every tie left
+}
+
+/// What the policy compares: a monster's VP, HP and Energy (or, in a series, their sums
+/// over every game) and their seat, from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TieKeys {
+    pub vp: u32,
+    pub hp: u32,
+    pub energy: u32,
+    pub seat: u32,
+}
+
+impl TieBreak {
+    /// The policy new games use: VP, then HP, then Energy, then seating order.
+    pub const DEFAULT: [TieBreak; 4] = [TieBreak::Vp, TieBreak::Hp, TieBreak::Energy, TieBreak::Seat];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TieBreak::Vp => "VP",
+            TieBreak::Hp => "HP",
+            TieBreak::Energy => "Energy",
+            TieBreak::Seat => "seating order",
+        }
+    }
+
+    /// Orders `a` and `b` by `policy`: Less when `a` ranks first, Equal when the policy
+    /// can't separate them.
+    pub fn compare(policy: &[TieBreak], a: &TieKeys, b: &TieKeys) -> Ordering {
+        policy.iter()
+            .map(|step| match step {
+                TieBreak::Vp => b.vp.cmp(&a.vp),
+                TieBreak::Hp => b.hp.cmp(&a.hp),
+                TieBreak::Energy => b.energy.cmp(&a.energy),
+                TieBreak::Seat => a.seat.cmp(&b.seat),
+            })
+            .find(|order| order.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// The policy in words, e.g. "VP, then HP, then seating order".
+    pub fn describe(policy: &[TieBreak]) -> String {
+        if policy.is_empty() {
+            return "nothing (tied monsters share a place)".to_string();
+        }
+        let steps: Vec<&str> = policy.iter().map(|step| step.label()).collect();
+        let mut description = steps.join(", then ");
+        if !policy.contains(&TieBreak::Seat) {
+            description.push_str("; monsters level on all of it share a place");
+        }
+        description
+    }
+}
+
+impl Player {
+    /// This monster's standing as the tie-break policy sees it.
+    pub fn tie_keys(&self) -> TieKeys {
+        TieKeys { vp: self.victory_points as u32, hp: self.hp as u32, energy: self.energy as u32, seat: self.id }
+    }
+}
@@ -0,0 +1,175 @@
+//! What a client may see of a game. A save (`Game::to_json`) holds everything needed to
+//! play on, including the seed and every RNG stream, from which the dice to come, the shop
+//! deck and every monster's evolution deck can be worked out. A view is the same JSON with
+//! all of that left out, and with other seats' evolution hands hidden, so a host can send
+//! it to players and spectators without handing them the game. `GameView` is the in-process
+//! counterpart: a borrow of a game that can be read but not changed.
+
+#[cfg(feature = "net")]
+use crate::GameEvent;
+use crate::{Card, Decision, Game, Player, RuleSet, TimedEvent, Tokyo, Victory};
+use serde_json::Value;
+
+/// Fields of a save that no view carries, by dotted path; `players.*` is every seat.
+const HIDDEN_FIELDS: [&str; 6] = ["seed", "actions", "shop.deck", "shop.rng", "players.*.rng", "players.*.evolution_deck"];
+
+/// A read-only look at a game, for bots and frontends that show or study it. `Game`'s fields
+/// are public so the engine and tools can set games up, and holding a `Game` is holding
+/// the whole of it; a `GameView` only hands out shared references, so whoever it's given
+/// to can't change the game behind the engine's back. Get one with `Game::view`.
+#[derive(Clone, Copy)]
+pub struct GameView<'a> {
+    game: &'a Game,
+}
+
+impl<'a> GameView<'a> {
+    /// Every player, eliminated or not, in seat order.
+    pub fn players(&self) -> &'a [Player] {
+        &self.game.players
+    }
+
+    /// The player with this ID, if there is one.
+    pub fn player(&self, player_id: u32) -> Option<&'a Player> {
+        self.game.get_player(player_id)
+    }
+
+    /// Players still in the game, in seat order.
+    pub fn living_players(&self) -> impl Iterator<Item = &'a Player> {
+        self.game.living_players()
+    }
+
+    /// Who is in Tokyo City and Tokyo Bay.
+    pub fn tokyo(&self) -> &'a Tokyo {
+        &self.game.tokyo
+    }
+
+    /// The cards face up in the shop, in slot order.
+    pub fn shop(&self) -> &'a [Card] {
+        self.game.shop.face_up()
+    }
+
+    /// The rules the game is played by.
+    pub fn rules(&self) -> &'a RuleSet {
+        &self.game.rules
+    }
+
+    /// Number of the turn in progress (0 before the first turn).
+    pub fn turn(&self) -> u32 {
+        self.game.turn
+    }
+
+    /// Everything that has happened so far, in order.
+    pub fn history(&self) -> &'a [TimedEvent] {
+        &self.game.history
+    }
+
+    /// The decision the game is waiting on, if any.
+    pub fn pending(&self) -> Option<Decision> {
+        self.game.pending()
+    }
+
+    /// The result of the game, once it has one.
+    pub fn victory(&self) -> Option<Victory> {
+        self.game.victory()
+    }
+}
+
+impl Game {
+    /// A read-only look at the game, to hand to code that shouldn't change it.
+    pub fn view(&self) -> GameView<'_> {
+        GameView { game: self }
+    }
+
+    /// The game as `viewer` may see it: the save format without the seed, the RNG streams,
+    /// the record of actions (which replays the game from the seed) or the shop deck, and
+    /// with every evolution hand but the viewer's left out. The seed in `GameStarted` is 0,
+    /// and evolutions other seats drew go unnamed. `None` is a spectator, who sees no hand.
+    /// A view has no checksum, so `from_json` refuses it; read it with `from_view_json`.
+    pub fn view_json(&self, viewer: Option<u32>) -> String {
+        let mut view = serde_json::to_value(self).expect("Game state is always serializable");
+        view["format_version"] = Game::SAVE_FORMAT_VERSION.into();
+        for path in HIDDEN_FIELDS {
+            let (parent, field) = path.rsplit_once('.').unwrap_or(("", path));
+            let parents: Vec<&mut Value> = match parent.strip_suffix(".*") {
+                Some(list) => view[list].as_array_mut().map_or(Vec::new(), |items| items.iter_mut().collect()),
+                None if parent.is_empty() => vec![&mut view],
+                None => vec![&mut view[parent]],
+            };
+            for object in parents.into_iter().filter_map(Value::as_object_mut) {
+                object.remove(field);
+            }
+        }
+        for player in view["players"].as_array_mut().into_iter().flatten() {
+            if player["id"].as_u64() != viewer.map(u64::from) {
+                if let Some(player) = player.as_object_mut() {
+                    player.remove("evolutions");
+                }
+            }
+        }
+        for timed in view["history"].as_array_mut().into_iter().flatten() {
+            if let Some(started) = timed["event"].get_mut("GameStarted") {
+                started["seed"] = 0.into();
+            }
+            if let Some(drawn) = timed["event"].get_mut("EvolutionDrawn") {
+                if drawn["player_id"].as_u64() != viewer.map(u64::from) {
+                    drawn["evolution"] = "".into();
+                }
+            }
+        }
+        view.to_string()
+    }
+
+    /// Reads a view written by `view_json`, for showing the game. What the view leaves out
+    /// stays empty, so the game can be looked at and asked for its decisions, but playing
+    /// it on rolls from a stand-in stream rather than the host's dice.
+    pub fn from_view_json(json: &str) -> serde_json::Result<Self> {
+        use serde::de::Error;
+        let view: Value = serde_json::from_str(json)?;
+        let version = view.get("format_version").and_then(Value::as_u64);
+        if version != Some(Game::SAVE_FORMAT_VERSION as u64) {
+            return Err(serde_json::Error::custom(format!(
+                "the view is in format {}, but this build reads format {}",
+                version.map_or("(none)".to_string(), |version| version.to_string()),
+                Game::SAVE_FORMAT_VERSION
+            )));
+        }
+        let mut game: Game = serde_json::from_value(view)?;
+        game.clock_mark = game.clock.monotonic();
+        game.elapsed_before = game.duration();
+        Ok(game)
+    }
+}
+
+/// Whatever in `view` gives away more than `viewer` may see, described for a report; None
+/// if it's a proper view. Used by the conformance suite on third-party hosts' `State`s.
+#[cfg(feature = "net")]
+pub(crate) fn view_leak(view: &Value, viewer: Option<u32>) -> Option<String> {
+    for path in HIDDEN_FIELDS {
+        let mut nodes = vec![view];
+        for key in path.split('.') {
+            nodes = match key {
+                "*" => nodes.into_iter().filter_map(Value::as_array).flatten().collect(),
+                key => nodes.into_iter().filter_map(|node| node.get(key)).collect(),
+            };
+        }
+        if !nodes.is_empty() {
+            return Some(format!("it carries {}", path));
+        }
+    }
+    for player in view["players"].as_array().into_iter().flatten() {
+        let id = player["id"].as_u64();
+        if id != viewer.map(u64::from) && player.get("evolutions").is_some_and(|hand| hand.as_array().is_none_or(|hand| !hand.is_empty())) {
+            return Some(format!("it shows player {}'s evolution hand", id.unwrap_or(0)));
+        }
+    }
+    for timed in view["history"].as_array().into_iter().flatten() {
+        match serde_json::from_value::<GameEvent>(timed["event"].clone()) {
+            Ok(GameEvent::GameStarted { seed, .. }) if seed != 0 => return Some("its GameStarted event carries the seed".to_string()),
+            Ok(GameEvent::EvolutionDrawn { player_id, evolution }) if Some(player_id) != viewer && !evolution.is_empty() => {
+                return Some(format!("its history names the evolution player {} drew", player_id));
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,89 @@
+//! A JSON-in, JSON-out face for the engine, for embedding it where only text crosses the
+//! boundary: a WebAssembly build driven from JavaScript, or any other FFI. Each method is a
+//! thin layer over `Game`, taking and returning the same JSON the save format and the
+//! network protocol use, so a `#[wasm_bindgen]` wrapper only has to forward the calls.
+//!
+//! Nothing here blocks or touches the filesystem, and the dice come from the seed the
+//! caller passes in, so the host decides where randomness comes from.
+
+use crate::{Action, Clock, Game, GameConfig, Monster};
+use serde::Deserialize;
+
+/// What `JsonGame::new` reads, e.g.
+/// `{"players": ["Ann", "Bo"], "seed": 7, "config": {"preset": "speed"}, "monsters": ["Kraken", null]}`.
+#[derive(Debug, Deserialize)]
+struct Setup {
+    players: Vec<String>,
+    seed: u64,
+    #[serde(default)]
+    config: Option<serde_json::Value>, // As for `GameConfig::from_json`; the official rules if left out
+    #[serde(default)]
+    monsters: Vec<Option<Monster>>,    // Per seat; missing seats play without a monster
+}
+
+/// A game driven entirely through JSON strings. Errors come back as readable messages.
+pub struct JsonGame {
+    game: Game,
+}
+
+impl JsonGame {
+    /// Sets up a new game from a JSON `Setup`.
+    pub fn new(setup: &str) -> Result<Self, String> {
+        let setup: Setup = serde_json::from_str(setup).map_err(|error| format!("the setup can't be read: {}", error))?;
+        let config = match setup.config {
+            Some(config) => GameConfig::from_json(&config.to_string()).map_err(|error| error.to_string())?,
+            None => GameConfig::official(),
+        };
+        let names: Vec<&str> = setup.players.iter().map(|name| name.as_str()).collect();
+        let mut game = Game::new(&names, setup.seed, config).map_err(|error| error.to_string())?;
+        for (player_id, monster) in (1..).zip(setup.monsters) {
+            if let Some(monster) = monster {
+                if !game.choose_monster(player_id, monster) {
+                    return Err(format!("{} can't be chosen for seat {}", monster.name(), player_id));
+                }
+            }
+        }
+        Ok(JsonGame { game })
+    }
+
+    /// Picks up a game saved with `state`.
+    pub fn load(save: &str) -> Result<Self, String> {
+        Game::from_json(save).map(|game| JsonGame { game }).map_err(|error| error.to_string())
+    }
+
+    /// The whole game as it stands, in the save format (see `Game::to_json`).
+    pub fn state(&self) -> String {
+        self.game.to_json()
+    }
+
+    /// The decision the game is waiting on, or `null` between turns and once it's over.
+    pub fn pending(&self) -> String {
+        serde_json::to_string(&self.game.pending()).expect("Decisions are always serializable")
+    }
+
+    /// Answers the pending decision with `action` (e.g. `{"Keep": [true, true, false, false,
+    /// false, false]}`), or starts the next turn given `null`. Returns the `Progress`.
+    pub fn advance(&mut self, action: &str) -> Result<String, String> {
+        let action: Option<Action> = serde_json::from_str(action).map_err(|error| format!("the action can't be read: {}", error))?;
+        let progress = self.game.advance(action).map_err(|error| error.to_string())?;
+        Ok(serde_json::to_string(&progress).expect("Progress is always serializable"))
+    }
+
+    /// The events logged from position `from` in the history on, as a JSON array, so a
+    /// frontend can animate what happened since it last looked.
+    pub fn events_since(&self, from: usize) -> String {
+        let events = self.game.history.get(from..).unwrap_or_default();
+        serde_json::to_string(events).expect("Events are always serializable")
+    }
+
+    /// Takes the time from `clock`, e.g. one reading `performance.now()`. Without one, games
+    /// in WebAssembly are timed by a `StoppedClock`.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.game.set_clock(clock);
+    }
+
+    /// The game underneath, for anything the JSON methods don't cover.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+}
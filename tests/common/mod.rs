@@ -0,0 +1,37 @@
+//! Helpers for driving practice games, where every roll is set by hand.
+
+use kingoftokyo::{Action, Decision, DieResult, Game, GameConfig, Progress, ShopAction};
+
+/// Dice that score nothing: no triple, no Claws, Hearts or Energy.
+pub const BLANK: [DieResult; 6] = [DieResult::One, DieResult::One, DieResult::Two, DieResult::Two, DieResult::Three, DieResult::Three];
+
+/// A practice game under the official rules with the first `players` of six names.
+pub fn practice_game(players: usize) -> Game {
+    let names = ["Alice", "Bob", "Cara", "Dan", "Eve", "Finn"];
+    let mut game = Game::new(&names[..players], 7, GameConfig::official()).expect("The official rules are valid");
+    assert!(game.enable_practice());
+    game
+}
+
+/// Plays the next turn with `dice`, keeping them, entering Tokyo whenever asked and
+/// answering every yield offer with `yields`. Returns every decision asked on the way.
+pub fn take_turn(game: &mut Game, dice: [DieResult; 6], yields: bool) -> Vec<Decision> {
+    let mut asked = Vec::new();
+    let mut progress = game.advance(None).expect("A turn starts without an action");
+    while let Progress::Decide(decision) = progress {
+        let action = match decision {
+            Decision::SetDice { .. } => Action::SetDice(Some(dice)),
+            Decision::KeepDice { .. } => Action::Keep([true; 6]),
+            Decision::ConcedeTokyo { .. } => Action::Concede(false),
+            Decision::YieldTokyo { .. } => Action::Yield(yields),
+            Decision::EnterTokyo { .. } => Action::Enter(true),
+            Decision::Shop { .. } => Action::Shop(ShopAction::Done),
+            Decision::Purchase { .. } => Action::Purchase(0),
+            Decision::Spectate { .. } => Action::Spectate(false),
+            _ => panic!("unexpected decision {:?}", decision),
+        };
+        asked.push(decision);
+        progress = game.advance(Some(action)).expect("Every scripted answer is legal");
+    }
+    asked
+}
@@ -0,0 +1,40 @@
+//! Configs are checked wherever they come in: config files, with the line and column of
+//! each problem, and saved games, whose rules must be playable before they are resumed.
+
+use kingoftokyo::{Game, GameConfig};
+
+#[test]
+fn config_file_issues_point_at_their_line() {
+    let json = "{\n  \"preset\": \"house-casual\",\n  \"rules\": {\n    \"energy_per_vp\": 0\n  }\n}\n";
+    let error = GameConfig::from_json(json).expect_err("a free Energy Exchange was accepted");
+    assert_eq!(error.issues.len(), 1, "{}", error);
+    assert_eq!(error.issues[0].field, "rules.energy_per_vp");
+    assert_eq!(error.issues[0].position, Some((4, 5)));
+}
+
+#[test]
+fn config_file_syntax_errors_point_at_their_line() {
+    let error = GameConfig::from_json("{\n  \"max_vp\": 15,\n}\n").expect_err("a trailing comma was accepted");
+    assert_eq!(error.issues[0].field, "(file)");
+    assert_eq!(error.issues[0].position, Some((3, 1)));
+}
+
+#[test]
+fn issues_about_fields_the_file_leaves_alone_have_no_position() {
+    let error = GameConfig::from_json("{\"start_hp\": 13}").expect_err("starting above max HP was accepted");
+    assert_eq!(error.issues[0].position, Some((1, 2)));
+    let error = GameConfig::from_json("{\"max_hp\": 5}").expect_err("starting above max HP was accepted");
+    assert_eq!(error.issues[0].field, "start_hp");
+    assert_eq!(error.issues[0].position, None);
+}
+
+#[test]
+fn saves_with_unplayable_rules_are_refused() {
+    // Format 3 is from before saves were checksummed, so it can be edited here
+    let mut save: serde_json::Value = serde_json::from_str(include_str!("fixtures/save_v3.json")).expect("Fixtures are JSON");
+    save["rules"]["energy_per_vp"] = 0.into();
+    let Err(error) = Game::from_legacy_json(&save.to_string()) else {
+        panic!("a save with a free Energy Exchange loaded");
+    };
+    assert!(error.to_string().contains("rules.energy_per_vp"), "{}", error);
+}
@@ -0,0 +1,67 @@
+//! The host checks of the conformance suite, run against small stand-in hosts that get one
+//! thing right or wrong.
+#![cfg(feature = "net")]
+
+use kingoftokyo::{check_host, ClientMessage, Connection, ConformanceReport, Game, GameConfig, HostMessage, EVOLUTIONS, PROTOCOL_VERSION};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+/// Runs `check_host` against a host that welcomes the client to seat 1 in
+/// `protocol_version`, sends it a `State` holding `state(game)` and hangs up. The host's
+/// own seat, 2, holds an evolution the client mustn't see.
+fn check_against(protocol_version: u32, state: fn(&Game) -> String) -> ConformanceReport {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("A local port is free");
+    let address = listener.local_addr().expect("The listener has an address");
+    let host = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("The suite connects");
+        let mut client = Connection::new(stream).expect("The connection can be split");
+        let Ok(ClientMessage::Join { name }) = client.receive() else {
+            panic!("the suite didn't join first");
+        };
+        let mut game = Game::new(&[&name, "Host"], 788, GameConfig::official()).expect("The official rules are valid");
+        game.players[1].evolutions.push(EVOLUTIONS[0]);
+        client.send(&HostMessage::Welcome { player_id: 1, protocol_version }).expect("The suite is listening");
+        client.send(&HostMessage::State { game: state(&game) }).expect("The suite is listening");
+    });
+    let mut connection = Connection::new(TcpStream::connect(address).expect("The host is listening")).expect("The connection can be split");
+    let report = check_host(&mut connection, "Suite", 0);
+    host.join().expect("The stand-in host doesn't panic");
+    report
+}
+
+fn result<'a>(report: &'a ConformanceReport, name: &str) -> &'a Result<(), String> {
+    &report.checks.iter().find(|check| check.name == name).unwrap_or_else(|| panic!("no {} check in {}", name, report)).result
+}
+
+#[test]
+fn hosts_sending_views_pass_the_filtering_check() {
+    let report = check_against(PROTOCOL_VERSION, |game| game.view_json(Some(1)));
+    for check in ["handshake", "schema", "state", "spectator filtering"] {
+        assert_eq!(result(&report, check), &Ok(()), "{}", report);
+    }
+}
+
+#[test]
+fn hosts_sending_saves_fail_the_filtering_check() {
+    let report = check_against(PROTOCOL_VERSION, Game::to_json);
+    let Err(reason) = result(&report, "spectator filtering") else {
+        panic!("a host sending the whole save passed: {}", report);
+    };
+    assert!(reason.contains("seed"), "{}", reason);
+}
+
+#[test]
+fn hosts_showing_another_seats_hand_fail_the_filtering_check() {
+    let report = check_against(PROTOCOL_VERSION, |game| game.view_json(Some(2)));
+    assert!(result(&report, "spectator filtering").is_err(), "{}", report);
+}
+
+#[test]
+fn hosts_on_another_protocol_version_fail_the_handshake() {
+    let report = check_against(0, |game| game.view_json(Some(1)));
+    let Err(reason) = result(&report, "handshake") else {
+        panic!("a host without a protocol version passed the handshake: {}", report);
+    };
+    assert!(reason.contains("protocol version 0"), "{}", reason);
+    assert!(!report.passed());
+}
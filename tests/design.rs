@@ -0,0 +1,85 @@
+//! Custom monsters built with `MonsterDesign`: what `validate` reports about a design, and
+//! what seating one does at the table.
+
+use kingoftokyo::{CardEffect, ConfigError, DieResult, Game, GameEffect, Monster, MonsterDesign, TokyoZone};
+
+mod common;
+
+use common::{practice_game, take_turn, BLANK};
+
+/// Scores 1 VP whenever its owner moves into Tokyo.
+struct Showboat;
+
+impl GameEffect for Showboat {
+    fn name(&self) -> &str {
+        "Showboat"
+    }
+
+    fn on_enter_tokyo(&self, _game: &Game, _owner: u32, _zone: TokyoZone) -> Vec<CardEffect> {
+        vec![CardEffect::GainVp(1)]
+    }
+}
+
+/// An effect answering to whatever name it is given.
+struct Named(&'static str);
+
+impl GameEffect for Named {
+    fn name(&self) -> &str {
+        self.0
+    }
+}
+
+fn fields(error: &ConfigError) -> Vec<&str> {
+    error.issues.iter().map(|issue| issue.field.as_str()).collect()
+}
+
+#[test]
+fn a_seated_design_plays_its_effects() {
+    let design = MonsterDesign::new("Space Penguin", "+1 VP whenever you enter Tokyo").with_effect(Showboat);
+    assert!(design.validate().is_ok());
+    let mut game = practice_game(2);
+    game.add_monster(1, design).expect("The design is valid");
+    assert_eq!(game.designed_monster(1), Some("Space Penguin"));
+    assert_eq!(game.designed_monster(2), None);
+
+    let mut claw = BLANK;
+    claw[0] = DieResult::Claw;
+    take_turn(&mut game, claw, false);
+    assert_eq!(game.tokyo.zone_of(1), Some(TokyoZone::City));
+    assert_eq!(game.players[0].victory_points, 2, "1 VP for entering Tokyo and 1 from Showboat");
+}
+
+#[test]
+fn validate_reports_every_issue_at_once() {
+    let error = MonsterDesign::new("  ", "").validate().expect_err("The design has issues");
+    assert_eq!(error.source, "monster design \"  \"");
+    assert_eq!(fields(&error), vec!["name", "ability", "effects"]);
+
+    let design = MonsterDesign::new("gigazaur", "Roars louder").with_effect(Named("")).with_effect(Named("armor")).with_effect(Showboat);
+    let error = design.validate().expect_err("The design has issues");
+    let issues: Vec<(&str, &str)> = error.issues.iter().map(|issue| (issue.field.as_str(), issue.message.as_str())).collect();
+    assert_eq!(issues, vec![
+        ("name", "gigazaur is an official monster"),
+        ("effects[1]", "the effect has no name"),
+        ("effects[2]", "\"armor\" is the name of a built-in ability"),
+    ]);
+    assert!(error.issues.iter().all(|issue| !issue.help.is_empty() && issue.position.is_none()));
+}
+
+#[test]
+fn seating_rechecks_the_design_and_the_seat() {
+    let mut game = practice_game(3);
+    assert!(game.choose_monster(3, Monster::Gigazaur));
+    let penguin = || MonsterDesign::new("Space Penguin", "Showboats").with_effect(Showboat);
+
+    let error = game.add_monster(1, MonsterDesign::new("Space Penguin", "")).expect_err("The design has no rules text or effects");
+    assert_eq!(fields(&error), vec!["ability", "effects"]);
+    assert_eq!(game.designed_monster(1), None, "a refused design isn't seated");
+
+    game.add_monster(1, penguin()).expect("The design is valid");
+    assert_eq!(fields(&game.add_monster(1, penguin()).expect_err("Alice has a monster")), vec!["player", "name"]);
+    assert_eq!(fields(&game.add_monster(2, penguin()).expect_err("Alice plays the Space Penguin")), vec!["name"]);
+    assert_eq!(fields(&game.add_monster(3, penguin()).expect_err("Cara plays Gigazaur")), vec!["player", "name"]);
+    assert_eq!(fields(&game.add_monster(9, penguin()).expect_err("There is no player 9")), vec!["player", "name"]);
+    assert_eq!(game.effects_of(2).len(), 0, "refused designs add no effects");
+}
@@ -0,0 +1,11 @@
+//! Random games between random bots, with random rules and monsters, must keep every
+//! invariant and end. Debug builds also check the invariants inside the engine after
+//! every step. `cli --fuzz` runs the same fuzzer for longer.
+
+#[test]
+fn random_games_keep_the_invariants_and_end() {
+    match kingoftokyo::fuzz(1000, 781) {
+        Ok(report) => assert_eq!(report.games, 1000),
+        Err(failure) => panic!("{}", failure),
+    }
+}
@@ -0,0 +1,101 @@
+//! Rated games on the bot leaderboard: how a finished game moves ratings and head-to-head
+//! records, which games don't count, and what counts as a fault.
+#![cfg(feature = "net")]
+
+use kingoftokyo::{AgentKind, BotRegistration, Game, GameConfig, Leaderboard, Progress, RatedBot};
+
+const NAMES: [&str; 4] = ["Ada", "Bea", "Cy", "Dot"];
+
+/// A game between `players` random bots, played to the end.
+fn finished_game(players: usize, seed: u64, practice: bool) -> Game {
+    let mut game = Game::new(&NAMES[..players], seed, GameConfig::official()).expect("The official rules are valid");
+    if practice {
+        assert!(game.enable_practice());
+    }
+    let mut bots: Vec<_> = (0..players).map(|seat| AgentKind::Random.build(seed + seat as u64)).collect();
+    loop {
+        let action = game.pending().map(|decision| decision.ask(bots[decision.player_id() as usize - 1].as_mut(), &game));
+        if let Progress::GameOver(_) = game.advance(action).expect("Random bots answer legally") {
+            return game;
+        }
+    }
+}
+
+fn total_rating(board: &Leaderboard) -> f64 {
+    board.bots.iter().map(|bot| bot.rating).sum()
+}
+
+#[test]
+fn rating_changes_cancel_out() {
+    let mut board = Leaderboard::default();
+    for (seed, players) in [(1, 4), (2, 3), (3, 2), (4, 4)] {
+        assert!(board.record(&finished_game(players, seed, false), &[]));
+        let expected = board.bots.len() as f64 * Leaderboard::INITIAL_RATING;
+        assert!((total_rating(&board) - expected).abs() < 1e-9, "ratings drifted to {} after seed {}", total_rating(&board), seed);
+    }
+    assert_eq!(board.games, 4);
+    assert!(board.bots.iter().any(|bot| bot.rating != Leaderboard::INITIAL_RATING));
+}
+
+#[test]
+fn every_pair_at_the_table_meets_once_each_way() {
+    let mut board = Leaderboard::default();
+    let game = finished_game(4, 5, false);
+    assert!(board.record(&game, &[]));
+    let placements = game.final_placements();
+    let placement = |name: &str| placements.iter().find(|&&(id, _)| game.get_player(id).is_some_and(|p| p.name == name)).map(|&(_, place)| place);
+    assert_eq!(board.head_to_head.len(), 4 * 3);
+    for record in &board.head_to_head {
+        let (mine, theirs) = (placement(&record.bot).expect("placed"), placement(&record.opponent).expect("placed"));
+        assert_eq!(record.games, 1);
+        assert_eq!((record.ahead, record.behind), ((mine < theirs) as u32, (mine > theirs) as u32), "{:?}", record);
+        let mirror = board.head_to_head.iter().find(|other| other.bot == record.opponent && other.opponent == record.bot).expect("both ways round");
+        assert_eq!((mirror.ahead, mirror.behind), (record.behind, record.ahead));
+    }
+
+    assert!(board.record(&finished_game(2, 6, false), &[]));
+    let ada_bea = board.head_to_head_of("Ada").into_iter().find(|record| record.opponent == "Bea").expect("Ada met Bea");
+    assert_eq!(ada_bea.games, 2);
+    assert_eq!(board.head_to_head_of("Ada").len(), 3, "Ada met nobody new");
+}
+
+#[test]
+fn practice_and_unfinished_games_dont_count() {
+    let mut board = Leaderboard::default();
+    assert!(!board.record(&finished_game(3, 7, true), &[]));
+    let unfinished = Game::new(&NAMES[..3], 8, GameConfig::official()).expect("The official rules are valid");
+    assert!(!board.record(&unfinished, &[]));
+    assert_eq!(board, Leaderboard::default());
+}
+
+#[test]
+fn faults_are_counted_for_the_bots_named() {
+    let mut board = Leaderboard::default();
+    assert!(board.record(&finished_game(3, 9, false), &["Bea".to_string()]));
+    let faults = |board: &Leaderboard| {
+        let mut faults: Vec<(String, u32)> = board.bots.iter().map(|bot| (bot.name.clone(), bot.faults)).collect();
+        faults.sort();
+        faults
+    };
+    let expected = vec![("Ada".to_string(), 0), ("Bea".to_string(), 1), ("Cy".to_string(), 0)];
+    assert_eq!(faults(&board), expected);
+    assert!(board.record(&finished_game(2, 10, false), &[]));
+    assert_eq!(faults(&board), expected, "a clean game leaves the fault standing");
+}
+
+/// A bot that joins and then never answers is timed out after `BOT_TIMEOUT`, played on by a
+/// random bot, and faulted.
+#[cfg(unix)]
+#[test]
+fn a_stalled_bot_is_faulted() {
+    let command: Vec<String> = ["sh", "-c", r#"echo '{"type":"join","name":"Sleepy"}'; sleep 60"#].iter().map(|arg| arg.to_string()).collect();
+    let sleepy = RatedBot::External(BotRegistration::new("Sleepy", &command).expect("The registration is valid"));
+    let bots = [RatedBot::Builtin(AgentKind::Random), sleepy];
+    let mut board = Leaderboard::default();
+    let game = board.play(&bots, &GameConfig::official(), 10).expect("Two bots make a table");
+    assert!(game.victory().is_some(), "the substitute played the game out");
+    let sleepy = board.bots.iter().find(|bot| bot.name == "Sleepy").expect("Sleepy was rated");
+    assert_eq!((sleepy.games, sleepy.faults), (1, 1));
+    let random = board.bots.iter().find(|bot| bot.name == AgentKind::Random.label()).expect("The random bot was rated");
+    assert_eq!(random.faults, 0);
+}
@@ -0,0 +1,58 @@
+//! Per-monster maximums changed by effects: how they clamp HP, healing and the VP target.
+
+use kingoftokyo::{DieResult, Victory};
+
+mod common;
+
+use common::{practice_game, take_turn, BLANK};
+
+const HEARTS: [DieResult; 6] = [DieResult::Heart; 6];
+const THREE_THREES: [DieResult; 6] = [DieResult::Three, DieResult::Three, DieResult::Three, DieResult::One, DieResult::Two, DieResult::Two];
+
+#[test]
+fn healing_is_capped_at_a_raised_max() {
+    let mut game = practice_game(2);
+    let start_hp = game.start_hp;
+    game.adjust_max_hp(1, 3);
+    let max_hp = game.max_hp + 3;
+    assert_eq!(game.get_player(1).map(|player| (player.hp, player.max_hp)), Some((start_hp, max_hp)));
+    take_turn(&mut game, HEARTS, false);
+    assert!(start_hp + 6 > max_hp, "six Hearts should overshoot the raised max");
+    assert_eq!(game.get_player(1).map(|player| player.hp), Some(max_hp));
+}
+
+#[test]
+fn lowering_the_max_pulls_hp_down() {
+    let mut game = practice_game(2);
+    game.adjust_max_hp(1, -5);
+    let max_hp = game.max_hp - 5;
+    assert_eq!(game.get_player(1).map(|player| (player.hp, player.max_hp)), Some((max_hp, max_hp)));
+    take_turn(&mut game, HEARTS, false);
+    assert_eq!(game.get_player(1).map(|player| player.hp), Some(max_hp));
+    assert_eq!(game.get_player(2).map(|player| player.max_hp), Some(game.max_hp), "another monster's max changed");
+}
+
+#[test]
+fn maximums_never_drop_below_one() {
+    let mut game = practice_game(2);
+    game.adjust_max_hp(1, -100);
+    game.adjust_max_vp(1, -100);
+    let player = game.get_player(1).expect("Alice is seated");
+    assert_eq!((player.hp, player.max_hp, player.max_vp), (1, 1, 1));
+    take_turn(&mut game, BLANK, false);
+    assert_eq!(game.victory(), None, "a 1-VP target was reached with no VP");
+}
+
+#[test]
+fn a_monsters_own_vp_target_decides_its_victory() {
+    let mut game = practice_game(2);
+    game.adjust_max_vp(2, 3 - game.max_vp as i16);
+
+    take_turn(&mut game, THREE_THREES, false); // Alice: 3 VP of 20
+    assert_eq!(game.get_player(1).map(|player| player.victory_points), Some(3));
+    assert_eq!(game.victory(), None);
+
+    take_turn(&mut game, THREE_THREES, false); // Bob: 3 VP of 3
+    assert_eq!(game.victory(), Some(Victory::Points { player_id: 2, max_vp: 3 }));
+    assert!(game.pending().is_none());
+}
@@ -0,0 +1,73 @@
+//! Every narration sentence must read properly in every shipped language: a template for
+//! each key, using the same placeholders as the English one, with nothing left unfilled.
+
+use kingoftokyo::{message, AgentKind, Arg, DieResult, Game, GameConfig, Locale, Message, MessageKey, Progress, TokyoZone};
+use std::collections::BTreeSet;
+
+/// The `{placeholders}` in `template`.
+fn placeholders(template: &str) -> BTreeSet<&str> {
+    template.split('{').skip(1).filter_map(|rest| rest.split_once('}').map(|(name, _)| name)).collect()
+}
+
+/// A value of the kind `placeholder` takes.
+fn sample(placeholder: &str) -> Arg {
+    match placeholder {
+        "player" | "by" | "target" | "monster" | "card" | "preset" | "effect" | "evolution" => Arg::Name("Gigazaur".to_string()),
+        "hp" | "damage" | "amount" | "cost" | "spent" | "times" | "max_hp" => Arg::Number(3),
+        "points" | "vp" | "total" | "max_vp" => Arg::Points(2),
+        "dice" => Arg::Dice([DieResult::Claw, DieResult::Claw, DieResult::One, DieResult::One, DieResult::One, DieResult::Heart]),
+        "face" => Arg::Face(DieResult::Energy),
+        "zone" => Arg::Zone(TokyoZone::Bay),
+        // The console's prompts and reports
+        "key" | "choices" | "list" | "path" | "error" | "reason" | "answer" | "policy" | "phase" | "duration" | "average" | "time"
+        | "luck" | "verdict" | "source" | "evolve" | "share" | "seed" => Arg::Name("x".to_string()),
+        "count" | "number" | "min" | "max" | "energy" | "score" | "goal" | "dealt" | "taken" => Arg::Number(12_345),
+        _ => panic!("no sample for the placeholder {{{}}}: is it a typo?", placeholder),
+    }
+}
+
+fn assert_reads_properly(locale: Locale, key: MessageKey, line: &str) {
+    assert!(!line.trim().is_empty(), "{:?} {:?} is empty", locale, key);
+    assert!(!line.contains('{') && !line.contains('}'), "{:?} {:?} left a placeholder: {}", locale, key, line);
+}
+
+#[test]
+fn every_key_has_a_template_in_every_locale() {
+    for key in MessageKey::ALL {
+        let english = placeholders(Locale::English.template(key));
+        for locale in Locale::ALL {
+            let template = locale.template(key);
+            assert_eq!(placeholders(template), english, "{:?} {:?} uses other placeholders than English", locale, key);
+            let message = Message { key, args: english.iter().map(|&name| (name, sample(name))).collect() };
+            assert_reads_properly(locale, key, &locale.render(&message));
+        }
+    }
+}
+
+#[test]
+fn narrated_games_leave_no_placeholder_unfilled() {
+    let mut seen = BTreeSet::new();
+    for (seed, players) in [(1, 2), (2, 4), (3, 6)] {
+        let mut config = GameConfig::official();
+        config.rules.power_up = true;
+        config.rules.energy_per_vp = Some(5);
+        config.rules.energy_per_heal = Some(2);
+        let names: Vec<String> = (1..=players).map(|seat| format!("Seat {}", seat)).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        let mut game = Game::new(&names, seed, config).expect("The rules are valid");
+        let mut bots: Vec<_> = (0..players).map(|seat| AgentKind::Random.build(seed + seat as u64)).collect();
+        loop {
+            let action = game.pending().map(|decision| decision.ask(bots[decision.player_id() as usize - 1].as_mut(), &game));
+            if let Progress::GameOver(_) = game.advance(action).expect("Random bots answer legally") {
+                break;
+            }
+        }
+        for message in game.history.iter().filter_map(|timed| message(&game, &timed.event)) {
+            seen.insert(format!("{:?}", message.key));
+            for locale in Locale::ALL {
+                assert_reads_properly(locale, message.key, &locale.render(&message));
+            }
+        }
+    }
+    assert!(seen.len() > 10, "too few kinds of sentence came up to tell: {:?}", seen);
+}
@@ -0,0 +1,133 @@
+//! Pre-game predictions: what they are made of, which games they are made for, and how
+//! recorded outcomes tune them.
+#![cfg(all(feature = "ai", feature = "storage"))]
+
+use kingoftokyo::{AgentKind, FileStorage, Game, GameConfig, Monster, Outcome, PredictionLog, Progress, DEFAULT_HISTORY_WEIGHT, MIN_CALIBRATION_GAMES};
+
+const SEATS: [AgentKind; 3] = [AgentKind::Random; 3];
+
+fn new_game(seed: u64) -> Game {
+    let mut game = Game::new(&["Alice", "Bob", "Cara"], seed, GameConfig::official()).expect("The official rules are valid");
+    game.choose_monster(1, Monster::TheKing);
+    game.choose_monster(2, Monster::Kraken);
+    game
+}
+
+fn play_out(game: &mut Game, seed: u64) {
+    let mut bots: Vec<_> = SEATS.iter().enumerate().map(|(seat, kind)| kind.build(seed + seat as u64)).collect();
+    loop {
+        let action = game.pending().map(|decision| decision.ask(bots[decision.player_id() as usize - 1].as_mut(), game));
+        if let Progress::GameOver(_) = game.advance(action).expect("Random bots answer legally") {
+            break;
+        }
+    }
+}
+
+/// An outcome for three seats where Alice won, the simulations gave everyone the same
+/// chance and past results gave `history` to Alice.
+fn alice_won(history: f64) -> Outcome {
+    let rest = (1.0 - history) / 2.0;
+    Outcome { simulated: vec![1.0 / 3.0; 3], history: vec![history, rest, rest], chances: vec![1.0 / 3.0; 3], winner: Some(0) }
+}
+
+#[test]
+fn a_fresh_log_blends_simulations_with_even_shares() {
+    let game = new_game(712);
+    let log = PredictionLog::default();
+    let prediction = log.predict(&game, &SEATS, 50).expect("A game about to start can be predicted");
+    assert_eq!(prediction.history_weight, DEFAULT_HISTORY_WEIGHT);
+    assert_eq!(prediction.seats.iter().map(|seat| (seat.name.as_str(), seat.monster)).collect::<Vec<_>>(),
+               vec![("Alice", Some(Monster::TheKing)), ("Bob", Some(Monster::Kraken)), ("Cara", None)]);
+
+    let decided: f64 = prediction.seats.iter().map(|seat| seat.simulated).sum();
+    assert!(decided > 0.0 && decided <= 1.0, "{}", decided);
+    for seat in &prediction.seats {
+        assert!((seat.history - decided / 3.0).abs() < 1e-9, "{} has a past with no games recorded", seat.name);
+        let blend = (1.0 - DEFAULT_HISTORY_WEIGHT) * seat.simulated + DEFAULT_HISTORY_WEIGHT * seat.history;
+        assert!((seat.chance - blend).abs() < 1e-9);
+    }
+    assert_eq!(log.predict(&game, &SEATS, 50).ok(), Some(prediction), "the same game and log predicted differently");
+}
+
+#[test]
+fn only_ranked_games_about_to_start_are_predicted() {
+    let log = PredictionLog::default();
+    assert!(log.predict(&new_game(1), &SEATS, 0).is_err(), "predicted from no games");
+
+    let mut practice = new_game(1);
+    practice.enable_practice();
+    assert!(log.predict(&practice, &SEATS, 10).is_err(), "predicted a practice game");
+
+    let mut started = new_game(1);
+    play_out(&mut started, 1);
+    let error = log.predict(&started, &SEATS, 10).expect_err("predicted a finished game");
+    assert!(error.to_string().contains("position"), "{}", error);
+}
+
+#[test]
+fn finished_games_add_to_the_records() {
+    let mut log = PredictionLog::default();
+    let mut game = new_game(3);
+    let prediction = log.predict(&game, &SEATS, 10).expect("A game about to start can be predicted");
+    assert!(!log.record(&prediction, &game), "an unfinished game was recorded");
+
+    play_out(&mut game, 3);
+    assert!(!log.record(&prediction, &new_game(4)), "an unfinished game was recorded");
+    let mut other_table = prediction.clone();
+    other_table.seats.swap(0, 1);
+    assert!(!log.record(&other_table, &game), "a prediction for another seating was recorded");
+
+    assert!(log.record(&prediction, &game));
+    assert_eq!(log.players.iter().map(|track| (track.name.as_str(), track.games)).collect::<Vec<_>>(),
+               vec![("Alice", 1), ("Bob", 1), ("Cara", 1)]);
+    assert_eq!(log.monsters.iter().map(|track| track.name.as_str()).collect::<Vec<_>>(), vec!["The King", "Kraken"]);
+    let wins: u32 = log.players.iter().map(|track| track.wins).sum();
+    assert_eq!(wins, log.outcomes[0].winner.is_some() as u32);
+    assert_eq!(log.outcomes[0].chances, prediction.seats.iter().map(|seat| seat.chance).collect::<Vec<_>>());
+}
+
+#[test]
+fn past_winners_are_favoured() {
+    let mut log = PredictionLog::default();
+    for seed in 0..5 {
+        let mut game = new_game(seed);
+        let prediction = log.predict(&game, &SEATS, 5).expect("A game about to start can be predicted");
+        play_out(&mut game, seed);
+        log.record(&prediction, &game);
+    }
+    let best = log.players.iter().max_by_key(|track| track.wins).expect("Three players were recorded");
+    let prediction = log.predict(&new_game(5), &SEATS, 5).expect("A game about to start can be predicted");
+    let seat = prediction.seats.iter().find(|seat| seat.name == best.name).expect("The best player is seated");
+    assert!(prediction.seats.iter().all(|other| other.history <= seat.history), "{} won most but isn't favoured: {}", best.name, prediction);
+}
+
+#[test]
+fn the_history_weight_fits_recorded_outcomes() {
+    let mut log = PredictionLog { outcomes: vec![alice_won(0.9); MIN_CALIBRATION_GAMES - 1], ..PredictionLog::default() };
+    assert_eq!(log.history_weight(), DEFAULT_HISTORY_WEIGHT, "fitted to too few games");
+
+    log.outcomes.push(alice_won(0.9));
+    assert_eq!(log.history_weight(), 1.0, "past results called every game but weren't trusted");
+    log.outcomes = vec![alice_won(0.0); MIN_CALIBRATION_GAMES];
+    assert_eq!(log.history_weight(), 0.0, "past results missed every game but were trusted");
+
+    let calibration = log.calibration();
+    assert_eq!((calibration.games, calibration.history_weight), (MIN_CALIBRATION_GAMES, 0.0));
+    assert!((calibration.brier - calibration.uniform_brier).abs() < 1e-9, "even chances were shown, so they score the same");
+}
+
+#[test]
+fn the_log_is_kept_in_storage() {
+    let dir = std::env::temp_dir().join(format!("kingoftokyo-prediction-{}", std::process::id()));
+    let mut storage = FileStorage::new(&dir);
+    assert_eq!(PredictionLog::load_from(&storage).ok(), Some(PredictionLog::default()));
+
+    let mut log = PredictionLog::default();
+    let mut game = new_game(9);
+    let prediction = log.predict(&game, &SEATS, 5).expect("A game about to start can be predicted");
+    play_out(&mut game, 9);
+    log.record(&prediction, &game);
+    log.save_to(&mut storage).expect("The temp dir is writable");
+    assert_eq!(PredictionLog::load_from(&storage).ok(), Some(log));
+    let _ = std::fs::remove_dir_all(dir);
+}
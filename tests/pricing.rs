@@ -0,0 +1,131 @@
+//! What a card costs a buyer: the printed cost, the economy, then the buyer's effects in
+//! order, held at a floor of 0 unless an effect lowers it, with a price below 0 paid out.
+
+use kingoftokyo::{Action, Card, CostStep, Decision, Game, GameEffect, GameEvent, Progress, ShopAction, STARTER_CARDS};
+
+mod common;
+
+use common::{practice_game, take_turn, BLANK};
+
+fn card(name: &str) -> Card {
+    *STARTER_CARDS.iter().find(|card| card.name == name).expect("The card is in the starter set")
+}
+
+/// Takes `off` Energy off every card, and lets the price fall to `floor` if given.
+struct Markdown {
+    off: i16,
+    floor: Option<i16>,
+}
+
+impl GameEffect for Markdown {
+    fn name(&self) -> &str {
+        "Markdown"
+    }
+
+    fn modify_card_cost(&self, _game: &Game, _owner: u32, _card: &Card, cost: i16) -> i16 {
+        cost - self.off
+    }
+
+    fn card_cost_floor(&self, _game: &Game, _owner: u32, _card: &Card) -> Option<i16> {
+        self.floor
+    }
+}
+
+/// Doubles every price it is handed.
+struct Surge;
+
+impl GameEffect for Surge {
+    fn name(&self) -> &str {
+        "Surge"
+    }
+
+    fn modify_card_cost(&self, _game: &Game, _owner: u32, _card: &Card, cost: i16) -> i16 {
+        cost * 2
+    }
+}
+
+fn step(source: &str, change: i16, cost: i16) -> CostStep {
+    CostStep { source: source.to_string(), change, cost }
+}
+
+#[test]
+fn alien_metabolism_takes_one_off() {
+    let mut game = practice_game(2);
+    let skyscraper = card("Skyscraper");
+    assert_eq!(game.card_cost(1, &skyscraper).total, 6);
+    game.players[0].cards.push(card("Alien Metabolism"));
+    let price = game.card_cost(1, &skyscraper);
+    assert_eq!(price.steps, vec![step("Discount", -1, 5)]);
+    assert_eq!(price.total, 5);
+    assert_eq!(game.card_cost(2, &skyscraper).total, 6, "only the holder gets the discount");
+}
+
+#[test]
+fn steps_run_economy_then_abilities_then_registered_effects() {
+    let mut game = practice_game(2);
+    game.rules.economy.card_cost_percent = 150;
+    game.players[0].cards.push(card("Alien Metabolism"));
+    game.add_effect(1, Box::new(Surge));
+    let price = game.card_cost(1, &card("Skyscraper"));
+    // 6 printed, 9 at 150%, 8 after the discount, then doubled; the other way round it'd be 17
+    assert_eq!(price.steps, vec![step("150% prices", 3, 9), step("Discount", -1, 8), step("Surge", 8, 16)]);
+    assert_eq!(price.total, 16);
+    assert_eq!(price.to_string(), "6 printed, +3 (150% prices), -1 (Discount), +8 (Surge) = 16");
+}
+
+#[test]
+fn prices_stop_at_zero() {
+    let mut game = practice_game(2);
+    game.players[0].cards.push(card("Alien Metabolism"));
+    game.add_effect(1, Box::new(Markdown { off: 4, floor: None }));
+    let price = game.card_cost(1, &card("Corner Store"));
+    assert_eq!(price.steps, vec![step("Discount", -1, 2), step("Markdown", -4, -2), step("floor", 2, 0)]);
+    assert_eq!((price.total, price.energy(), price.refund()), (0, 0, 0));
+}
+
+#[test]
+fn the_lowest_floor_named_wins() {
+    let mut game = practice_game(2);
+    game.add_effect(1, Box::new(Markdown { off: 5, floor: Some(-1) }));
+    game.add_effect(1, Box::new(Markdown { off: 0, floor: Some(-3) }));
+    let price = game.card_cost(1, &card("Corner Store"));
+    assert_eq!(price.steps, vec![step("Markdown", -5, -2)], "-2 is above both floors, so the floor doesn't step in");
+    assert_eq!((price.total, price.energy(), price.refund()), (-2, 0, 2));
+    assert!(price.affordable(0));
+
+    game.add_effect(1, Box::new(Markdown { off: 5, floor: None }));
+    let price = game.card_cost(1, &card("Corner Store"));
+    assert_eq!(price.steps.last(), Some(&step("floor", 4, -3)));
+    assert_eq!(price.total, -3);
+}
+
+#[test]
+fn a_price_below_zero_pays_the_buyer() {
+    let mut game = practice_game(2);
+    game.add_effect(1, Box::new(Markdown { off: 10, floor: Some(-2) }));
+    assert_eq!(game.players[0].energy, 0);
+    let mut bought = false;
+    let mut progress = game.advance(None).expect("A turn starts without an action");
+    while let Progress::Decide(decision) = progress {
+        let action = match decision {
+            Decision::SetDice { .. } => Action::SetDice(Some(BLANK)),
+            Decision::KeepDice { .. } => Action::Keep([true; 6]),
+            Decision::Shop { .. } if !bought => {
+                bought = true;
+                Action::Shop(ShopAction::Buy(0))
+            }
+            Decision::Shop { .. } => Action::Shop(ShopAction::Done),
+            _ => panic!("unexpected decision {:?}", decision),
+        };
+        progress = game.advance(Some(action)).expect("Every scripted answer is legal");
+    }
+    let events: Vec<&GameEvent> = game.history.iter().map(|timed| &timed.event).collect();
+    let purchase = events.iter().position(|event| matches!(event, GameEvent::CardPurchased { player_id: 1, cost: 0, .. }))
+        .expect("Alice bought a card for nothing");
+    let GameEvent::CardPurchased { price, .. } = events[purchase] else { unreachable!() };
+    assert_eq!((price.total, price.refund()), (-2, 2));
+    assert!(events[purchase..].iter().any(|event| matches!(event, GameEvent::EnergyGained { player_id: 1, amount: 2, .. })));
+    assert_eq!(game.players[0].energy, 2);
+    take_turn(&mut game, BLANK, false);
+    assert_eq!(game.players[0].energy, 2, "the payout is Alice's to keep");
+}
@@ -0,0 +1,33 @@
+//! The rules table: every rule says where it comes from, and summaries quote the values the
+//! game is played with rather than the printed ones.
+
+use kingoftokyo::{Game, GameConfig, RuleId};
+
+fn game(config: GameConfig) -> Game {
+    Game::new(&["Alice", "Bob"], 787, config).expect("The rules are valid")
+}
+
+#[test]
+fn every_rule_names_its_section_or_option() {
+    for rule in RuleId::ALL {
+        let reference = rule.reference();
+        assert_ne!(reference, "rulebook", "{} only cites the rulebook", rule.id());
+        assert!(reference.starts_with("rulebook, ") || reference.contains("(rules."), "{}: {}", rule.id(), reference);
+    }
+}
+
+#[test]
+fn summaries_quote_the_games_values() {
+    let official = game(GameConfig::official());
+    assert_eq!(RuleId::Energy.summary(&official), "Each Energy rolled gives 1 Energy cube.");
+    assert_eq!(RuleId::Victory.summary(&official), "The first monster to 20 VP, or the last one standing, wins.");
+
+    let mut config = GameConfig::official();
+    config.max_vp = 15;
+    config.rules.economy.energy_per_die = 2;
+    config.rules.economy.sweep_cost = 3;
+    let variant = game(config);
+    assert_eq!(RuleId::Energy.summary(&variant), "Each Energy rolled gives 2 Energy cubes.");
+    assert_eq!(RuleId::Victory.summary(&variant), "The first monster to 15 VP, or the last one standing, wins.");
+    assert!(RuleId::BuyCards.summary(&variant).contains("pay 3 Energy"), "{}", RuleId::BuyCards.summary(&variant));
+}
@@ -0,0 +1,155 @@
+//! The HTTP API, driven through `ApiServer::handle` with no sockets: opening and joining
+//! tables, playing a game to the end, and what each caller may see of it.
+#![cfg(feature = "net")]
+
+use kingoftokyo::{Agent, AgentKind, ApiResponse, ApiServer, FileStorage, Game};
+use serde_json::{json, Value};
+
+fn call(server: &mut ApiServer, method: &str, path: &str, body: Value) -> ApiResponse {
+    server.handle(method, path, &body.to_string())
+}
+
+fn get(server: &mut ApiServer, path: &str) -> ApiResponse {
+    server.handle("GET", path, "")
+}
+
+/// Opens a two-player table with a fixed seed and seats Ann and Bo at it. Returns their
+/// tokens, by seat.
+fn full_table(server: &mut ApiServer) -> [String; 2] {
+    let created = call(server, "POST", "/games", json!({ "players": 2, "seed": 782, "config": { "preset": "official" } }));
+    assert_eq!((created.status, created.body["game_id"].clone()), (201, json!(1)), "{}", created.body);
+    ["Ann", "Bo"].map(|name| {
+        let joined = call(server, "POST", "/games/1/join", json!({ "name": name }));
+        assert_eq!(joined.status, 201, "{}", joined.body);
+        joined.body["token"].as_str().expect("Joins hand out a token").to_string()
+    })
+}
+
+/// Answers the pending decision for whoever it belongs to, with a random bot.
+fn act(server: &mut ApiServer, tokens: &[String; 2], bots: &mut [Box<dyn Agent>]) -> ApiResponse {
+    let game: &Game = server.game(1).expect("The table is full");
+    let decision = game.pending().expect("The game is waiting on someone");
+    let seat = decision.player_id() as usize - 1;
+    let action = decision.ask(bots[seat].as_mut(), game);
+    call(server, "POST", "/games/1/actions", json!({ "token": tokens[seat], "action": action }))
+}
+
+#[test]
+fn tables_open_fill_up_and_start() {
+    let mut server = ApiServer::new(1);
+    let created = call(&mut server, "POST", "/games", json!({ "players": 2 }));
+    assert_eq!(created.status, 201);
+    assert_eq!(get(&mut server, "/games/1").body["status"], "waiting");
+    assert!(get(&mut server, "/games/1/pending").body["decision"].is_null());
+
+    let first = call(&mut server, "POST", "/games/1/join", json!({ "name": "Ann" }));
+    assert_eq!((first.body["player_id"].clone(), first.body["status"].clone()), (json!(1), json!("waiting")));
+    let second = call(&mut server, "POST", "/games/1/join", json!({ "name": "Bo" }));
+    assert_eq!((second.body["player_id"].clone(), second.body["status"].clone()), (json!(2), json!("playing")));
+    assert_ne!(first.body["token"], second.body["token"]);
+
+    let table = get(&mut server, "/games/1");
+    assert_eq!(table.body["joined"], json!(["Ann", "Bo"]));
+    assert_eq!(get(&mut server, "/games/1/pending").body["player_id"], 1, "Ann goes first");
+}
+
+#[test]
+fn a_full_table_turns_joins_away() {
+    let mut server = ApiServer::new(2);
+    full_table(&mut server);
+    let late = call(&mut server, "POST", "/games/1/join", json!({ "name": "Cy" }));
+    assert_eq!(late.status, 409, "{}", late.body);
+}
+
+#[test]
+fn only_the_deciding_seat_may_act() {
+    let mut server = ApiServer::new(3);
+    let tokens = full_table(&mut server);
+    let action = json!({ "Keep": [true, true, true, true, true, true] });
+
+    let early = call(&mut server, "POST", "/games/1/actions", json!({ "token": tokens[1], "action": action }));
+    assert_eq!(early.status, 409, "Bo acted on Ann's decision: {}", early.body);
+    let forged = call(&mut server, "POST", "/games/1/actions", json!({ "token": "0000000000000000", "action": action }));
+    assert_eq!(forged.status, 403, "{}", forged.body);
+    assert_eq!(get(&mut server, "/games/1?token=0000000000000000").status, 403);
+    assert_eq!(get(&mut server, "/games/1/events?since=0&token=0000000000000000").status, 403);
+}
+
+#[test]
+fn a_finished_game_takes_no_more_actions() {
+    let mut server = ApiServer::new(4);
+    let tokens = full_table(&mut server);
+    let mut bots: Vec<_> = (0..2).map(|seat| AgentKind::Random.build(seat)).collect();
+    while get(&mut server, "/games/1").body["status"] == "playing" {
+        let answer = act(&mut server, &tokens, &mut bots);
+        assert_eq!(answer.status, 200, "{}", answer.body);
+    }
+    let table = get(&mut server, "/games/1");
+    assert_eq!(table.body["status"], "over");
+    assert!(!table.body["victory"].is_null());
+
+    let late = call(&mut server, "POST", "/games/1/actions", json!({ "token": tokens[0], "action": { "Keep": [true, true, true, true, true, true] } }));
+    assert_eq!(late.status, 409, "{}", late.body);
+}
+
+#[test]
+fn events_are_read_on_from_a_position() {
+    let mut server = ApiServer::new(5);
+    let tokens = full_table(&mut server);
+    let mut bots: Vec<_> = (0..2).map(|seat| AgentKind::Random.build(seat)).collect();
+    for _ in 0..6 {
+        act(&mut server, &tokens, &mut bots);
+    }
+    let all = get(&mut server, "/games/1/events").body["events"].as_array().cloned().expect("Events are a list");
+    let logged = server.game(1).expect("The game has started").history.len();
+    assert_eq!(all.len(), logged);
+
+    let since = get(&mut server, "/games/1/events?since=3").body;
+    assert_eq!(since["since"], 3);
+    assert_eq!(since["events"].as_array().cloned(), Some(all[3..].to_vec()));
+    assert!(all.iter().any(|event| !event["rule"].is_null()), "no event cites a rule");
+    assert_eq!(get(&mut server, &format!("/games/1/events?since={}", logged + 10)).body["events"], json!([]));
+    assert_eq!(get(&mut server, "/games/1/events?since=soon").status, 400);
+}
+
+#[test]
+fn the_state_is_a_view_with_only_the_callers_hand() {
+    let mut server = ApiServer::new(6);
+    let tokens = full_table(&mut server);
+    for path in ["/games/1".to_string(), format!("/games/1?token={}", tokens[0])] {
+        let state = get(&mut server, &path).body["state"].clone();
+        assert!(state.get("seed").is_none() && state["shop"].get("deck").is_none(), "{} gives the game away", path);
+        assert!(state["players"].as_array().expect("The state lists the players").iter().all(|player| player.get("rng").is_none()));
+        assert!(Game::from_view_json(&state.to_string()).is_ok(), "{} isn't a view", path);
+    }
+    let spectator = get(&mut server, "/games/1").body["state"].clone();
+    let ann = get(&mut server, &format!("/games/1?token={}", tokens[0])).body["state"].clone();
+    assert!(spectator["players"][0].get("evolutions").is_none(), "a spectator sees Ann's hand");
+    assert!(ann["players"][0].get("evolutions").is_some(), "Ann can't see her own hand");
+    assert!(ann["players"][1].get("evolutions").is_none(), "Ann sees Bo's hand");
+}
+
+#[test]
+fn a_checkpointed_server_carries_on_where_it_stopped() {
+    let mut server = ApiServer::new(7);
+    let tokens = full_table(&mut server);
+    call(&mut server, "POST", "/games", json!({ "players": 3 }));
+    let mut bots: Vec<_> = (0..2).map(|seat| AgentKind::Random.build(seat)).collect();
+    for _ in 0..4 {
+        act(&mut server, &tokens, &mut bots);
+    }
+    let dir = std::env::temp_dir().join(format!("kingoftokyo-server-{}", std::process::id()));
+    let mut storage = FileStorage::new(&dir);
+    assert!(ApiServer::load_from(&storage, "api").expect("The temp dir is readable").is_none());
+    server.save_to(&mut storage, "api").expect("The temp dir is writable");
+
+    let mut restored = ApiServer::load_from(&storage, "api").expect("The checkpoint reads back").expect("The checkpoint was saved");
+    assert_eq!(restored.table_count(), 2);
+    for path in ["/games/1", "/games/1/pending", "/games/2"] {
+        assert_eq!(get(&mut restored, path), get(&mut server, path), "{} changed", path);
+    }
+    assert_eq!(act(&mut restored, &tokens, &mut bots).status, 200, "the seats' tokens were lost");
+    let opened = call(&mut restored, "POST", "/games", json!({ "players": 2 }));
+    assert_eq!(opened.body["game_id"], 3);
+    let _ = std::fs::remove_dir_all(dir);
+}
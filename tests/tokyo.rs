@@ -0,0 +1,112 @@
+//! Tokyo City and Tokyo Bay: who fills which zone, who hits whom, and when holding Tokyo
+//! pays. Games are practice games, so every roll is set by hand.
+
+use kingoftokyo::{Decision, DieResult, Game, GameEvent, TokyoZone};
+
+mod common;
+
+use common::{practice_game, take_turn, BLANK};
+
+fn claws(count: usize) -> [DieResult; 6] {
+    let mut dice = BLANK;
+    dice[..count].fill(DieResult::Claw);
+    dice
+}
+
+fn hp(game: &Game) -> Vec<u8> {
+    game.players.iter().map(|player| player.hp).collect()
+}
+
+fn yield_offers(asked: &[Decision]) -> Vec<(u32, TokyoZone)> {
+    asked.iter()
+        .filter_map(|decision| match *decision {
+            Decision::YieldTokyo { player_id, zone, .. } => Some((player_id, zone)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A five-player game with Alice in Tokyo City and Bob in Tokyo Bay, after one turn each.
+fn both_zones_held() -> Game {
+    let mut game = practice_game(5);
+    take_turn(&mut game, claws(1), false);
+    take_turn(&mut game, claws(1), false);
+    game
+}
+
+#[test]
+fn city_fills_before_bay() {
+    let mut game = practice_game(5);
+    assert_eq!(game.tokyo.first_open_zone(), Some(TokyoZone::City));
+    take_turn(&mut game, claws(1), false);
+    assert_eq!(game.tokyo.zone_of(1), Some(TokyoZone::City));
+    assert_eq!(game.tokyo.first_open_zone(), Some(TokyoZone::Bay));
+    take_turn(&mut game, claws(1), false);
+    assert_eq!(game.tokyo.zone_of(2), Some(TokyoZone::Bay));
+    assert_eq!(game.tokyo.occupants(), vec![(TokyoZone::City, 1), (TokyoZone::Bay, 2)]);
+    assert_eq!(game.tokyo.first_open_zone(), None);
+}
+
+#[test]
+fn outside_attackers_hit_both_occupants() {
+    let mut game = both_zones_held();
+    let before = hp(&game);
+    let asked = take_turn(&mut game, claws(2), false);
+    let after = hp(&game);
+    assert_eq!(after[0], before[0] - 2, "Tokyo City wasn't hit");
+    assert_eq!(after[1], before[1] - 2, "Tokyo Bay wasn't hit");
+    assert_eq!(after[2..], before[2..], "a monster outside Tokyo was hit");
+    assert_eq!(yield_offers(&asked), vec![(1, TokyoZone::City), (2, TokyoZone::Bay)]);
+}
+
+#[test]
+fn occupants_hit_everyone_outside() {
+    let mut game = both_zones_held();
+    for _ in 0..3 {
+        take_turn(&mut game, BLANK, false);
+    }
+    let before = hp(&game);
+    take_turn(&mut game, claws(1), false); // Alice, from Tokyo City
+    let after = hp(&game);
+    assert_eq!(after[..2], before[..2], "an occupant was hit from Tokyo");
+    for seat in 2..5 {
+        assert_eq!(after[seat], before[seat] - 1, "seat {} outside Tokyo wasn't hit", seat + 1);
+    }
+}
+
+#[test]
+fn bay_is_only_used_with_five_or_more_players() {
+    let mut game = practice_game(4);
+    take_turn(&mut game, claws(1), false);
+    assert_eq!(game.tokyo.first_open_zone(), None);
+    let asked = take_turn(&mut game, claws(1), false);
+    assert_eq!(yield_offers(&asked), vec![(1, TokyoZone::City)]);
+    assert_eq!(game.tokyo.occupants(), vec![(TokyoZone::City, 1)]);
+
+    let mut game = practice_game(5);
+    take_turn(&mut game, claws(1), false);
+    assert_eq!(game.tokyo.first_open_zone(), Some(TokyoZone::Bay));
+}
+
+#[test]
+fn holding_tokyo_pays_once_at_the_start_of_each_own_turn() {
+    let mut game = both_zones_held();
+    let held = |game: &Game, id: u32| game.history.iter().filter(|timed| matches!(timed.event, GameEvent::TokyoHeld { player_id, .. } if player_id == id)).count();
+    let vp = |game: &Game, id: u32| game.get_player(id).map(|player| player.victory_points);
+    assert_eq!((held(&game, 1), held(&game, 2)), (0, 0));
+
+    let (alice, bob) = (vp(&game, 1), vp(&game, 2));
+    for _ in 0..3 {
+        take_turn(&mut game, BLANK, false); // Cara, Dan and Eve
+    }
+    assert_eq!((vp(&game, 1), vp(&game, 2)), (alice, bob), "occupants scored on other monsters' turns");
+
+    take_turn(&mut game, BLANK, false); // Alice
+    assert_eq!(held(&game, 1), 1);
+    assert_eq!(vp(&game, 1), alice.map(|vp| vp + game.rules.tokyo_hold_vp));
+    assert_eq!(vp(&game, 2), bob);
+
+    take_turn(&mut game, BLANK, false); // Bob
+    assert_eq!((held(&game, 1), held(&game, 2)), (1, 1));
+    assert_eq!(vp(&game, 2), bob.map(|vp| vp + game.rules.tokyo_hold_vp));
+}
@@ -0,0 +1,99 @@
+//! Views: the game as a client may see it, with nothing that gives away the dice to come
+//! or another seat's hand.
+
+use kingoftokyo::{AgentKind, Game, GameConfig, GameEvent, Monster, Progress};
+use serde_json::Value;
+
+/// A Power Up! game between three random bots, played until someone holds an evolution,
+/// and that someone.
+fn game_with_a_hand() -> (Game, u32) {
+    let mut config = GameConfig::official();
+    config.rules.power_up = true;
+    let mut game = Game::new(&["Alice", "Bob", "Cara"], 764, config).expect("The rules are valid");
+    for (player_id, monster) in (1..).zip([Monster::Gigazaur, Monster::Kraken, Monster::TheKing]) {
+        game.choose_monster(player_id, monster);
+    }
+    let mut bots: Vec<_> = (0..3).map(|seat| AgentKind::Random.build(764 + seat)).collect();
+    loop {
+        if let Some(holder) = game.players.iter().find(|player| !player.evolutions.is_empty()).map(|player| player.id) {
+            return (game, holder);
+        }
+        let action = game.pending().map(|decision| decision.ask(bots[decision.player_id() as usize - 1].as_mut(), &game));
+        if let Progress::GameOver(_) = game.advance(action).expect("Random bots answer legally") {
+            panic!("nobody drew an evolution all game");
+        }
+    }
+}
+
+fn view(game: &Game, viewer: Option<u32>) -> Value {
+    serde_json::from_str(&game.view_json(viewer)).expect("Views are JSON")
+}
+
+#[test]
+fn views_leave_out_the_seed_and_every_rng_stream() {
+    let (game, _) = game_with_a_hand();
+    let save: Value = serde_json::from_str(&game.to_json()).expect("Saves are JSON");
+    let view = view(&game, None);
+    for field in ["seed", "actions", "checksum"] {
+        assert!(save.get(field).is_some(), "the save has no {}", field);
+        assert!(view.get(field).is_none(), "the view carries {}", field);
+    }
+    assert!(view["shop"].get("deck").is_none() && view["shop"].get("rng").is_none(), "the view carries the shop deck");
+    for player in view["players"].as_array().expect("Views list the players") {
+        assert!(player.get("rng").is_none() && player.get("evolution_deck").is_none(), "the view carries a seat's stream or deck");
+    }
+    let started = view["history"][0]["event"]["GameStarted"].clone();
+    assert_eq!(started["seed"], 0, "the history gives the seed away");
+}
+
+#[test]
+fn only_the_viewer_sees_their_evolution_hand() {
+    let (game, holder) = game_with_a_hand();
+    let hand = |view: &Value, player_id: u32| view["players"][player_id as usize - 1].get("evolutions").cloned();
+    let drawn = |view: &Value| -> Vec<String> {
+        view["history"].as_array().expect("Views have a history").iter()
+            .filter_map(|timed| serde_json::from_value::<GameEvent>(timed["event"].clone()).ok())
+            .filter_map(|event| match event {
+                GameEvent::EvolutionDrawn { evolution, .. } => Some(evolution),
+                _ => None,
+            })
+            .collect()
+    };
+    let own = view(&game, Some(holder));
+    assert!(hand(&own, holder).is_some_and(|hand| hand.as_array().is_some_and(|hand| !hand.is_empty())));
+    assert!(drawn(&own).iter().any(|name| !name.is_empty()), "the holder can't see what they drew");
+
+    let other = (holder % 3) + 1;
+    for view in [view(&game, Some(other)), view(&game, None)] {
+        assert_eq!(hand(&view, holder), None, "another seat sees the holder's hand");
+        assert!(drawn(&view).iter().all(|name| name.is_empty()), "the history names another seat's evolution");
+    }
+}
+
+#[test]
+fn views_show_the_game_but_are_not_saves() {
+    let (game, holder) = game_with_a_hand();
+    let json = game.view_json(Some(holder));
+    let shown = Game::from_view_json(&json).expect("A view reads back");
+    assert_eq!(shown.turn, game.turn);
+    assert_eq!(shown.pending(), game.pending());
+    let standings = |game: &Game| game.players.iter().map(|player| (player.hp, player.victory_points, player.energy)).collect::<Vec<_>>();
+    assert_eq!(standings(&shown), standings(&game));
+    assert!(Game::from_json(&json).is_err(), "a view loaded as a save");
+}
+
+#[test]
+fn a_game_view_reads_the_game_it_borrows() {
+    use kingoftokyo::prelude::*;
+    let (game, holder) = game_with_a_hand();
+    let view: GameView = game.view();
+    assert_eq!((view.turn(), view.pending(), view.victory()), (game.turn, game.pending(), None));
+    assert_eq!(view.players().len(), 3);
+    assert_eq!(view.player(holder).map(|player| player.evolutions.len()), Some(game.players[holder as usize - 1].evolutions.len()));
+    assert_eq!(view.player(9).map(|player| player.id), None);
+    assert_eq!(view.living_players().count(), game.living_players().count());
+    assert_eq!(view.shop().len(), game.shop.face_up().len());
+    assert_eq!(view.history().len(), game.history.len());
+    assert_eq!(view.tokyo().occupants(), game.tokyo.occupants());
+    assert!(view.rules().power_up);
+}